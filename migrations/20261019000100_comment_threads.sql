@@ -0,0 +1,2 @@
+CREATE INDEX comment_parent_id_idx ON Comment(parent_id);
+CREATE INDEX comment_cervidae_id_idx ON Comment(cervidae_id, parent_id);
@@ -0,0 +1,56 @@
+CREATE TABLE Review_Stats (
+    cervidae_id UUID PRIMARY KEY,
+    review_count BIGINT DEFAULT 0 NOT NULL,
+    average_danger DOUBLE PRECISION,
+    median_danger DOUBLE PRECISION,
+    histogram INTEGER[] DEFAULT array_fill(0, ARRAY[10]) NOT NULL,
+    last_reviewed_at TIMESTAMP,
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE
+);
+
+CREATE INDEX review_stats_average_danger_idx ON Review_Stats(average_danger);
+CREATE INDEX review_stats_review_count_idx ON Review_Stats(review_count);
+
+/*Recomputes the aggregate row for a single deer. Reviews per deer are few,
+so a full recount is cheaper to reason about than incremental deltas*/
+CREATE FUNCTION refresh_review_stats(deer UUID) RETURNS VOID AS $$
+BEGIN
+    INSERT INTO Review_Stats (cervidae_id, review_count, average_danger, median_danger, histogram, last_reviewed_at)
+    SELECT deer,
+        COUNT(*),
+        AVG(danger_level)::DOUBLE PRECISION,
+        percentile_cont(0.5) WITHIN GROUP (ORDER BY danger_level),
+        ARRAY(
+            SELECT COUNT(Review.danger_level)::INTEGER
+            FROM generate_series(1, 10) AS level
+            LEFT JOIN Review ON Review.cervidae_id = deer AND Review.danger_level = level
+            GROUP BY level ORDER BY level
+        ),
+        MAX(updated_at)
+    FROM Review WHERE cervidae_id = deer
+    ON CONFLICT (cervidae_id) DO UPDATE SET
+        review_count = EXCLUDED.review_count,
+        average_danger = EXCLUDED.average_danger,
+        median_danger = EXCLUDED.median_danger,
+        histogram = EXCLUDED.histogram,
+        last_reviewed_at = EXCLUDED.last_reviewed_at;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION review_stats_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM refresh_review_stats(OLD.cervidae_id);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') AND (TG_OP = 'INSERT' OR NEW.cervidae_id <> OLD.cervidae_id) THEN
+        PERFORM refresh_review_stats(NEW.cervidae_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER review_stats_refresh
+AFTER INSERT OR UPDATE OR DELETE ON Review
+FOR EACH ROW EXECUTE FUNCTION review_stats_trigger();
+
+SELECT refresh_review_stats(id) FROM Cervidae;
@@ -0,0 +1,50 @@
+/*Per-reviewer credibility in [0.5, 1]. Half comes for free, the rest is earned
+through account age (full after a year) and approved deer submissions (full after five)*/
+CREATE VIEW Reviewer_Credibility AS
+SELECT Users.id AS user_id,
+    0.5
+    + 0.25 * LEAST(EXTRACT(EPOCH FROM (NOW() - Users.created_at)) / (365 * 86400), 1)
+    + 0.25 * LEAST(COUNT(Cervidae.id) / 5.0, 1) AS weight
+FROM Users
+LEFT JOIN Cervidae ON Cervidae.created_by = Users.id AND Cervidae.status = 'Approved'
+GROUP BY Users.id;
+
+CREATE TABLE Danger_Score (
+    cervidae_id UUID PRIMARY KEY,
+    score DOUBLE PRECISION NOT NULL,
+    weighted_score DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE
+);
+
+CREATE INDEX danger_score_score_idx ON Danger_Score(score);
+CREATE INDEX danger_score_weighted_score_idx ON Danger_Score(weighted_score);
+
+/*Bayesian average: every deer starts with prior_weight phantom reviews at the
+global mean danger, so a handful of extreme reviews can't dominate the ranking.
+Pass NULL to recompute every deer*/
+CREATE FUNCTION refresh_danger_scores(deer UUID, prior_weight DOUBLE PRECISION) RETURNS VOID AS $$
+DECLARE
+    prior_mean DOUBLE PRECISION;
+BEGIN
+    SELECT COALESCE(AVG(danger_level), 5.5) INTO prior_mean FROM Review;
+    INSERT INTO Danger_Score (cervidae_id, score, weighted_score, updated_at)
+    SELECT Cervidae.id,
+        (prior_weight * prior_mean + COALESCE(SUM(Review.danger_level), 0))
+            / (prior_weight + COUNT(Review.danger_level)),
+        (prior_weight * prior_mean + COALESCE(SUM(Reviewer_Credibility.weight * Review.danger_level), 0))
+            / (prior_weight + COALESCE(SUM(Reviewer_Credibility.weight), 0)),
+        NOW()
+    FROM Cervidae
+    LEFT JOIN Review ON Review.cervidae_id = Cervidae.id
+    LEFT JOIN Reviewer_Credibility ON Reviewer_Credibility.user_id = Review.user_id
+    WHERE deer IS NULL OR Cervidae.id = deer
+    GROUP BY Cervidae.id
+    ON CONFLICT (cervidae_id) DO UPDATE SET
+        score = EXCLUDED.score,
+        weighted_score = EXCLUDED.weighted_score,
+        updated_at = EXCLUDED.updated_at;
+END;
+$$ LANGUAGE plpgsql;
+
+SELECT refresh_danger_scores(NULL, 5);
@@ -0,0 +1,86 @@
+ALTER TABLE Crime_Cervidae ADD COLUMN created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL;
+
+CREATE TYPE Leaderboard_Kind AS ENUM (
+    'DeerKillCount',
+    'DeerDangerScore',
+    'DeerCrimes',
+    'UserSubmissions',
+    'UserReviews'
+);
+
+CREATE TYPE Leaderboard_Period AS ENUM ('AllTime', 'Last30Days', 'Last7Days');
+
+/*subject_id points at Cervidae for deer boards and at Users for user boards*/
+CREATE TABLE Leaderboard_Entry (
+    board Leaderboard_Kind NOT NULL,
+    period Leaderboard_Period NOT NULL,
+    rank INTEGER NOT NULL,
+    subject_id UUID NOT NULL,
+    value DOUBLE PRECISION NOT NULL,
+    refreshed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (board, period, rank)
+);
+
+/*Rebuilds every board for every period. Kill counts carry no history, so the
+windowed kill count boards rank deer that were submitted inside the window*/
+CREATE FUNCTION refresh_leaderboards(board_size INTEGER, prior_weight DOUBLE PRECISION) RETURNS VOID AS $$
+DECLARE
+    board_period Leaderboard_Period;
+    window_start TIMESTAMP;
+    prior_mean DOUBLE PRECISION;
+BEGIN
+    DELETE FROM Leaderboard_Entry;
+    SELECT COALESCE(AVG(danger_level), 5.5) INTO prior_mean FROM Review;
+    FOREACH board_period IN ARRAY enum_range(NULL::Leaderboard_Period) LOOP
+        window_start := CASE board_period
+            WHEN 'Last30Days' THEN NOW() - INTERVAL '30 days'
+            WHEN 'Last7Days' THEN NOW() - INTERVAL '7 days'
+        END;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerKillCount', board_period, ROW_NUMBER() OVER (ORDER BY kill_count DESC, id), id, kill_count
+        FROM Cervidae
+        WHERE status = 'Approved' AND kill_count IS NOT NULL
+            AND (window_start IS NULL OR created_at >= window_start)
+        ORDER BY kill_count DESC, id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerDangerScore', board_period, ROW_NUMBER() OVER (ORDER BY score DESC, cervidae_id), cervidae_id, score
+        FROM (
+            SELECT Review.cervidae_id,
+                (prior_weight * prior_mean + SUM(Reviewer_Credibility.weight * Review.danger_level))
+                    / (prior_weight + SUM(Reviewer_Credibility.weight)) AS score
+            FROM Review
+            JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.status = 'Approved'
+            JOIN Reviewer_Credibility ON Reviewer_Credibility.user_id = Review.user_id
+            WHERE window_start IS NULL OR Review.created_at >= window_start
+            GROUP BY Review.cervidae_id
+        ) AS scores
+        ORDER BY score DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerCrimes', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, cervidae_id), cervidae_id, COUNT(*)
+        FROM Crime_Cervidae
+        JOIN Cervidae ON Cervidae.id = Crime_Cervidae.cervidae_id AND Cervidae.status = 'Approved'
+        WHERE window_start IS NULL OR Crime_Cervidae.created_at >= window_start
+        GROUP BY cervidae_id
+        ORDER BY COUNT(*) DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserSubmissions', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, created_by), created_by, COUNT(*)
+        FROM Cervidae
+        WHERE status = 'Approved' AND (window_start IS NULL OR created_at >= window_start)
+        GROUP BY created_by
+        ORDER BY COUNT(*) DESC, created_by LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserReviews', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, user_id), user_id, COUNT(*)
+        FROM Review
+        WHERE window_start IS NULL OR created_at >= window_start
+        GROUP BY user_id
+        ORDER BY COUNT(*) DESC, user_id LIMIT board_size;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+SELECT refresh_leaderboards(25, 5);
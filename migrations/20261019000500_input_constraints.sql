@@ -0,0 +1,29 @@
+/*Mirrors the validators on the GraphQL input objects so writes that bypass
+the API can't store what the API would reject*/
+CREATE UNIQUE INDEX users_email_lower_key ON Users (LOWER(email));
+
+ALTER TABLE Users
+    ADD CONSTRAINT users_name_check
+        CHECK (char_length(name) BETWEEN 1 AND 100 AND name = btrim(name) AND name IS NFC NORMALIZED),
+    ADD CONSTRAINT users_email_check
+        CHECK (char_length(email) <= 254 AND email ~ '^[^@\s]+@[^@\s]+\.[^@\s]+$');
+
+ALTER TABLE Cervidae
+    ADD CONSTRAINT cervidae_name_check
+        CHECK (char_length(name) BETWEEN 1 AND 200 AND name = btrim(name) AND name IS NFC NORMALIZED),
+    ADD CONSTRAINT cervidae_description_check CHECK (char_length(description) <= 10000),
+    ADD CONSTRAINT cervidae_image_url_check CHECK (char_length(image_url) <= 2048),
+    ADD CONSTRAINT cervidae_kill_count_check CHECK (kill_count >= 0);
+
+ALTER TABLE Review
+    ADD CONSTRAINT review_danger_level_check CHECK (danger_level BETWEEN 1 AND 10),
+    ADD CONSTRAINT review_title_check CHECK (char_length(title) BETWEEN 1 AND 200),
+    ADD CONSTRAINT review_body_check CHECK (char_length(body) BETWEEN 1 AND 10000);
+
+ALTER TABLE Comment
+    ADD CONSTRAINT comment_content_check CHECK (char_length(content) BETWEEN 1 AND 5000);
+
+ALTER TABLE Crime
+    ADD CONSTRAINT crime_name_check
+        CHECK (char_length(name) BETWEEN 1 AND 100 AND name = btrim(name) AND name IS NFC NORMALIZED),
+    ADD CONSTRAINT crime_description_check CHECK (char_length(description) <= 5000);
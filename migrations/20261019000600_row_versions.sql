@@ -0,0 +1,5 @@
+/*Bumped on every edit; updates must name the version they were based on*/
+ALTER TABLE Cervidae ADD COLUMN version INTEGER DEFAULT 1 NOT NULL;
+ALTER TABLE Review ADD COLUMN version INTEGER DEFAULT 1 NOT NULL;
+ALTER TABLE Comment ADD COLUMN version INTEGER DEFAULT 1 NOT NULL;
+ALTER TABLE Crime ADD COLUMN version INTEGER DEFAULT 1 NOT NULL;
@@ -0,0 +1,153 @@
+ALTER TABLE Cervidae
+    ADD COLUMN deleted_at TIMESTAMP,
+    ADD COLUMN deleted_by UUID REFERENCES Users(id) ON DELETE SET NULL;
+ALTER TABLE Review
+    ADD COLUMN deleted_at TIMESTAMP,
+    ADD COLUMN deleted_by UUID REFERENCES Users(id) ON DELETE SET NULL;
+ALTER TABLE Comment
+    ADD COLUMN deleted_at TIMESTAMP,
+    ADD COLUMN deleted_by UUID REFERENCES Users(id) ON DELETE SET NULL;
+ALTER TABLE Crime
+    ADD COLUMN deleted_at TIMESTAMP,
+    ADD COLUMN deleted_by UUID REFERENCES Users(id) ON DELETE SET NULL;
+
+CREATE INDEX cervidae_live_status_idx ON Cervidae(status) WHERE deleted_at IS NULL;
+CREATE INDEX review_live_cervidae_idx ON Review(cervidae_id) WHERE deleted_at IS NULL;
+
+/*A deleted comment stays in its thread as a placeholder for as long as
+something below it is still live*/
+CREATE FUNCTION comment_has_live_descendant(root UUID) RETURNS BOOLEAN AS $$
+    WITH RECURSIVE descendants AS (
+        SELECT id, deleted_at FROM Comment WHERE parent_id = root
+        UNION ALL
+        SELECT Comment.id, Comment.deleted_at FROM Comment
+        JOIN descendants ON Comment.parent_id = descendants.id
+    )
+    SELECT EXISTS (SELECT 1 FROM descendants WHERE deleted_at IS NULL);
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION refresh_review_stats(deer UUID) RETURNS VOID AS $$
+BEGIN
+    INSERT INTO Review_Stats (cervidae_id, review_count, average_danger, median_danger, histogram, last_reviewed_at)
+    SELECT deer,
+        COUNT(*),
+        AVG(danger_level)::DOUBLE PRECISION,
+        percentile_cont(0.5) WITHIN GROUP (ORDER BY danger_level),
+        ARRAY(
+            SELECT COUNT(Review.danger_level)::INTEGER
+            FROM generate_series(1, 10) AS level
+            LEFT JOIN Review ON Review.cervidae_id = deer AND Review.danger_level = level
+                AND Review.deleted_at IS NULL
+            GROUP BY level ORDER BY level
+        ),
+        MAX(updated_at)
+    FROM Review WHERE cervidae_id = deer AND deleted_at IS NULL
+    ON CONFLICT (cervidae_id) DO UPDATE SET
+        review_count = EXCLUDED.review_count,
+        average_danger = EXCLUDED.average_danger,
+        median_danger = EXCLUDED.median_danger,
+        histogram = EXCLUDED.histogram,
+        last_reviewed_at = EXCLUDED.last_reviewed_at;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE VIEW Reviewer_Credibility AS
+SELECT Users.id AS user_id,
+    0.5
+    + 0.25 * LEAST(EXTRACT(EPOCH FROM (NOW() - Users.created_at)) / (365 * 86400), 1)
+    + 0.25 * LEAST(COUNT(Cervidae.id) / 5.0, 1) AS weight
+FROM Users
+LEFT JOIN Cervidae ON Cervidae.created_by = Users.id AND Cervidae.status = 'Approved'
+    AND Cervidae.deleted_at IS NULL
+GROUP BY Users.id;
+
+CREATE OR REPLACE FUNCTION refresh_danger_scores(deer UUID, prior_weight DOUBLE PRECISION) RETURNS VOID AS $$
+DECLARE
+    prior_mean DOUBLE PRECISION;
+BEGIN
+    SELECT COALESCE(AVG(danger_level), 5.5) INTO prior_mean FROM Review WHERE deleted_at IS NULL;
+    INSERT INTO Danger_Score (cervidae_id, score, weighted_score, updated_at)
+    SELECT Cervidae.id,
+        (prior_weight * prior_mean + COALESCE(SUM(Review.danger_level), 0))
+            / (prior_weight + COUNT(Review.danger_level)),
+        (prior_weight * prior_mean + COALESCE(SUM(Reviewer_Credibility.weight * Review.danger_level), 0))
+            / (prior_weight + COALESCE(SUM(Reviewer_Credibility.weight), 0)),
+        NOW()
+    FROM Cervidae
+    LEFT JOIN Review ON Review.cervidae_id = Cervidae.id AND Review.deleted_at IS NULL
+    LEFT JOIN Reviewer_Credibility ON Reviewer_Credibility.user_id = Review.user_id
+    WHERE deer IS NULL OR Cervidae.id = deer
+    GROUP BY Cervidae.id
+    ON CONFLICT (cervidae_id) DO UPDATE SET
+        score = EXCLUDED.score,
+        weighted_score = EXCLUDED.weighted_score,
+        updated_at = EXCLUDED.updated_at;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION refresh_leaderboards(board_size INTEGER, prior_weight DOUBLE PRECISION) RETURNS VOID AS $$
+DECLARE
+    board_period Leaderboard_Period;
+    window_start TIMESTAMP;
+    prior_mean DOUBLE PRECISION;
+BEGIN
+    DELETE FROM Leaderboard_Entry;
+    SELECT COALESCE(AVG(danger_level), 5.5) INTO prior_mean FROM Review WHERE deleted_at IS NULL;
+    FOREACH board_period IN ARRAY enum_range(NULL::Leaderboard_Period) LOOP
+        window_start := CASE board_period
+            WHEN 'Last30Days' THEN NOW() - INTERVAL '30 days'
+            WHEN 'Last7Days' THEN NOW() - INTERVAL '7 days'
+        END;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerKillCount', board_period, ROW_NUMBER() OVER (ORDER BY kill_count DESC, id), id, kill_count
+        FROM Cervidae
+        WHERE status = 'Approved' AND deleted_at IS NULL AND kill_count IS NOT NULL
+            AND (window_start IS NULL OR created_at >= window_start)
+        ORDER BY kill_count DESC, id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerDangerScore', board_period, ROW_NUMBER() OVER (ORDER BY score DESC, cervidae_id), cervidae_id, score
+        FROM (
+            SELECT Review.cervidae_id,
+                (prior_weight * prior_mean + SUM(Reviewer_Credibility.weight * Review.danger_level))
+                    / (prior_weight + SUM(Reviewer_Credibility.weight)) AS score
+            FROM Review
+            JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.status = 'Approved'
+                AND Cervidae.deleted_at IS NULL
+            JOIN Reviewer_Credibility ON Reviewer_Credibility.user_id = Review.user_id
+            WHERE Review.deleted_at IS NULL
+                AND (window_start IS NULL OR Review.created_at >= window_start)
+            GROUP BY Review.cervidae_id
+        ) AS scores
+        ORDER BY score DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerCrimes', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, cervidae_id), cervidae_id, COUNT(*)
+        FROM Crime_Cervidae
+        JOIN Cervidae ON Cervidae.id = Crime_Cervidae.cervidae_id AND Cervidae.status = 'Approved'
+            AND Cervidae.deleted_at IS NULL
+        JOIN Crime ON Crime.id = Crime_Cervidae.crime_id AND Crime.deleted_at IS NULL
+        WHERE window_start IS NULL OR Crime_Cervidae.created_at >= window_start
+        GROUP BY cervidae_id
+        ORDER BY COUNT(*) DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserSubmissions', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, created_by), created_by, COUNT(*)
+        FROM Cervidae
+        WHERE status = 'Approved' AND deleted_at IS NULL
+            AND (window_start IS NULL OR created_at >= window_start)
+        GROUP BY created_by
+        ORDER BY COUNT(*) DESC, created_by LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserReviews', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, user_id), user_id, COUNT(*)
+        FROM Review
+        JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.deleted_at IS NULL
+        WHERE Review.deleted_at IS NULL
+            AND (window_start IS NULL OR Review.created_at >= window_start)
+        GROUP BY user_id
+        ORDER BY COUNT(*) DESC, user_id LIMIT board_size;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
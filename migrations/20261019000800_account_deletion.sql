@@ -0,0 +1,104 @@
+/*Placeholder author that inherits the content of deleted accounts. It has no
+usable password and is kept out of user listings, logins and leaderboards*/
+CREATE FUNCTION ghost_user_id() RETURNS UUID AS $$
+    SELECT '00000000-0000-0000-0000-000000000000'::UUID;
+$$ LANGUAGE sql IMMUTABLE;
+
+INSERT INTO Users (id, name, email, password)
+VALUES (ghost_user_id(), 'Deleted user', 'ghost@cervidae.invalid', '!');
+
+/*Several deleted accounts may have reviewed the same deer, so reviews need a
+key of their own once they can be handed over to the ghost*/
+ALTER TABLE Review ADD COLUMN id UUID DEFAULT gen_random_uuid() NOT NULL;
+ALTER TABLE Review DROP CONSTRAINT review_pkey;
+ALTER TABLE Review ADD PRIMARY KEY (id);
+CREATE UNIQUE INDEX review_author_deer_idx ON Review(user_id, cervidae_id)
+    WHERE user_id <> ghost_user_id();
+
+ALTER TABLE User_Session DROP CONSTRAINT user_session_user_id_fkey;
+ALTER TABLE User_Session ADD FOREIGN KEY (user_id) REFERENCES Users(id) ON DELETE CASCADE;
+CREATE INDEX user_session_user_id_idx ON User_Session(user_id);
+
+CREATE TYPE Account_Content_Policy AS ENUM ('Keep', 'Erase');
+
+/*user_id and requested_by deliberately carry no foreign key: the account they
+point at is gone by the time the row is read*/
+CREATE TABLE Account_Deletion_Audit (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    requested_by UUID NOT NULL,
+    self_service BOOLEAN NOT NULL,
+    content_policy Account_Content_Policy NOT NULL,
+    deer_reassigned BIGINT NOT NULL,
+    reviews_affected BIGINT NOT NULL,
+    comments_affected BIGINT NOT NULL,
+    sessions_revoked BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE OR REPLACE FUNCTION refresh_leaderboards(board_size INTEGER, prior_weight DOUBLE PRECISION) RETURNS VOID AS $$
+DECLARE
+    board_period Leaderboard_Period;
+    window_start TIMESTAMP;
+    prior_mean DOUBLE PRECISION;
+BEGIN
+    DELETE FROM Leaderboard_Entry;
+    SELECT COALESCE(AVG(danger_level), 5.5) INTO prior_mean FROM Review WHERE deleted_at IS NULL;
+    FOREACH board_period IN ARRAY enum_range(NULL::Leaderboard_Period) LOOP
+        window_start := CASE board_period
+            WHEN 'Last30Days' THEN NOW() - INTERVAL '30 days'
+            WHEN 'Last7Days' THEN NOW() - INTERVAL '7 days'
+        END;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerKillCount', board_period, ROW_NUMBER() OVER (ORDER BY kill_count DESC, id), id, kill_count
+        FROM Cervidae
+        WHERE status = 'Approved' AND deleted_at IS NULL AND kill_count IS NOT NULL
+            AND (window_start IS NULL OR created_at >= window_start)
+        ORDER BY kill_count DESC, id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerDangerScore', board_period, ROW_NUMBER() OVER (ORDER BY score DESC, cervidae_id), cervidae_id, score
+        FROM (
+            SELECT Review.cervidae_id,
+                (prior_weight * prior_mean + SUM(Reviewer_Credibility.weight * Review.danger_level))
+                    / (prior_weight + SUM(Reviewer_Credibility.weight)) AS score
+            FROM Review
+            JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.status = 'Approved'
+                AND Cervidae.deleted_at IS NULL
+            JOIN Reviewer_Credibility ON Reviewer_Credibility.user_id = Review.user_id
+            WHERE Review.deleted_at IS NULL
+                AND (window_start IS NULL OR Review.created_at >= window_start)
+            GROUP BY Review.cervidae_id
+        ) AS scores
+        ORDER BY score DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerCrimes', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, cervidae_id), cervidae_id, COUNT(*)
+        FROM Crime_Cervidae
+        JOIN Cervidae ON Cervidae.id = Crime_Cervidae.cervidae_id AND Cervidae.status = 'Approved'
+            AND Cervidae.deleted_at IS NULL
+        JOIN Crime ON Crime.id = Crime_Cervidae.crime_id AND Crime.deleted_at IS NULL
+        WHERE window_start IS NULL OR Crime_Cervidae.created_at >= window_start
+        GROUP BY cervidae_id
+        ORDER BY COUNT(*) DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserSubmissions', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, created_by), created_by, COUNT(*)
+        FROM Cervidae
+        WHERE status = 'Approved' AND deleted_at IS NULL AND created_by <> ghost_user_id()
+            AND (window_start IS NULL OR created_at >= window_start)
+        GROUP BY created_by
+        ORDER BY COUNT(*) DESC, created_by LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserReviews', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, user_id), user_id, COUNT(*)
+        FROM Review
+        JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.deleted_at IS NULL
+        WHERE Review.deleted_at IS NULL AND Review.user_id <> ghost_user_id()
+            AND (window_start IS NULL OR Review.created_at >= window_start)
+        GROUP BY user_id
+        ORDER BY COUNT(*) DESC, user_id LIMIT board_size;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
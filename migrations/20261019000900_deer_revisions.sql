@@ -0,0 +1,37 @@
+/*revision is the deer's version at the time of the edit, so numbers skip
+over version bumps that left the content untouched (moderation, deletion)*/
+CREATE TABLE Cervidae_Revision (
+    id UUID PRIMARY KEY,
+    cervidae_id UUID NOT NULL,
+    revision INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    image_url TEXT,
+    kill_count BIGINT,
+    edited_by UUID NOT NULL,
+    reverted_from UUID,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    UNIQUE (cervidae_id, revision),
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE,
+    FOREIGN KEY (edited_by) REFERENCES Users(id),
+    FOREIGN KEY (reverted_from) REFERENCES Cervidae_Revision(id)
+);
+
+CREATE FUNCTION cervidae_revision_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' OR (NEW.name, NEW.description, NEW.image_url, NEW.kill_count)
+        IS DISTINCT FROM (OLD.name, OLD.description, OLD.image_url, OLD.kill_count) THEN
+        INSERT INTO Cervidae_Revision (id, cervidae_id, revision, name, description, image_url, kill_count, edited_by)
+        VALUES (gen_random_uuid(), NEW.id, NEW.version, NEW.name, NEW.description, NEW.image_url, NEW.kill_count, NEW.updated_by);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER cervidae_revision_record
+AFTER INSERT OR UPDATE ON Cervidae
+FOR EACH ROW EXECUTE FUNCTION cervidae_revision_trigger();
+
+INSERT INTO Cervidae_Revision (id, cervidae_id, revision, name, description, image_url, kill_count, edited_by, created_at)
+SELECT gen_random_uuid(), id, version, name, description, image_url, kill_count, updated_by, COALESCE(updated_at, NOW())
+FROM Cervidae;
@@ -0,0 +1,15 @@
+/*note is a private remark for other moderators; reason is shown to the submitter*/
+CREATE TABLE Deer_Moderation_Event (
+    id UUID PRIMARY KEY,
+    cervidae_id UUID NOT NULL,
+    actor_id UUID NOT NULL,
+    from_status Deer_Entry_Status NOT NULL,
+    to_status Deer_Entry_Status NOT NULL,
+    reason TEXT CHECK (char_length(reason) <= 2000),
+    note TEXT CHECK (char_length(note) <= 2000),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE,
+    FOREIGN KEY (actor_id) REFERENCES Users(id)
+);
+
+CREATE INDEX deer_moderation_event_cervidae_idx ON Deer_Moderation_Event(cervidae_id, created_at);
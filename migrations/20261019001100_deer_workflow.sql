@@ -0,0 +1,3 @@
+/*Allowed moves between these states are enforced by the server, see DEER_TRANSITIONS*/
+ALTER TYPE Deer_Entry_Status ADD VALUE 'Draft' BEFORE 'Pending';
+ALTER TYPE Deer_Entry_Status ADD VALUE 'Archived';
@@ -0,0 +1,103 @@
+/*Author of automatic moderation decisions. Like the ghost user it can't log in*/
+CREATE FUNCTION system_user_id() RETURNS UUID AS $$
+    SELECT '00000000-0000-0000-0000-000000000001'::UUID;
+$$ LANGUAGE sql IMMUTABLE;
+
+INSERT INTO Users (id, name, email, password)
+VALUES (system_user_id(), 'System', 'system@cervidae.invalid', '!');
+
+CREATE FUNCTION is_reserved_user(id UUID) RETURNS BOOLEAN AS $$
+    SELECT id IN (ghost_user_id(), system_user_id());
+$$ LANGUAGE sql IMMUTABLE;
+
+/*Single row holding the quorum rules. A deer is approved as soon as it has
+approve_quorum approve votes and no reject votes. Otherwise, once
+voting_window_hours have passed since it entered Pending, a strict majority of
+at least min_window_votes votes decides*/
+CREATE TABLE Moderation_Config (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    approve_quorum INTEGER DEFAULT 2 NOT NULL CHECK (approve_quorum >= 1),
+    voting_window_hours INTEGER DEFAULT 72 NOT NULL CHECK (voting_window_hours >= 1),
+    min_window_votes INTEGER DEFAULT 1 NOT NULL CHECK (min_window_votes >= 1),
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_by UUID REFERENCES Users(id) ON DELETE SET NULL
+);
+
+INSERT INTO Moderation_Config DEFAULT VALUES;
+
+CREATE TYPE Moderation_Decision AS ENUM ('Approve', 'Reject');
+
+/*voter_id goes NULL when the voter deletes their account; the vote still counts*/
+CREATE TABLE Deer_Moderation_Vote (
+    id UUID PRIMARY KEY,
+    cervidae_id UUID NOT NULL,
+    voter_id UUID,
+    round INTEGER NOT NULL,
+    decision Moderation_Decision NOT NULL,
+    reason TEXT CHECK (char_length(reason) <= 2000),
+    note TEXT CHECK (char_length(note) <= 2000),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    UNIQUE (cervidae_id, voter_id, round),
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE,
+    FOREIGN KEY (voter_id) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+/*The round counts how often a deer has been moved to Pending, so votes cast
+before a resubmission stop counting*/
+CREATE FUNCTION moderation_round(deer UUID) RETURNS INTEGER AS $$
+    SELECT COUNT(*)::INTEGER FROM Deer_Moderation_Event
+    WHERE cervidae_id = deer AND to_status = 'Pending';
+$$ LANGUAGE sql STABLE;
+
+CREATE FUNCTION deer_vote_tally(deer UUID)
+RETURNS TABLE (round INTEGER, opened_at TIMESTAMP, approvals BIGINT, rejections BIGINT) AS $$
+    SELECT moderation_round(deer),
+        COALESCE(
+            (SELECT MAX(created_at) FROM Deer_Moderation_Event
+             WHERE cervidae_id = deer AND to_status = 'Pending'),
+            (SELECT created_at FROM Cervidae WHERE id = deer)
+        ),
+        COUNT(*) FILTER (WHERE decision = 'Approve'),
+        COUNT(*) FILTER (WHERE decision = 'Reject')
+    FROM Deer_Moderation_Vote
+    WHERE cervidae_id = deer AND Deer_Moderation_Vote.round = moderation_round(deer);
+$$ LANGUAGE sql STABLE;
+
+/*Applies the quorum rules to a pending deer and moves it when they are met.
+Returns the new status, or NULL when the vote is still open*/
+CREATE FUNCTION settle_moderation_round(deer UUID) RETURNS Deer_Entry_Status AS $$
+DECLARE
+    config Moderation_Config;
+    tally RECORD;
+    decision Deer_Entry_Status;
+    reason TEXT;
+BEGIN
+    PERFORM 1 FROM Cervidae WHERE id = deer AND status = 'Pending' AND deleted_at IS NULL FOR UPDATE;
+    IF NOT FOUND THEN
+        RETURN NULL;
+    END IF;
+    SELECT * INTO config FROM Moderation_Config;
+    SELECT * INTO tally FROM deer_vote_tally(deer);
+
+    IF tally.rejections = 0 AND tally.approvals >= config.approve_quorum THEN
+        decision := 'Approved';
+    ELSIF NOW() >= tally.opened_at + make_interval(hours => config.voting_window_hours)
+        AND tally.approvals + tally.rejections >= config.min_window_votes
+        AND tally.approvals <> tally.rejections THEN
+        decision := CASE WHEN tally.approvals > tally.rejections THEN 'Approved' ELSE 'Rejected' END;
+    ELSE
+        RETURN NULL;
+    END IF;
+
+    IF decision = 'Rejected' THEN
+        SELECT left(string_agg(Deer_Moderation_Vote.reason, '; ' ORDER BY created_at), 2000) INTO reason
+        FROM Deer_Moderation_Vote
+        WHERE cervidae_id = deer AND round = tally.round AND Deer_Moderation_Vote.decision = 'Reject';
+    END IF;
+    INSERT INTO Deer_Moderation_Event (id, cervidae_id, actor_id, from_status, to_status, reason, note)
+    VALUES (gen_random_uuid(), deer, system_user_id(), 'Pending', decision, reason,
+        format('Settled by vote: %s approve, %s reject', tally.approvals, tally.rejections));
+    UPDATE Cervidae SET status = decision WHERE id = deer;
+    RETURN decision;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,41 @@
+CREATE TYPE Appeal_Status AS ENUM ('Open', 'Upheld', 'Overturned');
+
+/*An appeal targets one rejection. round is the moderation round that produced
+it, so the moderators who voted to reject can be kept off the appeal*/
+CREATE TABLE Deer_Appeal (
+    id UUID PRIMARY KEY,
+    cervidae_id UUID NOT NULL,
+    appellant_id UUID,
+    rejection_event_id UUID NOT NULL UNIQUE,
+    round INTEGER NOT NULL,
+    justification TEXT NOT NULL CHECK (char_length(justification) BETWEEN 1 AND 5000),
+    status Appeal_Status DEFAULT 'Open' NOT NULL,
+    handled_by UUID,
+    resolution TEXT CHECK (char_length(resolution) <= 2000),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    resolved_at TIMESTAMP,
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE,
+    FOREIGN KEY (appellant_id) REFERENCES Users(id) ON DELETE SET NULL,
+    FOREIGN KEY (rejection_event_id) REFERENCES Deer_Moderation_Event(id) ON DELETE CASCADE,
+    FOREIGN KEY (handled_by) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX deer_appeal_cervidae_idx ON Deer_Appeal(cervidae_id, created_at);
+CREATE INDEX deer_appeal_appellant_idx ON Deer_Appeal(appellant_id, created_at);
+CREATE INDEX deer_appeal_queue_idx ON Deer_Appeal(created_at) WHERE status = 'Open';
+
+/*Whoever rejected the deer, directly or by voting to reject in the round,
+may not handle the appeal against it*/
+CREATE FUNCTION took_part_in_rejection(appeal_id UUID, moderator_id UUID) RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1 FROM Deer_Appeal
+        JOIN Deer_Moderation_Event ON Deer_Moderation_Event.id = Deer_Appeal.rejection_event_id
+        WHERE Deer_Appeal.id = appeal_id AND Deer_Moderation_Event.actor_id = moderator_id
+    ) OR EXISTS (
+        SELECT 1 FROM Deer_Appeal
+        JOIN Deer_Moderation_Vote ON Deer_Moderation_Vote.cervidae_id = Deer_Appeal.cervidae_id
+            AND Deer_Moderation_Vote.round = Deer_Appeal.round
+        WHERE Deer_Appeal.id = appeal_id AND Deer_Moderation_Vote.voter_id = moderator_id
+            AND Deer_Moderation_Vote.decision = 'Reject'
+    );
+$$ LANGUAGE sql STABLE;
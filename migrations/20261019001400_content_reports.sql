@@ -0,0 +1,29 @@
+CREATE TYPE Report_Target AS ENUM ('Deer', 'Review', 'Comment', 'User');
+CREATE TYPE Report_Reason AS ENUM ('Spam', 'Harassment', 'Offensive', 'Misinformation', 'Other');
+CREATE TYPE Report_Status AS ENUM ('Open', 'Resolved', 'Dismissed', 'ActionTaken');
+
+/*target_id points into the table named by target_type, so it has no foreign key*/
+CREATE TABLE Content_Report (
+    id UUID PRIMARY KEY,
+    target_type Report_Target NOT NULL,
+    target_id UUID NOT NULL,
+    reporter_id UUID,
+    reason Report_Reason NOT NULL,
+    details TEXT CHECK (char_length(details) <= 2000),
+    status Report_Status DEFAULT 'Open' NOT NULL,
+    handled_by UUID,
+    resolution TEXT CHECK (char_length(resolution) <= 2000),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    resolved_at TIMESTAMP,
+    FOREIGN KEY (reporter_id) REFERENCES Users(id) ON DELETE SET NULL,
+    FOREIGN KEY (handled_by) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+CREATE UNIQUE INDEX content_report_open_reporter_idx ON Content_Report(target_type, target_id, reporter_id)
+    WHERE status = 'Open';
+CREATE INDEX content_report_queue_idx ON Content_Report(created_at, id) WHERE status = 'Open';
+
+/*Reported content is hidden by soft deleting it as the system user, so a
+dismissed report can tell its own hiding apart from a moderator's delete*/
+ALTER TABLE Moderation_Config
+    ADD COLUMN report_hide_threshold INTEGER DEFAULT 3 NOT NULL CHECK (report_hide_threshold >= 1);
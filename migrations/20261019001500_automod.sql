@@ -0,0 +1,49 @@
+CREATE TYPE Automod_Rule_Kind AS ENUM ('WordList', 'Regex', 'LinkLimit', 'NewAccount', 'Duplicate', 'RateLimit');
+CREATE TYPE Automod_Action AS ENUM ('Allow', 'Hold', 'Reject', 'ShadowHide');
+
+/*What threshold and window_minutes mean depends on the kind: the most links
+allowed, the youngest account age in hours, or the posts allowed per window*/
+CREATE TABLE Automod_Rule (
+    id UUID PRIMARY KEY,
+    name VARCHAR(200) NOT NULL CHECK (char_length(name) >= 1),
+    kind Automod_Rule_Kind NOT NULL,
+    action Automod_Action NOT NULL,
+    patterns TEXT[] DEFAULT '{}' NOT NULL,
+    threshold INTEGER CHECK (threshold >= 0),
+    window_minutes INTEGER CHECK (window_minutes >= 1),
+    applies_to_reviews BOOLEAN DEFAULT TRUE NOT NULL,
+    applies_to_comments BOOLEAN DEFAULT TRUE NOT NULL,
+    enabled BOOLEAN DEFAULT TRUE NOT NULL,
+    priority INTEGER DEFAULT 0 NOT NULL,
+    created_by UUID,
+    updated_by UUID,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (created_by) REFERENCES Users(id) ON DELETE SET NULL,
+    FOREIGN KEY (updated_by) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+/*Rejected writes leave no row behind, so target_id stays empty and the
+excerpt is all that is kept of them*/
+CREATE TABLE Automod_Hit (
+    id UUID PRIMARY KEY,
+    rule_id UUID,
+    rule_name VARCHAR(200) NOT NULL,
+    action Automod_Action NOT NULL,
+    target_type Report_Target NOT NULL,
+    target_id UUID,
+    user_id UUID,
+    matched TEXT NOT NULL,
+    excerpt TEXT NOT NULL,
+    reviewed_by UUID,
+    reviewed_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (rule_id) REFERENCES Automod_Rule(id) ON DELETE SET NULL,
+    FOREIGN KEY (user_id) REFERENCES Users(id) ON DELETE SET NULL,
+    FOREIGN KEY (reviewed_by) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX automod_hit_target_idx ON Automod_Hit(target_type, target_id);
+CREATE INDEX automod_hit_hold_idx ON Automod_Hit(created_at) WHERE action = 'Hold' AND reviewed_at IS NULL;
+CREATE INDEX comment_user_created_idx ON Comment(user_id, created_at);
+CREATE INDEX review_user_created_idx ON Review(user_id, created_at);
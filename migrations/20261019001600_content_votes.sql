@@ -0,0 +1,103 @@
+/*Counts are kept on the rows so pages can be ordered and keyset-paged by them.
+controversy is highest for many votes split evenly between the two sides*/
+ALTER TABLE Review
+    ADD COLUMN helpful_votes INTEGER DEFAULT 0 NOT NULL,
+    ADD COLUMN unhelpful_votes INTEGER DEFAULT 0 NOT NULL,
+    ADD COLUMN score INTEGER NOT NULL GENERATED ALWAYS AS (helpful_votes - unhelpful_votes) STORED,
+    ADD COLUMN controversy DOUBLE PRECISION NOT NULL GENERATED ALWAYS AS (
+        CASE WHEN helpful_votes = 0 OR unhelpful_votes = 0 THEN 0
+        ELSE power((helpful_votes + unhelpful_votes)::DOUBLE PRECISION,
+            LEAST(helpful_votes, unhelpful_votes)::DOUBLE PRECISION / GREATEST(helpful_votes, unhelpful_votes))
+        END
+    ) STORED;
+
+ALTER TABLE Comment
+    ADD COLUMN upvotes INTEGER DEFAULT 0 NOT NULL,
+    ADD COLUMN downvotes INTEGER DEFAULT 0 NOT NULL,
+    ADD COLUMN score INTEGER NOT NULL GENERATED ALWAYS AS (upvotes - downvotes) STORED,
+    ADD COLUMN controversy DOUBLE PRECISION NOT NULL GENERATED ALWAYS AS (
+        CASE WHEN upvotes = 0 OR downvotes = 0 THEN 0
+        ELSE power((upvotes + downvotes)::DOUBLE PRECISION,
+            LEAST(upvotes, downvotes)::DOUBLE PRECISION / GREATEST(upvotes, downvotes))
+        END
+    ) STORED;
+
+CREATE TABLE Review_Vote (
+    user_id UUID NOT NULL,
+    review_id UUID NOT NULL,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (user_id, review_id),
+    FOREIGN KEY (user_id) REFERENCES Users(id) ON DELETE CASCADE,
+    FOREIGN KEY (review_id) REFERENCES Review(id) ON DELETE CASCADE
+);
+
+CREATE TABLE Comment_Vote (
+    user_id UUID NOT NULL,
+    comment_id UUID NOT NULL,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (user_id, comment_id),
+    FOREIGN KEY (user_id) REFERENCES Users(id) ON DELETE CASCADE,
+    FOREIGN KEY (comment_id) REFERENCES Comment(id) ON DELETE CASCADE
+);
+
+CREATE INDEX review_vote_review_idx ON Review_Vote(review_id);
+CREATE INDEX comment_vote_comment_idx ON Comment_Vote(comment_id);
+
+CREATE FUNCTION count_review_votes(target UUID) RETURNS VOID AS $$
+    UPDATE Review SET
+        helpful_votes = (SELECT COUNT(*) FROM Review_Vote WHERE review_id = target AND value = 1),
+        unhelpful_votes = (SELECT COUNT(*) FROM Review_Vote WHERE review_id = target AND value = -1)
+    WHERE id = target;
+$$ LANGUAGE sql;
+
+CREATE FUNCTION review_vote_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM count_review_votes(OLD.review_id);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM count_review_votes(NEW.review_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER review_vote_count
+AFTER INSERT OR UPDATE OR DELETE ON Review_Vote
+FOR EACH ROW EXECUTE FUNCTION review_vote_trigger();
+
+CREATE FUNCTION count_comment_votes(target UUID) RETURNS VOID AS $$
+    UPDATE Comment SET
+        upvotes = (SELECT COUNT(*) FROM Comment_Vote WHERE comment_id = target AND value = 1),
+        downvotes = (SELECT COUNT(*) FROM Comment_Vote WHERE comment_id = target AND value = -1)
+    WHERE id = target;
+$$ LANGUAGE sql;
+
+CREATE FUNCTION comment_vote_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM count_comment_votes(OLD.comment_id);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM count_comment_votes(NEW.comment_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER comment_vote_count
+AFTER INSERT OR UPDATE OR DELETE ON Comment_Vote
+FOR EACH ROW EXECUTE FUNCTION comment_vote_trigger();
+
+/*Vote counts change far more often than anything the review stats read*/
+DROP TRIGGER review_stats_refresh ON Review;
+CREATE TRIGGER review_stats_refresh
+AFTER INSERT OR DELETE OR UPDATE OF cervidae_id, danger_level, created_at, updated_at, deleted_at ON Review
+FOR EACH ROW EXECUTE FUNCTION review_stats_trigger();
+
+CREATE INDEX review_top_idx ON Review(cervidae_id, score DESC, created_at DESC, id DESC) WHERE deleted_at IS NULL;
+CREATE INDEX comment_top_idx ON Comment(parent_id, score DESC, created_at DESC, id DESC);
@@ -0,0 +1,88 @@
+CREATE TYPE Taxon_Rank AS ENUM ('Family', 'Subfamily', 'Genus', 'Species', 'Subspecies');
+
+/*Only families sit at the root, and every other taxon hangs under one of a
+higher rank, which also rules out cycles*/
+CREATE TABLE Taxon (
+    id UUID PRIMARY KEY,
+    parent_id UUID,
+    rank Taxon_Rank NOT NULL,
+    scientific_name VARCHAR(200) NOT NULL UNIQUE CHECK (char_length(scientific_name) >= 1),
+    common_name VARCHAR(200),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (parent_id) REFERENCES Taxon(id) ON DELETE RESTRICT,
+    CHECK ((rank = 'Family') = (parent_id IS NULL))
+);
+
+CREATE INDEX taxon_parent_idx ON Taxon(parent_id);
+
+CREATE FUNCTION taxon_rank_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.parent_id IS NOT NULL AND (SELECT rank FROM Taxon WHERE id = NEW.parent_id) >= NEW.rank THEN
+        RAISE EXCEPTION 'A % must sit under a taxon of higher rank', NEW.rank
+            USING ERRCODE = 'check_violation';
+    END IF;
+    IF TG_OP = 'UPDATE' AND NEW.rank <> OLD.rank THEN
+        RAISE EXCEPTION 'The rank of a taxon cannot change' USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER taxon_rank_check
+BEFORE INSERT OR UPDATE ON Taxon
+FOR EACH ROW EXECUTE FUNCTION taxon_rank_trigger();
+
+CREATE FUNCTION taxon_subtree(root UUID) RETURNS SETOF UUID AS $$
+    WITH RECURSIVE subtree AS (
+        SELECT id FROM Taxon WHERE id = root
+        UNION ALL
+        SELECT Taxon.id FROM Taxon JOIN subtree ON Taxon.parent_id = subtree.id
+    )
+    SELECT id FROM subtree;
+$$ LANGUAGE sql STABLE;
+
+ALTER TABLE Cervidae ADD COLUMN taxon_id UUID REFERENCES Taxon(id) ON DELETE SET NULL;
+CREATE INDEX cervidae_taxon_idx ON Cervidae(taxon_id);
+
+CREATE TABLE Deer_Alias (
+    id UUID PRIMARY KEY,
+    cervidae_id UUID NOT NULL,
+    alias VARCHAR(200) NOT NULL CHECK (char_length(alias) >= 1),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX deer_alias_unique_idx ON Deer_Alias(cervidae_id, lower(alias));
+CREATE INDEX deer_alias_lookup_idx ON Deer_Alias(lower(alias));
+
+INSERT INTO Taxon (id, parent_id, rank, scientific_name, common_name) VALUES
+    ('6a1d0000-0000-4000-8000-000000000001', NULL, 'Family', 'Cervidae', 'Deer'),
+    ('6a1d0000-0000-4000-8000-000000000002', '6a1d0000-0000-4000-8000-000000000001', 'Subfamily', 'Cervinae', 'Old World deer'),
+    ('6a1d0000-0000-4000-8000-000000000003', '6a1d0000-0000-4000-8000-000000000001', 'Subfamily', 'Capreolinae', 'New World deer'),
+    ('6a1d0000-0000-4000-8000-000000000004', '6a1d0000-0000-4000-8000-000000000002', 'Genus', 'Cervus', NULL),
+    ('6a1d0000-0000-4000-8000-000000000005', '6a1d0000-0000-4000-8000-000000000002', 'Genus', 'Dama', 'Fallow deer'),
+    ('6a1d0000-0000-4000-8000-000000000006', '6a1d0000-0000-4000-8000-000000000002', 'Genus', 'Axis', NULL),
+    ('6a1d0000-0000-4000-8000-000000000007', '6a1d0000-0000-4000-8000-000000000002', 'Genus', 'Muntiacus', 'Muntjacs'),
+    ('6a1d0000-0000-4000-8000-000000000008', '6a1d0000-0000-4000-8000-000000000003', 'Genus', 'Capreolus', NULL),
+    ('6a1d0000-0000-4000-8000-000000000009', '6a1d0000-0000-4000-8000-000000000003', 'Genus', 'Odocoileus', NULL),
+    ('6a1d0000-0000-4000-8000-00000000000a', '6a1d0000-0000-4000-8000-000000000003', 'Genus', 'Rangifer', NULL),
+    ('6a1d0000-0000-4000-8000-00000000000b', '6a1d0000-0000-4000-8000-000000000003', 'Genus', 'Alces', NULL),
+    ('6a1d0000-0000-4000-8000-00000000000c', '6a1d0000-0000-4000-8000-000000000004', 'Species', 'Cervus elaphus', 'Red deer'),
+    ('6a1d0000-0000-4000-8000-00000000000d', '6a1d0000-0000-4000-8000-000000000004', 'Species', 'Cervus canadensis', 'Elk'),
+    ('6a1d0000-0000-4000-8000-00000000000e', '6a1d0000-0000-4000-8000-000000000004', 'Species', 'Cervus nippon', 'Sika deer'),
+    ('6a1d0000-0000-4000-8000-00000000000f', '6a1d0000-0000-4000-8000-000000000005', 'Species', 'Dama dama', 'European fallow deer'),
+    ('6a1d0000-0000-4000-8000-000000000010', '6a1d0000-0000-4000-8000-000000000006', 'Species', 'Axis axis', 'Chital'),
+    ('6a1d0000-0000-4000-8000-000000000011', '6a1d0000-0000-4000-8000-000000000007', 'Species', 'Muntiacus reevesi', 'Reeves''s muntjac'),
+    ('6a1d0000-0000-4000-8000-000000000012', '6a1d0000-0000-4000-8000-000000000008', 'Species', 'Capreolus capreolus', 'Roe deer'),
+    ('6a1d0000-0000-4000-8000-000000000013', '6a1d0000-0000-4000-8000-000000000009', 'Species', 'Odocoileus virginianus', 'White-tailed deer'),
+    ('6a1d0000-0000-4000-8000-000000000014', '6a1d0000-0000-4000-8000-000000000009', 'Species', 'Odocoileus hemionus', 'Mule deer'),
+    ('6a1d0000-0000-4000-8000-000000000015', '6a1d0000-0000-4000-8000-00000000000a', 'Species', 'Rangifer tarandus', 'Reindeer'),
+    ('6a1d0000-0000-4000-8000-000000000016', '6a1d0000-0000-4000-8000-00000000000b', 'Species', 'Alces alces', 'Moose');
+
+UPDATE Cervidae SET taxon_id = Taxon.id
+FROM Taxon WHERE lower(Taxon.scientific_name) = lower(Cervidae.name);
+
+INSERT INTO Deer_Alias (id, cervidae_id, alias)
+SELECT gen_random_uuid(), Cervidae.id, Taxon.common_name
+FROM Cervidae JOIN Taxon ON Taxon.id = Cervidae.taxon_id
+WHERE Taxon.common_name IS NOT NULL;
@@ -0,0 +1,44 @@
+CREATE TYPE Sighting_Status AS ENUM ('Unverified', 'Verified', 'Disputed');
+
+CREATE TABLE Sighting (
+    id UUID PRIMARY KEY,
+    cervidae_id UUID NOT NULL,
+    reporter_id UUID,
+    latitude DOUBLE PRECISION NOT NULL CHECK (latitude BETWEEN -90 AND 90),
+    longitude DOUBLE PRECISION NOT NULL CHECK (longitude BETWEEN -180 AND 180),
+    accuracy_meters DOUBLE PRECISION CHECK (accuracy_meters > 0),
+    observed_at TIMESTAMP NOT NULL,
+    notes TEXT CHECK (char_length(notes) <= 2000),
+    photo_url VARCHAR(2048),
+    status Sighting_Status DEFAULT 'Unverified' NOT NULL,
+    verified_by UUID,
+    verified_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE,
+    FOREIGN KEY (reporter_id) REFERENCES Users(id) ON DELETE SET NULL,
+    FOREIGN KEY (verified_by) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX sighting_cervidae_idx ON Sighting(cervidae_id, observed_at);
+CREATE INDEX sighting_reporter_idx ON Sighting(reporter_id, created_at);
+CREATE INDEX sighting_location_idx ON Sighting(latitude, longitude);
+
+/*One dispute per user; a sighting stays disputed until a moderator verifies it*/
+CREATE TABLE Sighting_Dispute (
+    sighting_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    reason TEXT NOT NULL CHECK (char_length(reason) BETWEEN 1 AND 2000),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (sighting_id, user_id),
+    FOREIGN KEY (sighting_id) REFERENCES Sighting(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES Users(id) ON DELETE CASCADE
+);
+
+/*Great-circle distance in kilometres on a spherical earth, good to about 0.5%*/
+CREATE FUNCTION haversine_km(lat1 DOUBLE PRECISION, lon1 DOUBLE PRECISION,
+    lat2 DOUBLE PRECISION, lon2 DOUBLE PRECISION) RETURNS DOUBLE PRECISION AS $$
+    SELECT 2 * 6371.0088 * asin(LEAST(1, sqrt(
+        power(sin(radians(lat2 - lat1) / 2), 2)
+        + cos(radians(lat1)) * cos(radians(lat2)) * power(sin(radians(lon2 - lon1) / 2), 2)
+    )));
+$$ LANGUAGE sql IMMUTABLE PARALLEL SAFE;
@@ -0,0 +1,59 @@
+/*An incident is one dated occurrence of a crime by a deer. Only verified
+incidents of live crimes count towards a derived kill count*/
+CREATE TABLE Incident (
+    id UUID PRIMARY KEY,
+    cervidae_id UUID NOT NULL,
+    crime_id UUID NOT NULL,
+    occurred_at TIMESTAMP NOT NULL,
+    location_name VARCHAR(200),
+    latitude DOUBLE PRECISION CHECK (latitude BETWEEN -90 AND 90),
+    longitude DOUBLE PRECISION CHECK (longitude BETWEEN -180 AND 180),
+    casualties INTEGER DEFAULT 0 NOT NULL CHECK (casualties >= 0),
+    description TEXT CHECK (char_length(description) <= 5000),
+    sources TEXT[] DEFAULT '{}' NOT NULL CHECK (cardinality(sources) <= 20),
+    created_by UUID,
+    verified_by UUID,
+    verified_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CHECK ((latitude IS NULL) = (longitude IS NULL)),
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE,
+    FOREIGN KEY (crime_id) REFERENCES Crime(id) ON DELETE CASCADE,
+    FOREIGN KEY (created_by) REFERENCES Users(id) ON DELETE SET NULL,
+    FOREIGN KEY (verified_by) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX incident_cervidae_idx ON Incident(cervidae_id, occurred_at);
+CREATE INDEX incident_crime_idx ON Incident(crime_id, occurred_at);
+
+-- An incident implies the deer is linked to the crime
+CREATE FUNCTION incident_link_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO Crime_Cervidae (crime_id, cervidae_id)
+    VALUES (NEW.crime_id, NEW.cervidae_id)
+    ON CONFLICT DO NOTHING;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER incident_link
+AFTER INSERT OR UPDATE OF crime_id, cervidae_id ON Incident
+FOR EACH ROW EXECUTE FUNCTION incident_link_trigger();
+
+ALTER TABLE Cervidae ADD COLUMN kill_count_derived BOOLEAN DEFAULT FALSE NOT NULL;
+
+/*Brings a derived kill count in line with the verified incidents, recording
+the change as an edit by actor, and returns the deer if it changed.
+Hand-maintained counts are left alone*/
+CREATE FUNCTION sync_kill_count(deer UUID, actor UUID) RETURNS SETOF Cervidae AS $$
+    UPDATE Cervidae SET kill_count = derived.total, updated_by = actor,
+        updated_at = NOW(), version = version + 1
+    FROM (
+        SELECT COALESCE(SUM(casualties), 0)::BIGINT AS total FROM Incident
+        JOIN Crime ON Crime.id = Incident.crime_id AND Crime.deleted_at IS NULL
+        WHERE cervidae_id = deer AND verified_at IS NOT NULL
+    ) AS derived
+    WHERE Cervidae.id = deer AND Cervidae.kill_count_derived
+        AND Cervidae.kill_count IS DISTINCT FROM derived.total
+    RETURNING Cervidae.*;
+$$ LANGUAGE sql;
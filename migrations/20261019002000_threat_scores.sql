@@ -0,0 +1,30 @@
+CREATE TYPE Crime_Category AS ENUM ('Violent', 'Property', 'State', 'Other');
+
+ALTER TABLE Crime
+    ADD COLUMN severity INTEGER DEFAULT 5 NOT NULL CHECK (severity BETWEEN 1 AND 10),
+    ADD COLUMN category Crime_Category DEFAULT 'Other' NOT NULL;
+
+UPDATE Crime SET severity = 10, category = 'Violent' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb3d';
+UPDATE Crime SET severity = 6, category = 'Violent' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb3e';
+UPDATE Crime SET severity = 3, category = 'Property' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb3f';
+UPDATE Crime SET severity = 7, category = 'Property' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb40';
+UPDATE Crime SET severity = 10, category = 'State' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb41';
+UPDATE Crime SET severity = 9, category = 'State' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb42';
+
+/*Each factor of the threat score is scaled to 0..1 and the score is their
+weighted mean, out of 100. Only the ratios between the weights matter*/
+CREATE TABLE Threat_Config (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    severity_weight DOUBLE PRECISION DEFAULT 0.35 NOT NULL CHECK (severity_weight >= 0),
+    frequency_weight DOUBLE PRECISION DEFAULT 0.2 NOT NULL CHECK (frequency_weight >= 0),
+    kill_weight DOUBLE PRECISION DEFAULT 0.25 NOT NULL CHECK (kill_weight >= 0),
+    danger_weight DOUBLE PRECISION DEFAULT 0.2 NOT NULL CHECK (danger_weight >= 0),
+    frequency_window_days INTEGER DEFAULT 365 NOT NULL CHECK (frequency_window_days >= 1),
+    frequency_half_count INTEGER DEFAULT 3 NOT NULL CHECK (frequency_half_count >= 1),
+    kill_count_cap BIGINT DEFAULT 10000000 NOT NULL CHECK (kill_count_cap >= 1),
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_by UUID REFERENCES Users(id) ON DELETE SET NULL,
+    CHECK (severity_weight + frequency_weight + kill_weight + danger_weight > 0)
+);
+
+INSERT INTO Threat_Config DEFAULT VALUES;
@@ -0,0 +1,90 @@
+-- The flat category enum gives way to a table of the same name
+ALTER TYPE Crime_Category RENAME TO Crime_Category_Kind;
+
+/*Categories nest, so a search for a category also finds the crimes filed
+under any of its subcategories*/
+CREATE TABLE Crime_Category (
+    id UUID PRIMARY KEY,
+    parent_id UUID,
+    slug VARCHAR(100) NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    name VARCHAR(100) NOT NULL CHECK (char_length(name) >= 1),
+    description TEXT CHECK (char_length(description) <= 5000),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (parent_id) REFERENCES Crime_Category(id) ON DELETE RESTRICT,
+    CHECK (parent_id <> id)
+);
+
+CREATE INDEX crime_category_parent_idx ON Crime_Category(parent_id);
+
+CREATE FUNCTION crime_category_subtree(root UUID) RETURNS SETOF UUID AS $$
+    WITH RECURSIVE subtree AS (
+        SELECT id FROM Crime_Category WHERE id = root
+        UNION
+        SELECT Crime_Category.id FROM Crime_Category JOIN subtree ON Crime_Category.parent_id = subtree.id
+    )
+    SELECT id FROM subtree;
+$$ LANGUAGE sql STABLE;
+
+-- Reparenting a category under its own subtree would detach it from the roots
+CREATE FUNCTION crime_category_cycle_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.parent_id IS NOT NULL AND NEW.parent_id IN (SELECT crime_category_subtree(NEW.id)) THEN
+        RAISE EXCEPTION 'A category cannot sit under one of its own subcategories'
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER crime_category_cycle_check
+BEFORE UPDATE OF parent_id ON Crime_Category
+FOR EACH ROW EXECUTE FUNCTION crime_category_cycle_trigger();
+
+INSERT INTO Crime_Category (id, parent_id, slug, name) VALUES
+    ('c7a70000-0000-4000-8000-000000000001', NULL, 'violent', 'Violent'),
+    ('c7a70000-0000-4000-8000-000000000002', NULL, 'property', 'Property'),
+    ('c7a70000-0000-4000-8000-000000000003', NULL, 'state', 'State'),
+    ('c7a70000-0000-4000-8000-000000000004', NULL, 'other', 'Other'),
+    ('c7a70000-0000-4000-8000-000000000011', 'c7a70000-0000-4000-8000-000000000001', 'homicide', 'Homicide'),
+    ('c7a70000-0000-4000-8000-000000000012', 'c7a70000-0000-4000-8000-000000000001', 'assault', 'Assault'),
+    ('c7a70000-0000-4000-8000-000000000021', 'c7a70000-0000-4000-8000-000000000002', 'theft', 'Theft'),
+    ('c7a70000-0000-4000-8000-000000000022', 'c7a70000-0000-4000-8000-000000000002', 'property-damage', 'Property damage');
+
+ALTER TABLE Crime ADD COLUMN category_id UUID REFERENCES Crime_Category(id) ON DELETE RESTRICT;
+
+UPDATE Crime SET category_id = CASE category
+    WHEN 'Violent' THEN 'c7a70000-0000-4000-8000-000000000001'::UUID
+    WHEN 'Property' THEN 'c7a70000-0000-4000-8000-000000000002'::UUID
+    WHEN 'State' THEN 'c7a70000-0000-4000-8000-000000000003'::UUID
+    ELSE 'c7a70000-0000-4000-8000-000000000004'::UUID
+END;
+UPDATE Crime SET category_id = 'c7a70000-0000-4000-8000-000000000011' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb3d';
+UPDATE Crime SET category_id = 'c7a70000-0000-4000-8000-000000000012' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb3e';
+UPDATE Crime SET category_id = 'c7a70000-0000-4000-8000-000000000021' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb3f';
+UPDATE Crime SET category_id = 'c7a70000-0000-4000-8000-000000000022' WHERE id = '8c8d502b-f7e1-4ad1-a741-b9dc5bdbdb40';
+
+ALTER TABLE Crime
+    ALTER COLUMN category_id SET NOT NULL,
+    ALTER COLUMN category_id SET DEFAULT 'c7a70000-0000-4000-8000-000000000004',
+    DROP COLUMN category;
+DROP TYPE Crime_Category_Kind;
+
+CREATE INDEX crime_category_idx ON Crime(category_id);
+
+/*The local law a base crime is prosecuted under, one entry per section of
+each jurisdiction's code*/
+CREATE TABLE Statute (
+    id UUID PRIMARY KEY,
+    crime_id UUID NOT NULL,
+    jurisdiction VARCHAR(100) NOT NULL CHECK (char_length(jurisdiction) >= 1),
+    code VARCHAR(100) NOT NULL CHECK (char_length(code) >= 1),
+    title VARCHAR(200),
+    text TEXT NOT NULL CHECK (char_length(text) BETWEEN 1 AND 20000),
+    source_url VARCHAR(2048),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (crime_id) REFERENCES Crime(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX statute_code_idx ON Statute(lower(jurisdiction), lower(code));
+CREATE INDEX statute_crime_idx ON Statute(crime_id);
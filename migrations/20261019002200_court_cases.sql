@@ -0,0 +1,69 @@
+CREATE TYPE Verdict AS ENUM ('Pending', 'Guilty', 'Acquitted', 'Dismissed');
+
+/*A case tries one deer in one jurisdiction. CASE is a reserved word, hence
+the table name*/
+CREATE TABLE Court_Case (
+    id UUID PRIMARY KEY,
+    cervidae_id UUID NOT NULL,
+    jurisdiction VARCHAR(100) NOT NULL CHECK (char_length(jurisdiction) >= 1),
+    docket_number VARCHAR(100) CHECK (char_length(docket_number) >= 1),
+    summary TEXT CHECK (char_length(summary) <= 5000),
+    opened_by UUID,
+    opened_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE,
+    FOREIGN KEY (opened_by) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX court_case_cervidae_idx ON Court_Case(cervidae_id, opened_at);
+CREATE UNIQUE INDEX court_case_docket_idx ON Court_Case(lower(jurisdiction), lower(docket_number));
+
+/*Each charge is decided on its own. Only a guilty verdict is a conviction,
+and only a conviction carries a sentence*/
+CREATE TABLE Charge (
+    id UUID PRIMARY KEY,
+    case_id UUID NOT NULL,
+    crime_id UUID NOT NULL,
+    incident_id UUID,
+    statute_id UUID,
+    verdict Verdict DEFAULT 'Pending' NOT NULL,
+    decided_at TIMESTAMP,
+    sentence TEXT CHECK (char_length(sentence) BETWEEN 1 AND 2000),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CHECK ((verdict = 'Pending') = (decided_at IS NULL)),
+    CHECK (sentence IS NULL OR verdict = 'Guilty'),
+    FOREIGN KEY (case_id) REFERENCES Court_Case(id) ON DELETE CASCADE,
+    FOREIGN KEY (crime_id) REFERENCES Crime(id) ON DELETE CASCADE,
+    FOREIGN KEY (incident_id) REFERENCES Incident(id) ON DELETE SET NULL,
+    FOREIGN KEY (statute_id) REFERENCES Statute(id) ON DELETE SET NULL
+);
+
+CREATE INDEX charge_case_idx ON Charge(case_id);
+CREATE INDEX charge_crime_idx ON Charge(crime_id);
+
+-- A conviction implies the deer is linked to the crime
+CREATE FUNCTION charge_link_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO Crime_Cervidae (crime_id, cervidae_id)
+    SELECT NEW.crime_id, cervidae_id FROM Court_Case WHERE id = NEW.case_id
+    ON CONFLICT DO NOTHING;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER charge_link
+AFTER INSERT OR UPDATE OF verdict ON Charge
+FOR EACH ROW WHEN (NEW.verdict = 'Guilty') EXECUTE FUNCTION charge_link_trigger();
+
+CREATE TABLE Hearing (
+    id UUID PRIMARY KEY,
+    case_id UUID NOT NULL,
+    scheduled_at TIMESTAMP NOT NULL,
+    court VARCHAR(200) CHECK (char_length(court) >= 1),
+    notes TEXT CHECK (char_length(notes) <= 2000),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (case_id) REFERENCES Court_Case(id) ON DELETE CASCADE
+);
+
+CREATE INDEX hearing_case_idx ON Hearing(case_id, scheduled_at);
@@ -0,0 +1,103 @@
+/*Tags are free-form but canonical: every spelling a user types is reduced to
+a slug, and synonym slugs resolve to the tag they stand for*/
+CREATE TABLE Tag (
+    id UUID PRIMARY KEY,
+    slug VARCHAR(100) NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    name VARCHAR(100) NOT NULL CHECK (char_length(name) >= 1),
+    description TEXT CHECK (char_length(description) <= 2000),
+    banned BOOLEAN DEFAULT FALSE NOT NULL,
+    created_by UUID,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (created_by) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+CREATE TABLE Tag_Synonym (
+    slug VARCHAR(100) PRIMARY KEY CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    tag_id UUID NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    FOREIGN KEY (tag_id) REFERENCES Tag(id) ON DELETE CASCADE
+);
+
+CREATE INDEX tag_synonym_tag_idx ON Tag_Synonym(tag_id);
+
+-- A slug names either a tag or a synonym, never both
+CREATE FUNCTION tag_slug_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF (TG_TABLE_NAME = 'tag' AND EXISTS (SELECT 1 FROM Tag_Synonym WHERE slug = NEW.slug))
+        OR (TG_TABLE_NAME = 'tag_synonym' AND EXISTS (SELECT 1 FROM Tag WHERE slug = NEW.slug)) THEN
+        RAISE EXCEPTION 'Slug % is already taken', NEW.slug USING ERRCODE = 'unique_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tag_slug_check
+BEFORE INSERT OR UPDATE OF slug ON Tag
+FOR EACH ROW EXECUTE FUNCTION tag_slug_trigger();
+
+CREATE TRIGGER tag_synonym_slug_check
+BEFORE INSERT OR UPDATE OF slug ON Tag_Synonym
+FOR EACH ROW EXECUTE FUNCTION tag_slug_trigger();
+
+CREATE FUNCTION resolve_tag(target VARCHAR) RETURNS UUID AS $$
+    SELECT id FROM Tag WHERE slug = target
+    UNION ALL
+    SELECT tag_id FROM Tag_Synonym WHERE slug = target
+    LIMIT 1;
+$$ LANGUAGE sql STABLE;
+
+/*Whoever tags a deer casts the first up vote. A tag only describes the deer
+while its score is positive, so the community can vote a bad tag off*/
+CREATE TABLE Deer_Tag (
+    cervidae_id UUID NOT NULL,
+    tag_id UUID NOT NULL,
+    tagged_by UUID,
+    upvotes INTEGER DEFAULT 0 NOT NULL,
+    downvotes INTEGER DEFAULT 0 NOT NULL,
+    score INTEGER NOT NULL GENERATED ALWAYS AS (upvotes - downvotes) STORED,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (cervidae_id, tag_id),
+    FOREIGN KEY (cervidae_id) REFERENCES Cervidae(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES Tag(id) ON DELETE CASCADE,
+    FOREIGN KEY (tagged_by) REFERENCES Users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX deer_tag_tag_idx ON Deer_Tag(tag_id) WHERE score > 0;
+
+CREATE TABLE Deer_Tag_Vote (
+    user_id UUID NOT NULL,
+    cervidae_id UUID NOT NULL,
+    tag_id UUID NOT NULL,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (user_id, cervidae_id, tag_id),
+    FOREIGN KEY (user_id) REFERENCES Users(id) ON DELETE CASCADE,
+    FOREIGN KEY (cervidae_id, tag_id) REFERENCES Deer_Tag(cervidae_id, tag_id) ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+CREATE INDEX deer_tag_vote_target_idx ON Deer_Tag_Vote(cervidae_id, tag_id);
+
+CREATE FUNCTION count_deer_tag_votes(deer UUID, target UUID) RETURNS VOID AS $$
+    UPDATE Deer_Tag SET
+        upvotes = (SELECT COUNT(*) FROM Deer_Tag_Vote WHERE cervidae_id = deer AND tag_id = target AND value = 1),
+        downvotes = (SELECT COUNT(*) FROM Deer_Tag_Vote WHERE cervidae_id = deer AND tag_id = target AND value = -1)
+    WHERE cervidae_id = deer AND tag_id = target;
+$$ LANGUAGE sql;
+
+CREATE FUNCTION deer_tag_vote_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM count_deer_tag_votes(OLD.cervidae_id, OLD.tag_id);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM count_deer_tag_votes(NEW.cervidae_id, NEW.tag_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER deer_tag_vote_count
+AFTER INSERT OR UPDATE OR DELETE ON Deer_Tag_Vote
+FOR EACH ROW EXECUTE FUNCTION deer_tag_vote_trigger();
@@ -0,0 +1,24 @@
+/*A reviewer whose reviews the community finds helpful counts for more. The
+helpful share is smoothed towards one half, so a reviewer without votes is
+neither rewarded nor punished*/
+CREATE OR REPLACE VIEW Reviewer_Credibility AS
+SELECT Users.id AS user_id,
+    0.4
+    + 0.2 * LEAST(EXTRACT(EPOCH FROM (NOW() - Users.created_at)) / (365 * 86400), 1)
+    + 0.2 * LEAST(COALESCE(submissions.approved, 0) / 5.0, 1)
+    + 0.4 * (COALESCE(votes.helpful, 0) + 1.0) / (COALESCE(votes.total, 0) + 2.0) AS weight
+FROM Users
+LEFT JOIN (
+    SELECT created_by, COUNT(*) AS approved
+    FROM Cervidae
+    WHERE status = 'Approved' AND deleted_at IS NULL
+    GROUP BY created_by
+) AS submissions ON submissions.created_by = Users.id
+LEFT JOIN (
+    SELECT user_id, SUM(helpful_votes) AS helpful, SUM(helpful_votes + unhelpful_votes) AS total
+    FROM Review
+    WHERE deleted_at IS NULL
+    GROUP BY user_id
+) AS votes ON votes.user_id = Users.id;
+
+SELECT refresh_danger_scores(NULL, 5.0);
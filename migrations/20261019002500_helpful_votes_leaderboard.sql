@@ -0,0 +1,81 @@
+/*Ranks reviewers by the helpful votes their reviews earned. Within a period
+only the votes cast in that period count*/
+ALTER TYPE Leaderboard_Kind ADD VALUE 'UserHelpfulVotes';
+
+CREATE OR REPLACE FUNCTION refresh_leaderboards(board_size INTEGER, prior_weight DOUBLE PRECISION) RETURNS VOID AS $$
+DECLARE
+    board_period Leaderboard_Period;
+    window_start TIMESTAMP;
+    prior_mean DOUBLE PRECISION;
+BEGIN
+    DELETE FROM Leaderboard_Entry;
+    SELECT COALESCE(AVG(danger_level), 5.5) INTO prior_mean FROM Review WHERE deleted_at IS NULL;
+    FOREACH board_period IN ARRAY enum_range(NULL::Leaderboard_Period) LOOP
+        window_start := CASE board_period
+            WHEN 'Last30Days' THEN NOW() - INTERVAL '30 days'
+            WHEN 'Last7Days' THEN NOW() - INTERVAL '7 days'
+        END;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerKillCount', board_period, ROW_NUMBER() OVER (ORDER BY kill_count DESC, id), id, kill_count
+        FROM Cervidae
+        WHERE status = 'Approved' AND deleted_at IS NULL AND kill_count IS NOT NULL
+            AND (window_start IS NULL OR created_at >= window_start)
+        ORDER BY kill_count DESC, id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerDangerScore', board_period, ROW_NUMBER() OVER (ORDER BY score DESC, cervidae_id), cervidae_id, score
+        FROM (
+            SELECT Review.cervidae_id,
+                (prior_weight * prior_mean + SUM(Reviewer_Credibility.weight * Review.danger_level))
+                    / (prior_weight + SUM(Reviewer_Credibility.weight)) AS score
+            FROM Review
+            JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.status = 'Approved'
+                AND Cervidae.deleted_at IS NULL
+            JOIN Reviewer_Credibility ON Reviewer_Credibility.user_id = Review.user_id
+            WHERE Review.deleted_at IS NULL
+                AND (window_start IS NULL OR Review.created_at >= window_start)
+            GROUP BY Review.cervidae_id
+        ) AS scores
+        ORDER BY score DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerCrimes', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, cervidae_id), cervidae_id, COUNT(*)
+        FROM Crime_Cervidae
+        JOIN Cervidae ON Cervidae.id = Crime_Cervidae.cervidae_id AND Cervidae.status = 'Approved'
+            AND Cervidae.deleted_at IS NULL
+        JOIN Crime ON Crime.id = Crime_Cervidae.crime_id AND Crime.deleted_at IS NULL
+        WHERE window_start IS NULL OR Crime_Cervidae.created_at >= window_start
+        GROUP BY cervidae_id
+        ORDER BY COUNT(*) DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserSubmissions', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, created_by), created_by, COUNT(*)
+        FROM Cervidae
+        WHERE status = 'Approved' AND deleted_at IS NULL AND created_by <> ghost_user_id()
+            AND (window_start IS NULL OR created_at >= window_start)
+        GROUP BY created_by
+        ORDER BY COUNT(*) DESC, created_by LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserReviews', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, user_id), user_id, COUNT(*)
+        FROM Review
+        JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.deleted_at IS NULL
+        WHERE Review.deleted_at IS NULL AND Review.user_id <> ghost_user_id()
+            AND (window_start IS NULL OR Review.created_at >= window_start)
+        GROUP BY user_id
+        ORDER BY COUNT(*) DESC, user_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserHelpfulVotes', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, Review.user_id),
+            Review.user_id, COUNT(*)
+        FROM Review_Vote
+        JOIN Review ON Review.id = Review_Vote.review_id AND Review.deleted_at IS NULL
+        JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.deleted_at IS NULL
+        WHERE Review_Vote.value = 1 AND Review.user_id <> ghost_user_id()
+            AND (window_start IS NULL OR Review_Vote.created_at >= window_start)
+        GROUP BY Review.user_id
+        ORDER BY COUNT(*) DESC, Review.user_id LIMIT board_size;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,37 @@
+-- Settling a round changes the entry, so edits made against the old version must fail
+CREATE OR REPLACE FUNCTION settle_moderation_round(deer UUID) RETURNS Deer_Entry_Status AS $$
+DECLARE
+    config Moderation_Config;
+    tally RECORD;
+    decision Deer_Entry_Status;
+    reason TEXT;
+BEGIN
+    PERFORM 1 FROM Cervidae WHERE id = deer AND status = 'Pending' AND deleted_at IS NULL FOR UPDATE;
+    IF NOT FOUND THEN
+        RETURN NULL;
+    END IF;
+    SELECT * INTO config FROM Moderation_Config;
+    SELECT * INTO tally FROM deer_vote_tally(deer);
+
+    IF tally.rejections = 0 AND tally.approvals >= config.approve_quorum THEN
+        decision := 'Approved';
+    ELSIF NOW() >= tally.opened_at + make_interval(hours => config.voting_window_hours)
+        AND tally.approvals + tally.rejections >= config.min_window_votes
+        AND tally.approvals <> tally.rejections THEN
+        decision := CASE WHEN tally.approvals > tally.rejections THEN 'Approved' ELSE 'Rejected' END;
+    ELSE
+        RETURN NULL;
+    END IF;
+
+    IF decision = 'Rejected' THEN
+        SELECT left(string_agg(Deer_Moderation_Vote.reason, '; ' ORDER BY created_at), 2000) INTO reason
+        FROM Deer_Moderation_Vote
+        WHERE cervidae_id = deer AND round = tally.round AND Deer_Moderation_Vote.decision = 'Reject';
+    END IF;
+    INSERT INTO Deer_Moderation_Event (id, cervidae_id, actor_id, from_status, to_status, reason, note)
+    VALUES (gen_random_uuid(), deer, system_user_id(), 'Pending', decision, reason,
+        format('Settled by vote: %s approve, %s reject', tally.approvals, tally.rejections));
+    UPDATE Cervidae SET status = decision, version = version + 1 WHERE id = deer;
+    RETURN decision;
+END;
+$$ LANGUAGE plpgsql;
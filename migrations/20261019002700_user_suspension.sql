@@ -0,0 +1,5 @@
+/*Acting on a report against a user suspends the account instead of deleting
+it, so an admin can lift the suspension if the report was mistaken*/
+ALTER TABLE Users
+    ADD COLUMN suspended_at TIMESTAMP,
+    ADD COLUMN suspended_by UUID REFERENCES Users(id) ON DELETE SET NULL;
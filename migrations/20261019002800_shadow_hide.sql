@@ -0,0 +1,178 @@
+/*Shadow-hidden posts stay live for their author, who keeps reading and
+editing them as usual, while everyone else reads past them and no score or
+board counts them*/
+ALTER TABLE Review ADD COLUMN shadow_hidden BOOLEAN DEFAULT FALSE NOT NULL;
+ALTER TABLE Comment ADD COLUMN shadow_hidden BOOLEAN DEFAULT FALSE NOT NULL;
+
+-- Posts shadow-hidden so far were soft-deleted by the system user instead
+UPDATE Review SET deleted_at = NULL, deleted_by = NULL, shadow_hidden = TRUE
+WHERE deleted_by = system_user_id()
+    AND id IN (SELECT target_id FROM Automod_Hit WHERE target_type = 'Review' AND action = 'ShadowHide')
+    AND id NOT IN (SELECT target_id FROM Automod_Hit WHERE target_type = 'Review' AND action = 'Hold'
+        AND target_id IS NOT NULL)
+    AND id NOT IN (SELECT target_id FROM Content_Report WHERE target_type = 'Review');
+UPDATE Comment SET deleted_at = NULL, deleted_by = NULL, shadow_hidden = TRUE
+WHERE deleted_by = system_user_id()
+    AND id IN (SELECT target_id FROM Automod_Hit WHERE target_type = 'Comment' AND action = 'ShadowHide')
+    AND id NOT IN (SELECT target_id FROM Automod_Hit WHERE target_type = 'Comment' AND action = 'Hold'
+        AND target_id IS NOT NULL)
+    AND id NOT IN (SELECT target_id FROM Content_Report WHERE target_type = 'Comment');
+
+-- Shadow-hidden posts wait for a moderator alongside held ones
+DROP INDEX automod_hit_hold_idx;
+CREATE INDEX automod_hit_hold_idx ON Automod_Hit(created_at)
+    WHERE action IN ('Hold', 'ShadowHide') AND reviewed_at IS NULL;
+
+CREATE OR REPLACE FUNCTION refresh_review_stats(deer UUID) RETURNS VOID AS $$
+BEGIN
+    INSERT INTO Review_Stats (cervidae_id, review_count, average_danger, median_danger, histogram, last_reviewed_at)
+    SELECT deer,
+        COUNT(*),
+        AVG(danger_level)::DOUBLE PRECISION,
+        percentile_cont(0.5) WITHIN GROUP (ORDER BY danger_level),
+        ARRAY(
+            SELECT COUNT(Review.danger_level)::INTEGER
+            FROM generate_series(1, 10) AS level
+            LEFT JOIN Review ON Review.cervidae_id = deer AND Review.danger_level = level
+                AND Review.deleted_at IS NULL AND NOT Review.shadow_hidden
+            GROUP BY level ORDER BY level
+        ),
+        MAX(updated_at)
+    FROM Review WHERE cervidae_id = deer AND deleted_at IS NULL AND NOT shadow_hidden
+    ON CONFLICT (cervidae_id) DO UPDATE SET
+        review_count = EXCLUDED.review_count,
+        average_danger = EXCLUDED.average_danger,
+        median_danger = EXCLUDED.median_danger,
+        histogram = EXCLUDED.histogram,
+        last_reviewed_at = EXCLUDED.last_reviewed_at;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE VIEW Reviewer_Credibility AS
+SELECT Users.id AS user_id,
+    0.4
+    + 0.2 * LEAST(EXTRACT(EPOCH FROM (NOW() - Users.created_at)) / (365 * 86400), 1)
+    + 0.2 * LEAST(COALESCE(submissions.approved, 0) / 5.0, 1)
+    + 0.4 * (COALESCE(votes.helpful, 0) + 1.0) / (COALESCE(votes.total, 0) + 2.0) AS weight
+FROM Users
+LEFT JOIN (
+    SELECT created_by, COUNT(*) AS approved
+    FROM Cervidae
+    WHERE status = 'Approved' AND deleted_at IS NULL
+    GROUP BY created_by
+) AS submissions ON submissions.created_by = Users.id
+LEFT JOIN (
+    SELECT user_id, SUM(helpful_votes) AS helpful, SUM(helpful_votes + unhelpful_votes) AS total
+    FROM Review
+    WHERE deleted_at IS NULL AND NOT shadow_hidden
+    GROUP BY user_id
+) AS votes ON votes.user_id = Users.id;
+
+CREATE OR REPLACE FUNCTION refresh_danger_scores(deer UUID, prior_weight DOUBLE PRECISION) RETURNS VOID AS $$
+DECLARE
+    prior_mean DOUBLE PRECISION;
+BEGIN
+    SELECT COALESCE(AVG(danger_level), 5.5) INTO prior_mean FROM Review
+        WHERE deleted_at IS NULL AND NOT shadow_hidden;
+    INSERT INTO Danger_Score (cervidae_id, score, weighted_score, updated_at)
+    SELECT Cervidae.id,
+        (prior_weight * prior_mean + COALESCE(SUM(Review.danger_level), 0))
+            / (prior_weight + COUNT(Review.danger_level)),
+        (prior_weight * prior_mean + COALESCE(SUM(Reviewer_Credibility.weight * Review.danger_level), 0))
+            / (prior_weight + COALESCE(SUM(Reviewer_Credibility.weight), 0)),
+        NOW()
+    FROM Cervidae
+    LEFT JOIN Review ON Review.cervidae_id = Cervidae.id AND Review.deleted_at IS NULL
+        AND NOT Review.shadow_hidden
+    LEFT JOIN Reviewer_Credibility ON Reviewer_Credibility.user_id = Review.user_id
+    WHERE deer IS NULL OR Cervidae.id = deer
+    GROUP BY Cervidae.id
+    ON CONFLICT (cervidae_id) DO UPDATE SET
+        score = EXCLUDED.score,
+        weighted_score = EXCLUDED.weighted_score,
+        updated_at = EXCLUDED.updated_at;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION refresh_leaderboards(board_size INTEGER, prior_weight DOUBLE PRECISION) RETURNS VOID AS $$
+DECLARE
+    board_period Leaderboard_Period;
+    window_start TIMESTAMP;
+    prior_mean DOUBLE PRECISION;
+BEGIN
+    DELETE FROM Leaderboard_Entry;
+    SELECT COALESCE(AVG(danger_level), 5.5) INTO prior_mean FROM Review
+        WHERE deleted_at IS NULL AND NOT shadow_hidden;
+    FOREACH board_period IN ARRAY enum_range(NULL::Leaderboard_Period) LOOP
+        window_start := CASE board_period
+            WHEN 'Last30Days' THEN NOW() - INTERVAL '30 days'
+            WHEN 'Last7Days' THEN NOW() - INTERVAL '7 days'
+        END;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerKillCount', board_period, ROW_NUMBER() OVER (ORDER BY kill_count DESC, id), id, kill_count
+        FROM Cervidae
+        WHERE status = 'Approved' AND deleted_at IS NULL AND kill_count IS NOT NULL
+            AND (window_start IS NULL OR created_at >= window_start)
+        ORDER BY kill_count DESC, id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerDangerScore', board_period, ROW_NUMBER() OVER (ORDER BY score DESC, cervidae_id), cervidae_id, score
+        FROM (
+            SELECT Review.cervidae_id,
+                (prior_weight * prior_mean + SUM(Reviewer_Credibility.weight * Review.danger_level))
+                    / (prior_weight + SUM(Reviewer_Credibility.weight)) AS score
+            FROM Review
+            JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.status = 'Approved'
+                AND Cervidae.deleted_at IS NULL
+            JOIN Reviewer_Credibility ON Reviewer_Credibility.user_id = Review.user_id
+            WHERE Review.deleted_at IS NULL AND NOT Review.shadow_hidden
+                AND (window_start IS NULL OR Review.created_at >= window_start)
+            GROUP BY Review.cervidae_id
+        ) AS scores
+        ORDER BY score DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'DeerCrimes', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, cervidae_id), cervidae_id, COUNT(*)
+        FROM Crime_Cervidae
+        JOIN Cervidae ON Cervidae.id = Crime_Cervidae.cervidae_id AND Cervidae.status = 'Approved'
+            AND Cervidae.deleted_at IS NULL
+        JOIN Crime ON Crime.id = Crime_Cervidae.crime_id AND Crime.deleted_at IS NULL
+        WHERE window_start IS NULL OR Crime_Cervidae.created_at >= window_start
+        GROUP BY cervidae_id
+        ORDER BY COUNT(*) DESC, cervidae_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserSubmissions', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, created_by), created_by, COUNT(*)
+        FROM Cervidae
+        WHERE status = 'Approved' AND deleted_at IS NULL AND created_by <> ghost_user_id()
+            AND (window_start IS NULL OR created_at >= window_start)
+        GROUP BY created_by
+        ORDER BY COUNT(*) DESC, created_by LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserReviews', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, user_id), user_id, COUNT(*)
+        FROM Review
+        JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.deleted_at IS NULL
+        WHERE Review.deleted_at IS NULL AND NOT Review.shadow_hidden AND Review.user_id <> ghost_user_id()
+            AND (window_start IS NULL OR Review.created_at >= window_start)
+        GROUP BY user_id
+        ORDER BY COUNT(*) DESC, user_id LIMIT board_size;
+
+        INSERT INTO Leaderboard_Entry (board, period, rank, subject_id, value)
+        SELECT 'UserHelpfulVotes', board_period, ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, Review.user_id),
+            Review.user_id, COUNT(*)
+        FROM Review_Vote
+        JOIN Review ON Review.id = Review_Vote.review_id AND Review.deleted_at IS NULL
+            AND NOT Review.shadow_hidden
+        JOIN Cervidae ON Cervidae.id = Review.cervidae_id AND Cervidae.deleted_at IS NULL
+        WHERE Review_Vote.value = 1 AND Review.user_id <> ghost_user_id()
+            AND (window_start IS NULL OR Review_Vote.created_at >= window_start)
+        GROUP BY Review.user_id
+        ORDER BY COUNT(*) DESC, Review.user_id LIMIT board_size;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+SELECT refresh_review_stats(id) FROM Cervidae;
+SELECT refresh_danger_scores(NULL, 5.0);
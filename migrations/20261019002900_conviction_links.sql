@@ -0,0 +1,51 @@
+/*A link made by a conviction is taken back when the verdict is overturned or
+the case is deleted, unless an incident or another conviction still ties the deer to the crime.
+Links assigned by hand or made by an incident are never removed this way*/
+ALTER TABLE Crime_Cervidae ADD COLUMN from_conviction BOOLEAN DEFAULT FALSE NOT NULL;
+
+/*The conviction trigger inserted each link in the transaction that decided the
+charge, so the link and the verdict share a timestamp. A verdict recorded again
+later moves decided_at, and such a link is left unflagged and kept*/
+UPDATE Crime_Cervidae SET from_conviction = TRUE
+WHERE EXISTS (
+    SELECT 1 FROM Charge JOIN Court_Case ON Court_Case.id = Charge.case_id
+    WHERE Charge.crime_id = Crime_Cervidae.crime_id
+     AND Court_Case.cervidae_id = Crime_Cervidae.cervidae_id
+     AND Charge.verdict = 'Guilty' AND Charge.decided_at = Crime_Cervidae.created_at
+);
+
+CREATE OR REPLACE FUNCTION charge_link_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO Crime_Cervidae (crime_id, cervidae_id, from_conviction)
+    SELECT NEW.crime_id, cervidae_id, TRUE FROM Court_Case WHERE id = NEW.case_id
+    ON CONFLICT DO NOTHING;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Drops a deer's conviction links that no incident or guilty charge backs any more
+CREATE FUNCTION drop_unbacked_conviction_links(deer UUID) RETURNS VOID AS $$
+    DELETE FROM Crime_Cervidae
+    WHERE cervidae_id = deer AND from_conviction
+     AND NOT EXISTS (
+        SELECT 1 FROM Incident
+        WHERE Incident.crime_id = Crime_Cervidae.crime_id AND Incident.cervidae_id = deer
+     )
+     AND NOT EXISTS (
+        SELECT 1 FROM Charge JOIN Court_Case ON Court_Case.id = Charge.case_id
+        WHERE Charge.crime_id = Crime_Cervidae.crime_id AND Court_Case.cervidae_id = deer
+         AND Charge.verdict = 'Guilty'
+     );
+$$ LANGUAGE sql;
+
+CREATE FUNCTION charge_unlink_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM drop_unbacked_conviction_links(cervidae_id) FROM Court_Case WHERE id = NEW.case_id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER charge_unlink
+AFTER UPDATE OF verdict ON Charge
+FOR EACH ROW WHEN (OLD.verdict = 'Guilty' AND NEW.verdict <> 'Guilty')
+EXECUTE FUNCTION charge_unlink_trigger();
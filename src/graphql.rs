@@ -1,17 +1,34 @@
-use async_graphql::{Context, Object, Result};
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+use automod::AutomodSubject;
 use aws_sdk_s3::{presigning::PresigningConfig, Client};
 use bcrypt::{hash, verify};
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 use http::header::{HeaderValue, SET_COOKIE};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use models::TransitionRole::{Admin, AppealReviewer, Submitter, System};
 use models::*;
-use sqlx::{self, query, query_as, Encode, PgPool, Postgres, QueryBuilder, Type};
+use moderation::settle_moderation_round;
+use scoring::refresh_danger_scores;
+use serde::Serialize;
+use sqlx::{
+    self, query, query_as, query_builder::Separated, query_scalar, Acquire, Encode, PgConnection,
+    PgPool, Postgres, QueryBuilder, Transaction, Type,
+};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::time::Duration;
+use storage::{get_comment, get_comment_thread, get_crime, get_deer, get_review, get_revision};
 use tower_cookies::Cookies;
+use tracing::info;
+use unicode_normalization::UnicodeNormalization;
 use uuid::Uuid;
 
+pub mod automod;
+pub mod geojson;
 pub mod models;
+pub mod moderation;
+pub mod scoring;
 pub mod storage;
 // Root types for GraphQL schema
 pub struct QueryRoot;
@@ -29,96 +46,434 @@ fn add_to_query<'b, 'a, T>(
     query_builder.push_bind(value);
 }
 
+// For updates with no column that is always set: commas go between the columns given
+fn add_to_set<'a, T>(set: &mut Separated<'_, 'a, Postgres, &'static str>, key: &str, value: &'a T)
+where
+    T: Encode<'a, Postgres> + Type<Postgres> + 'a,
+{
+    set.push(key);
+    set.push_unseparated(" = ");
+    set.push_bind_unseparated(value);
+}
+
+// Trims a text input, refusing one that is nothing but whitespace
+fn trim_non_blank<'a>(value: &'a str, field: &str) -> Result<&'a str> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(async_graphql::Error::new(format!(
+            "Invalid arguments: {} cannot be blank",
+            field
+        )));
+    }
+    Ok(value)
+}
+
+// Trims and NFC-normalizes display names so visually identical names compare equal
+fn normalize_name(name: &str) -> Result<String> {
+    let name: String = name.trim().nfc().collect();
+    if name.is_empty() {
+        return Err(async_graphql::Error::new("Name cannot be blank"));
+    }
+    Ok(name)
+}
+
+// Reduces a typed tag to its slug: "Pack Hunter!" and "pack-hunter" are the same tag
+fn tag_slug(tag: &str) -> String {
+    let tag: String = tag.nfkd().filter(char::is_ascii).collect();
+    tag.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn email_taken(e: sqlx::Error) -> async_graphql::Error {
+    match e {
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            async_graphql::Error::new("Email is already in use")
+        }
+        e => e.into(),
+    }
+}
+
+fn taxon_error(e: sqlx::Error) -> async_graphql::Error {
+    match e {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+            async_graphql::Error::new("Taxon not found")
+        }
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            async_graphql::Error::new("A taxon with that scientific name already exists")
+        }
+        // Raised by the rank trigger with a message meant for the caller
+        sqlx::Error::Database(e) if e.is_check_violation() => {
+            async_graphql::Error::new(e.message())
+        }
+        e => e.into(),
+    }
+}
+
+fn crime_category_error(e: sqlx::Error) -> async_graphql::Error {
+    match e {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+            async_graphql::Error::new("Crime category not found")
+        }
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            async_graphql::Error::new("A category with that slug already exists")
+        }
+        // Raised by the cycle trigger with a message meant for the caller
+        sqlx::Error::Database(e) if e.is_check_violation() => {
+            async_graphql::Error::new(e.message())
+        }
+        e => e.into(),
+    }
+}
+
+fn case_error(e: sqlx::Error) -> async_graphql::Error {
+    match e {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+            async_graphql::Error::new("Deer not found")
+        }
+        sqlx::Error::Database(e) if e.is_unique_violation() => async_graphql::Error::new(
+            "That jurisdiction already has a case with this docket number",
+        ),
+        e => e.into(),
+    }
+}
+
+fn tag_error(e: sqlx::Error) -> async_graphql::Error {
+    match e {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+            async_graphql::Error::new("Tag not found")
+        }
+        // Raised by the slug trigger when a synonym would shadow a tag or the reverse
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            async_graphql::Error::new("That slug already names a tag or synonym")
+        }
+        e => e.into(),
+    }
+}
+
+fn statute_error(e: sqlx::Error) -> async_graphql::Error {
+    match e {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+            async_graphql::Error::new("Crime not found")
+        }
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            async_graphql::Error::new("That jurisdiction already has a statute with this code")
+        }
+        e => e.into(),
+    }
+}
+
+// A guarded update matched no row: either the row is gone or its version moved on
+fn stale_write<T: Serialize>(current: Option<T>, not_found: &str) -> async_graphql::Error {
+    match current {
+        Some(current) => {
+            async_graphql::Error::new("Version conflict: the record was modified since it was read")
+                .extend_with(|_, e| {
+                    e.set("code", "CONFLICT");
+                    if let Ok(current) = async_graphql::to_value(&current) {
+                        e.set("current", current);
+                    }
+                })
+        }
+        None => async_graphql::Error::new(not_found),
+    }
+}
+
+fn decode_session_cookie(context: &Context<'_>) -> Result<Claims> {
+    let cookies = context.data::<Cookies>()?;
+    let cookie = cookies.get("cerv_token");
+    if let Some(token) = cookie {
+        let key = DecodingKey::from_secret(env::var("CLIENT_SECRET")?.as_bytes());
+        let decoded = decode::<Claims>(token.value(), &key, &Validation::default());
+        if decoded.is_err() {
+            return Err(decoded.err().unwrap().to_string().into());
+        }
+        Ok(decoded.unwrap().claims)
+    } else {
+        Err(async_graphql::Error::new("No token found"))
+    }
+}
+
+// Decodes the session cookie of the caller and checks the session hasn't been revoked
+// and the account isn't suspended, whenever the token was issued
+async fn current_claims(context: &Context<'_>) -> Result<Claims> {
+    let claims = decode_session_cookie(context)?;
+    let suspended = query_scalar!(
+        r#"
+        SELECT Users.suspended_at IS NOT NULL AS "suspended!" FROM User_Session
+         JOIN Users ON Users.id = User_Session.user_id
+         WHERE User_Session.id = $1 AND User_Session.user_id = $2 AND User_Session.expires_at > NOW()"#,
+        claims.jti,
+        Uuid::parse_str(&claims.sub)?,
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await?;
+    match suspended {
+        None => Err(async_graphql::Error::new(
+            "Session has expired or was revoked",
+        )),
+        Some(true) => Err(async_graphql::Error::new("This account is suspended")),
+        Some(false) => Ok(claims),
+    }
+}
+
+async fn current_user_id(context: &Context<'_>) -> Result<Uuid> {
+    let claims = current_claims(context).await?;
+    Ok(Uuid::parse_str(&claims.sub)?)
+}
+
+async fn require_admin(context: &Context<'_>) -> Result<Uuid> {
+    let claims = current_claims(context).await?;
+    if !claims.is_admin {
+        return Err(async_graphql::Error::new("Admin privileges required"));
+    }
+    Ok(Uuid::parse_str(&claims.sub)?)
+}
+
+// Authors may remove their own content, admins anyone's
+async fn require_owner_or_admin(context: &Context<'_>, owner: Uuid) -> Result<Uuid> {
+    let claims = current_claims(context).await?;
+    let user_id = Uuid::parse_str(&claims.sub)?;
+    if user_id != owner && !claims.is_admin {
+        return Err(async_graphql::Error::new(
+            "Only the author or an admin can do this",
+        ));
+    }
+    Ok(user_id)
+}
+
+const SESSION_SECS: i64 = 86400;
+
+// Overwrites the session cookie with an already expired token
+fn expire_session_cookie(context: &Context<'_>) -> Result<String> {
+    let header = Header::default();
+    let claims = Claims {
+        sub: "".to_string(),
+        exp: (Utc::now().timestamp() - SESSION_SECS) as usize,
+        iat: Utc::now().timestamp() as usize,
+        iss: "National Cervidae Analystics Association".to_string(),
+        is_admin: false,
+        jti: "".to_string(),
+    };
+    let key = EncodingKey::from_secret(env::var("CLIENT_SECRET")?.as_bytes());
+    let token = encode(&header, &claims, &key)?;
+
+    // Set the cookie in the response
+    let cookie_value = format!("cerv_token={}; Path=/; HttpOnly;", token);
+    context.append_http_header(SET_COOKIE, HeaderValue::from_str(&cookie_value)?);
+
+    Ok(token)
+}
+
+const DEER_FROM: &str = " FROM Cervidae \
+    LEFT JOIN Review_Stats ON Review_Stats.cervidae_id = Cervidae.id \
+    LEFT JOIN Danger_Score ON Danger_Score.cervidae_id = Cervidae.id";
+
+// Columns a deer page is ordered by; the id always comes last so the order is total
+fn deer_sort_key(sort: DeerSort) -> &'static [&'static str] {
+    match sort {
+        DeerSort::Id => &["Cervidae.id"],
+        DeerSort::ReviewCount => &["COALESCE(Review_Stats.review_count, 0)", "Cervidae.id"],
+        DeerSort::AverageDanger => &["COALESCE(Review_Stats.average_danger, 0)", "Cervidae.id"],
+        DeerSort::MedianDanger => &["COALESCE(Review_Stats.median_danger, 0)", "Cervidae.id"],
+        DeerSort::DangerScore => &["COALESCE(Danger_Score.score, 0)", "Cervidae.id"],
+        DeerSort::WeightedDangerScore => {
+            &["COALESCE(Danger_Score.weighted_score, 0)", "Cervidae.id"]
+        }
+        DeerSort::LastReviewed => &[
+            "COALESCE(Review_Stats.last_reviewed_at, 'epoch')",
+            "Cervidae.id",
+        ],
+    }
+}
+
+struct DeerPageQuery {
+    status: DeerEntryStatus,
+    created_by: Option<Uuid>,
+    filter: DeerFilter,
+    sort: DeerSort,
+    descending: bool,
+}
+
+fn push_deer_conditions(query_builder: &mut QueryBuilder<'_, Postgres>, query: &DeerPageQuery) {
+    query_builder.push(" WHERE Cervidae.deleted_at IS NULL AND Cervidae.status = ");
+    query_builder.push_bind(query.status);
+    if let Some(created_by) = query.created_by {
+        query_builder.push(" AND Cervidae.created_by = ");
+        query_builder.push_bind(created_by);
+    }
+    let filter = &query.filter;
+    if let Some(min) = filter.min_review_count {
+        query_builder.push(" AND COALESCE(Review_Stats.review_count, 0) >= ");
+        query_builder.push_bind(min);
+    }
+    if let Some(max) = filter.max_review_count {
+        query_builder.push(" AND COALESCE(Review_Stats.review_count, 0) <= ");
+        query_builder.push_bind(max);
+    }
+    if let Some(min) = filter.min_average_danger {
+        query_builder.push(" AND Review_Stats.average_danger >= ");
+        query_builder.push_bind(min);
+    }
+    if let Some(max) = filter.max_average_danger {
+        query_builder.push(" AND Review_Stats.average_danger <= ");
+        query_builder.push_bind(max);
+    }
+    if let Some(taxon_id) = filter.taxon_id {
+        query_builder.push(" AND Cervidae.taxon_id IN (SELECT taxon_subtree(");
+        query_builder.push_bind(Uuid::from(taxon_id));
+        query_builder.push("))");
+    }
+    if let Some(category_id) = filter.crime_category_id {
+        query_builder.push(
+            " AND EXISTS (SELECT 1 FROM Crime_Cervidae JOIN Crime ON Crime.id = Crime_Cervidae.crime_id \
+             WHERE Crime_Cervidae.cervidae_id = Cervidae.id AND Crime.deleted_at IS NULL \
+             AND Crime.category_id IN (SELECT crime_category_subtree(",
+        );
+        query_builder.push_bind(Uuid::from(category_id));
+        query_builder.push(")))");
+    }
+    for tag in filter.tags.iter().flatten() {
+        query_builder.push(
+            " AND EXISTS (SELECT 1 FROM Deer_Tag JOIN Tag ON Tag.id = Deer_Tag.tag_id \
+             WHERE Deer_Tag.cervidae_id = Cervidae.id AND Deer_Tag.score > 0 AND NOT Tag.banned \
+             AND Deer_Tag.tag_id = resolve_tag(",
+        );
+        query_builder.push_bind(tag_slug(tag));
+        query_builder.push("))");
+    }
+    if let Some(name) = &filter.name {
+        let pattern = format!(
+            "%{}%",
+            name.trim()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        query_builder.push(" AND (Cervidae.name ILIKE ");
+        query_builder.push_bind(pattern.clone());
+        query_builder.push(
+            " OR EXISTS (SELECT 1 FROM Deer_Alias WHERE Deer_Alias.cervidae_id = Cervidae.id AND Deer_Alias.alias ILIKE ",
+        );
+        query_builder.push_bind(pattern);
+        query_builder.push("))");
+    }
+}
+
+// Restricts to rows strictly greater (or lower) than the cursor row in sort-key order
+fn push_deer_keyset(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    key: &[&str],
+    greater: bool,
+    cursor: Uuid,
+) {
+    let columns = key.join(", ");
+    query_builder.push(" AND (");
+    query_builder.push(&columns);
+    query_builder.push(if greater {
+        ") > (SELECT "
+    } else {
+        ") < (SELECT "
+    });
+    query_builder.push(&columns);
+    query_builder.push(DEER_FROM);
+    query_builder.push(" WHERE Cervidae.id = ");
+    query_builder.push_bind(cursor);
+    query_builder.push(")");
+}
+
+async fn deer_exists_beyond(
+    context: &Context<'_>,
+    query: &DeerPageQuery,
+    greater: bool,
+    cursor: Uuid,
+) -> Result<bool> {
+    let mut query_builder: QueryBuilder<'_, Postgres> =
+        QueryBuilder::new("SELECT EXISTS (SELECT 1");
+    query_builder.push(DEER_FROM);
+    push_deer_conditions(&mut query_builder, query);
+    push_deer_keyset(
+        &mut query_builder,
+        deer_sort_key(query.sort),
+        greater,
+        cursor,
+    );
+    query_builder.push(")");
+    let exists = query_builder
+        .build_query_scalar()
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await?;
+    Ok(exists)
+}
+
 async fn deer_page(
     context: &Context<'_>,
     after: Option<Uuid>,
     before: Option<Uuid>,
     first: Option<i64>,
     last: Option<i64>,
-    status: DeerEntryStatus,
-    created_by: Option<Uuid>,
+    query: DeerPageQuery,
 ) -> Result<Vec<DeerConnection>> {
     if first.is_some() && last.is_some() {
         return Err(async_graphql::Error::new(
             "Invalid arguments: please specify only one of first or last",
         ));
     }
-    if (first.is_some() && first.unwrap() == 0) || (last.is_some() && last.unwrap() == 0) {
+    if first == Some(0) || last == Some(0) {
         return Err(async_graphql::Error::new(
             "Invalid arguments: first and last cannot be 0",
         ));
     }
-    let mut query_builder: QueryBuilder<'_, Postgres> = QueryBuilder::new("SELECT * FROM ");
-    if first.is_some() {
-        query_builder.push("Cervidae WHERE status = ");
-        query_builder.push_bind(&status);
-        if after.is_some() {
-            query_builder.push(" AND id > ");
-            query_builder.push_bind(after);
-        }
-        if created_by.is_some() {
-            query_builder.push(" AND created_by = ");
-            query_builder.push_bind(created_by);
-        }
-        query_builder.push(" ORDER BY id ASC LIMIT ");
-        query_builder.push_bind(first.unwrap());
-    } else if last.is_some() {
-        query_builder.push("(SELECT * FROM Cervidae WHERE status = ");
-        query_builder.push_bind(&status);
-        if last.is_some() {
-            query_builder.push(" AND id < ");
-            query_builder.push_bind(before);
-        }
-        if created_by.is_some() {
-            query_builder.push(" AND created_by = ");
-            query_builder.push_bind(created_by);
-        }
-        query_builder.push(" ORDER BY id DESC LIMIT ");
-        query_builder.push_bind(last.unwrap());
-        query_builder.push(") AS deer ORDER BY id ASC");
-    } else {
-        return Err(async_graphql::Error::new("Invalid pagination arguments"));
+    // Paging backwards walks the sort order in reverse, then flips the rows back
+    let (limit, forward, cursor) = match (first, last) {
+        (Some(first), None) => (first, true, after),
+        (None, Some(last)) => (last, false, before),
+        _ => return Err(async_graphql::Error::new("Invalid pagination arguments")),
+    };
+    let ascending = forward != query.descending;
+    let key = deer_sort_key(query.sort);
+
+    let mut query_builder: QueryBuilder<'_, Postgres> = QueryBuilder::new("SELECT Cervidae.*");
+    query_builder.push(DEER_FROM);
+    push_deer_conditions(&mut query_builder, &query);
+    if let Some(cursor) = cursor {
+        push_deer_keyset(&mut query_builder, key, ascending, cursor);
     }
-    let deer: Vec<Deer> = query_builder
+    let direction = if ascending { " ASC" } else { " DESC" };
+    let order: Vec<String> = key
+        .iter()
+        .map(|column| format!("{}{}", column, direction))
+        .collect();
+    query_builder.push(" ORDER BY ");
+    query_builder.push(order.join(", "));
+    query_builder.push(" LIMIT ");
+    query_builder.push_bind(limit);
+    let mut deer: Vec<Deer> = query_builder
         .build_query_as()
         .fetch_all(context.data_unchecked::<PgPool>())
         .await?;
+    if !forward {
+        deer.reverse();
+    }
     if deer.is_empty() {
         return Err(async_graphql::Error::new("No deer found"));
     }
     let start_cursor = deer.first().unwrap().id;
     let end_cursor = deer.last().unwrap().id;
-    let mut has_next_page_query =
-        String::from("SELECT COUNT(*) FROM Cervidae WHERE id > $1 AND status = $3");
-    let mut has_previous_page_query =
-        String::from("SELECT COUNT(*) FROM Cervidae WHERE id < $2 AND status = $3");
-
-    if created_by.is_some() {
-        has_next_page_query.push_str(" AND created_by = $4");
-        has_previous_page_query.push_str(" AND created_by = $4");
-    }
-
-    let query = format!(
-        r#"SELECT 
-            (({}) > 0) AS has_next_page,
-            (({}) > 0) AS has_previous_page,
-            $1 AS start_cursor, 
-            $2 AS end_cursor, 
-            COUNT(*) AS total_count 
-        FROM Cervidae 
-        WHERE status = $3"#,
-        has_next_page_query, has_previous_page_query
-    );
 
-    let mut query = sqlx::query_as::<_, PageInfo>(&query)
-        .bind(end_cursor)
-        .bind(start_cursor)
-        .bind(status);
+    let has_next_page = deer_exists_beyond(context, &query, !query.descending, end_cursor).await?;
+    let has_previous_page =
+        deer_exists_beyond(context, &query, query.descending, start_cursor).await?;
+    let mut count_query: QueryBuilder<'_, Postgres> = QueryBuilder::new("SELECT COUNT(*)");
+    count_query.push(DEER_FROM);
+    push_deer_conditions(&mut count_query, &query);
+    let total_count: i64 = count_query
+        .build_query_scalar()
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await?;
 
-    if let Some(id) = created_by {
-        query = query.bind(id);
-    }
-    let page_test = query.fetch_one(context.data_unchecked::<PgPool>()).await?;
     let deer_edges = deer
         .iter()
         .map(|deer: &Deer| DeerEdge {
@@ -128,623 +483,4070 @@ async fn deer_page(
         .collect();
     let deer_connection = DeerConnection {
         edges: deer_edges,
-        page_info: page_test,
+        page_info: PageInfo {
+            has_next_page: Some(has_next_page),
+            has_previous_page: Some(has_previous_page),
+            start_cursor: Some(start_cursor),
+            end_cursor: Some(end_cursor),
+            total_count: Some(total_count),
+        },
     };
     Ok(vec![deer_connection])
 }
 
-#[Object]
-impl QueryRoot {
-    // Add your query resolvers here
-    async fn users(&self, context: &Context<'_>) -> Result<Vec<User>> {
-        let users = query_as!(User, "SELECT * FROM Users")
-            .fetch_all(context.data_unchecked::<PgPool>())
+// Hands the account's deer to the ghost user, applies the content policy to its
+// reviews and comments, revokes its sessions and removes it, all or nothing
+async fn delete_account(
+    context: &Context<'_>,
+    user_id: Uuid,
+    requested_by: Uuid,
+    policy: AccountContentPolicy,
+) -> Result<()> {
+    let pool = context.data_unchecked::<PgPool>();
+    let mut tx = pool.begin().await?;
+    let exists = query_scalar!(
+        "SELECT EXISTS (SELECT 1 FROM Users WHERE id = $1 AND NOT is_reserved_user(id))",
+        user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if !exists.unwrap_or(false) {
+        return Err("User not found".into());
+    }
+
+    let deer_reassigned = query!(
+        r#"
+        UPDATE Cervidae SET
+            created_by = CASE WHEN created_by = $1 THEN ghost_user_id() ELSE created_by END,
+            updated_by = CASE WHEN updated_by = $1 THEN ghost_user_id() ELSE updated_by END
+         WHERE created_by = $1 OR updated_by = $1"#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    query!(
+        "UPDATE Cervidae_Revision SET edited_by = ghost_user_id() WHERE edited_by = $1",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    query!(
+        "UPDATE Deer_Moderation_Event SET actor_id = ghost_user_id() WHERE actor_id = $1",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    let (reviews_affected, comments_affected) = match policy {
+        AccountContentPolicy::Keep => {
+            let reviews = query!(
+                "UPDATE Review SET user_id = ghost_user_id() WHERE user_id = $1",
+                user_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            let comments = query!(
+                "UPDATE Comment SET user_id = ghost_user_id() WHERE user_id = $1",
+                user_id
+            )
+            .execute(&mut *tx)
             .await?;
+            (reviews.rows_affected(), comments.rows_affected())
+        }
+        AccountContentPolicy::Erase => {
+            let reviews = query!("DELETE FROM Review WHERE user_id = $1", user_id)
+                .execute(&mut *tx)
+                .await?;
+            // Comments can't be removed outright without breaking the threads under them
+            let comments = query!(
+                r#"
+                UPDATE Comment SET user_id = ghost_user_id(), content = $2,
+                    deleted_at = COALESCE(deleted_at, NOW()), version = version + 1
+                 WHERE user_id = $1"#,
+                user_id,
+                DELETED_COMMENT_PLACEHOLDER,
+            )
+            .execute(&mut *tx)
+            .await?;
+            (reviews.rows_affected(), comments.rows_affected())
+        }
+    };
+    let sessions_revoked = query!("DELETE FROM User_Session WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    query!(
+        r#"
+        DELETE FROM Leaderboard_Entry
+         WHERE board IN ('UserSubmissions', 'UserReviews', 'UserHelpfulVotes') AND subject_id = $1"#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    query!("DELETE FROM Users WHERE id = $1", user_id)
+        .execute(&mut *tx)
+        .await?;
+    query!(
+        r#"
+        INSERT INTO Account_Deletion_Audit (id, user_id, requested_by, self_service, content_policy,
+            deer_reassigned, reviews_affected, comments_affected, sessions_revoked)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+        Uuid::new_v4(),
+        user_id,
+        requested_by,
+        user_id == requested_by,
+        policy as AccountContentPolicy,
+        deer_reassigned as i64,
+        reviews_affected as i64,
+        comments_affected as i64,
+        sessions_revoked as i64,
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
 
-        Ok(users)
+    if policy == AccountContentPolicy::Erase {
+        refresh_danger_scores(pool, None).await?;
     }
+    Ok(())
+}
 
-    async fn user(&self, context: &Context<'_>, id: UuidScalar) -> Result<Option<User>> {
-        let id: Uuid = id.into();
-        let user = query_as!(User, "SELECT * FROM Users WHERE id = $1", id)
-            .fetch_optional(context.data_unchecked::<PgPool>())
-            .await
-            .map_err(|e| e.to_string())?;
+// Every move a deer entry may make, and who may make it
+const DEER_TRANSITIONS: &[(DeerEntryStatus, DeerEntryStatus, &[TransitionRole])] = &[
+    (
+        DeerEntryStatus::Draft,
+        DeerEntryStatus::Pending,
+        &[Submitter],
+    ),
+    (
+        DeerEntryStatus::Pending,
+        DeerEntryStatus::Approved,
+        &[System],
+    ),
+    (
+        DeerEntryStatus::Pending,
+        DeerEntryStatus::Rejected,
+        &[System],
+    ),
+    (
+        DeerEntryStatus::Rejected,
+        DeerEntryStatus::Pending,
+        &[Submitter],
+    ),
+    (
+        DeerEntryStatus::Rejected,
+        DeerEntryStatus::Approved,
+        &[AppealReviewer],
+    ),
+    (
+        DeerEntryStatus::Approved,
+        DeerEntryStatus::Archived,
+        &[Submitter, Admin],
+    ),
+];
 
-        Ok(user)
+fn check_transition(
+    from: DeerEntryStatus,
+    to: DeerEntryStatus,
+    roles: &[TransitionRole],
+) -> Result<()> {
+    let transition = DEER_TRANSITIONS
+        .iter()
+        .find(|(source, target, _)| *source == from && *target == to);
+    let Some((_, _, allowed)) = transition else {
+        return Err(async_graphql::Error::new(format!(
+            "Cannot move a deer from {} to {}",
+            from, to
+        )));
+    };
+    if !allowed.iter().any(|role| roles.contains(role)) {
+        let allowed: Vec<String> = allowed.iter().map(|role| role.to_string()).collect();
+        return Err(async_graphql::Error::new(format!(
+            "Only {} can move a {} deer to {}",
+            allowed.join(" or "),
+            from,
+            to
+        )));
     }
+    Ok(())
+}
 
-    async fn deer(&self, context: &Context<'_>, id: UuidScalar) -> Result<Option<Deer>> {
-        let id: Uuid = id.into();
-        let deer = query_as("SELECT * FROM Cervidae WHERE id = $1")
-            .bind(id)
-            .fetch_optional(context.data_unchecked::<PgPool>())
-            .await?;
+struct StatusChange<'a> {
+    to: DeerEntryStatus,
+    // Status the deer was seen in when the move was checked
+    from: DeerEntryStatus,
+    actor_id: Uuid,
+    reason: Option<&'a str>,
+    note: Option<&'a str>,
+}
 
-        Ok(deer)
+fn moderation_reason(reason: &Option<String>, required: bool) -> Result<Option<&str>> {
+    let reason = reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    if required && reason.is_none() {
+        return Err(async_graphql::Error::new(
+            "A reason is required to reject a deer",
+        ));
     }
+    Ok(reason)
+}
 
-    async fn deer_all(&self, context: &Context<'_>) -> Result<Vec<Deer>> {
-        let deer = query_as("SELECT * FROM Cervidae WHERE status = 'Approved'")
-            .fetch_all(context.data_unchecked::<PgPool>())
-            .await?;
+// Moves a live deer to a new status and logs the move in the same statement
+async fn change_deer_status<'e, E>(
+    executor: E,
+    deer_id: Uuid,
+    change: &StatusChange<'_>,
+) -> sqlx::Result<Option<Deer>>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    query_as(
+        r#"
+        WITH previous AS (
+            SELECT id, status FROM Cervidae
+             WHERE id = $1 AND deleted_at IS NULL AND status = $3
+             FOR UPDATE
+        ), event AS (
+            INSERT INTO Deer_Moderation_Event (id, cervidae_id, actor_id, from_status, to_status, reason, note)
+            SELECT gen_random_uuid(), id, $4, status, $2, $5, $6 FROM previous
+        )
+        UPDATE Cervidae SET status = $2, version = Cervidae.version + 1
+          FROM previous WHERE Cervidae.id = previous.id
+         RETURNING Cervidae.*"#,
+    )
+    .bind(deer_id)
+    .bind(change.to)
+    .bind(change.from)
+    .bind(change.actor_id)
+    .bind(change.reason)
+    .bind(change.note)
+    .fetch_optional(executor)
+    .await
+}
 
-        Ok(deer)
+// Checks the caller may make the move, then makes it and logs it
+async fn transition_deer(
+    context: &Context<'_>,
+    id: Uuid,
+    to: DeerEntryStatus,
+    reason: Option<&str>,
+    note: Option<&str>,
+) -> Result<Deer> {
+    let Some(deer) = get_deer(context, id).await? else {
+        return Err("Deer not found".into());
+    };
+    let claims = current_claims(context).await?;
+    let actor_id = Uuid::parse_str(&claims.sub)?;
+    let mut roles = Vec::new();
+    if actor_id == deer.created_by {
+        roles.push(Submitter);
     }
+    if claims.is_admin {
+        roles.push(Admin);
+    }
+    check_transition(deer.status, to, &roles)?;
+    let change = StatusChange {
+        to,
+        from: deer.status,
+        actor_id,
+        reason,
+        note,
+    };
+    let deer = change_deer_status(context.data_unchecked::<PgPool>(), id, &change).await?;
+    deer.ok_or_else(|| "Deer status changed concurrently, please retry".into())
+}
 
-    async fn deer_pending(&self, context: &Context<'_>) -> Result<Vec<Deer>> {
-        let deer = query_as("SELECT * FROM Cervidae WHERE status = 'Pending'")
-            .fetch_all(context.data_unchecked::<PgPool>())
-            .await?;
+fn moderation_note(note: &Option<String>) -> Option<&str> {
+    note.as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty())
+}
 
-        Ok(deer)
-    }
+struct ModerationVote<'a> {
+    voter_id: Uuid,
+    decision: ModerationDecision,
+    reason: Option<&'a str>,
+    note: Option<&'a str>,
+}
 
-    async fn deer_reviews(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Review>> {
-        let id: Uuid = id.into();
-        let reviews = query_as!(Review, "SELECT * FROM review WHERE cervidae_id = $1", id)
-            .fetch_all(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(reviews)
-    }
+enum VoteOutcome {
+    Counted,
+    NotFound,
+    NotPending,
+    OwnSubmission,
+}
 
-    async fn user_reviews(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Review>> {
-        let id: Uuid = id.into();
-        let reviews = query_as!(Review, "SELECT * FROM review WHERE user_id = $1", id)
-            .fetch_all(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(reviews)
+// Records a vote in the deer's current round, replacing the voter's earlier
+// vote in that round, then settles the round if the quorum is met
+async fn cast_vote(
+    conn: &mut PgConnection,
+    deer_id: Uuid,
+    vote: &ModerationVote<'_>,
+) -> sqlx::Result<VoteOutcome> {
+    let deer = query!(
+        r#"
+        SELECT created_by, status AS "status: DeerEntryStatus" FROM Cervidae
+         WHERE id = $1 AND deleted_at IS NULL FOR UPDATE"#,
+        deer_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(deer) = deer else {
+        return Ok(VoteOutcome::NotFound);
+    };
+    if deer.status != DeerEntryStatus::Pending {
+        return Ok(VoteOutcome::NotPending);
+    }
+    if deer.created_by == vote.voter_id {
+        return Ok(VoteOutcome::OwnSubmission);
     }
+    query!(
+        r#"
+        INSERT INTO Deer_Moderation_Vote (id, cervidae_id, voter_id, round, decision, reason, note)
+         VALUES ($1, $2, $3, moderation_round($2), $4, $5, $6)
+         ON CONFLICT (cervidae_id, voter_id, round) DO UPDATE SET
+            decision = EXCLUDED.decision,
+            reason = EXCLUDED.reason,
+            note = EXCLUDED.note,
+            created_at = NOW()"#,
+        Uuid::new_v4(),
+        deer_id,
+        vote.voter_id,
+        vote.decision as ModerationDecision,
+        vote.reason,
+        vote.note,
+    )
+    .execute(&mut *conn)
+    .await?;
+    settle_moderation_round(conn, deer_id).await?;
+    Ok(VoteOutcome::Counted)
+}
 
-    async fn deer_comments(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Comment>> {
-        let id: Uuid = id.into();
-        let comments = query_as!(
-            Comment,
-            "SELECT * FROM comment WHERE cervidae_id = $1 ORDER BY created_at DESC",
-            id
-        )
-        .fetch_all(context.data_unchecked::<PgPool>())
-        .await?;
-        Ok(comments)
+async fn vote_on_deer(
+    context: &Context<'_>,
+    id: Uuid,
+    decision: ModerationDecision,
+    reason: &Option<String>,
+    note: &Option<String>,
+) -> Result<Deer> {
+    let admin_id = require_admin(context).await?;
+    let vote = ModerationVote {
+        voter_id: admin_id,
+        decision,
+        reason: moderation_reason(reason, decision == ModerationDecision::Reject)?,
+        note: moderation_note(note),
+    };
+    let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+    match cast_vote(&mut tx, id, &vote).await? {
+        VoteOutcome::Counted => {}
+        VoteOutcome::NotFound => return Err("Deer not found".into()),
+        VoteOutcome::NotPending => return Err("Only pending deer can be voted on".into()),
+        VoteOutcome::OwnSubmission => return Err("You cannot vote on your own submission".into()),
     }
+    tx.commit().await?;
+    get_deer(context, id)
+        .await?
+        .ok_or_else(|| "Deer not found".into())
+}
 
-    async fn user_comments(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Comment>> {
-        let id: Uuid = id.into();
-        let comments = query_as!(Comment, "SELECT * FROM comment WHERE user_id = $1", id)
-            .fetch_all(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(comments)
+// Table holding reportable content that can be hidden; accounts are never hidden
+fn flagged_content_table(target_type: ReportTarget) -> Option<&'static str> {
+    match target_type {
+        ReportTarget::Deer => Some("Cervidae"),
+        ReportTarget::Review => Some("Review"),
+        ReportTarget::Comment => Some("Comment"),
+        ReportTarget::User => None,
     }
+}
 
-    async fn crimes(&self, context: &Context<'_>) -> Result<Vec<Crime>> {
-        let crimes = query_as!(Crime, "SELECT * FROM crime")
-            .fetch_all(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(crimes)
+async fn report_target_exists(
+    conn: &mut PgConnection,
+    target_type: ReportTarget,
+    target_id: Uuid,
+) -> sqlx::Result<bool> {
+    let sql = match flagged_content_table(target_type) {
+        Some(table) => format!(
+            "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1 AND deleted_at IS NULL)",
+            table
+        ),
+        None => "SELECT EXISTS (SELECT 1 FROM Users WHERE id = $1 AND NOT is_reserved_user(id))"
+            .to_string(),
+    };
+    query_scalar(&sql).bind(target_id).fetch_one(conn).await
+}
+
+// Hides reported or automod-flagged content by soft deleting it as the system user
+async fn hide_flagged_content(
+    conn: &mut PgConnection,
+    target_type: ReportTarget,
+    target_id: Uuid,
+) -> sqlx::Result<bool> {
+    let Some(table) = flagged_content_table(target_type) else {
+        return Ok(false);
+    };
+    let sql = format!(
+        "UPDATE {} SET deleted_at = NOW(), deleted_by = system_user_id(), version = version + 1 \
+         WHERE id = $1 AND deleted_at IS NULL",
+        table
+    );
+    let result = query(&sql).bind(target_id).execute(conn).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+// Hides a post from everyone but its author. The version stays put so the
+// author's next edit goes through and nothing gives the hide away
+async fn shadow_hide_content(
+    conn: &mut PgConnection,
+    target_type: ReportTarget,
+    target_id: Uuid,
+) -> sqlx::Result<()> {
+    if let Some(table) = flagged_content_table(target_type) {
+        let sql = format!(
+            "UPDATE {} SET shadow_hidden = TRUE WHERE id = $1 AND deleted_at IS NULL",
+            table
+        );
+        query(&sql).bind(target_id).execute(conn).await?;
     }
+    Ok(())
+}
 
-    async fn deer_crimes(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Crime>> {
-        let id: Uuid = id.into();
-        let crimes = query_as!(Crime, "SELECT * FROM Crime WHERE id IN (SELECT crime_id FROM Crime_Cervidae WHERE cervidae_id = $1)", id)
-            .fetch_all(context.data_unchecked::<PgPool>())
+// Shows a shadow-hidden post to everyone again
+async fn unshadow_content(
+    conn: &mut PgConnection,
+    target_type: ReportTarget,
+    target_id: Uuid,
+) -> sqlx::Result<bool> {
+    let Some(table) = flagged_content_table(target_type) else {
+        return Ok(false);
+    };
+    let sql = format!(
+        "UPDATE {} SET shadow_hidden = FALSE WHERE id = $1 AND shadow_hidden",
+        table
+    );
+    let result = query(&sql).bind(target_id).execute(conn).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+// Shows content again if it is only hidden by reports or automod
+async fn unhide_flagged_content(
+    conn: &mut PgConnection,
+    target_type: ReportTarget,
+    target_id: Uuid,
+) -> sqlx::Result<bool> {
+    let Some(table) = flagged_content_table(target_type) else {
+        return Ok(false);
+    };
+    let sql = format!(
+        "UPDATE {} SET deleted_at = NULL, deleted_by = NULL, version = version + 1 \
+         WHERE id = $1 AND deleted_by = system_user_id()",
+        table
+    );
+    let result = query(&sql).bind(target_id).execute(conn).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+// Removes flagged content for good, taking over a hide left by reports or automod
+async fn remove_flagged_content(
+    conn: &mut PgConnection,
+    target_type: ReportTarget,
+    target_id: Uuid,
+    moderator_id: Uuid,
+) -> sqlx::Result<bool> {
+    let Some(table) = flagged_content_table(target_type) else {
+        return Ok(false);
+    };
+    let sql = format!(
+        "UPDATE {} SET deleted_at = COALESCE(deleted_at, NOW()), deleted_by = $2, \
+         shadow_hidden = FALSE, version = version + 1 \
+         WHERE id = $1 AND (deleted_at IS NULL OR deleted_by = system_user_id())",
+        table
+    );
+    let result = query(&sql)
+        .bind(target_id)
+        .bind(moderator_id)
+        .execute(conn)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+// Hiding or showing a review changes the danger score of its deer
+async fn refresh_flagged_scores(
+    pool: &PgPool,
+    target_type: ReportTarget,
+    target_id: Uuid,
+) -> Result<()> {
+    if target_type == ReportTarget::Review {
+        let deer_id = query_scalar!("SELECT cervidae_id FROM Review WHERE id = $1", target_id)
+            .fetch_optional(pool)
             .await?;
-        Ok(crimes)
+        if let Some(deer_id) = deer_id {
+            refresh_danger_scores(pool, Some(deer_id)).await?;
+        }
     }
+    Ok(())
+}
 
-    async fn crime_deer(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Deer>> {
-        let id: Uuid = id.into();
-        let deer = query_as("SELECT * FROM Cervidae WHERE id IN (SELECT cervidae_id FROM Crime_Cervidae WHERE crime_id = $1)")
-            .bind(id)
-            .fetch_all(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(deer)
+// Runs the automod rules on a review or comment written in tx, then commits.
+// Rejected writes are rolled back and held ones are hidden; both come back as errors.
+// Shadow-hidden writes succeed as far as their author can tell
+async fn commit_moderated_write(
+    pool: &PgPool,
+    mut tx: sqlx::Transaction<'static, Postgres>,
+    subject: &AutomodSubject<'_>,
+) -> Result<()> {
+    let verdict = automod::evaluate(&mut tx, subject).await?;
+    if verdict.action == AutomodAction::Reject {
+        tx.rollback().await?;
+        let mut conn = pool.acquire().await?;
+        automod::record_hits(&mut conn, subject, &verdict).await?;
+        return Err(
+            async_graphql::Error::new("Your post was rejected by the moderation rules")
+                .extend_with(|_, e| e.set("code", "AUTOMOD_REJECTED")),
+        );
+    }
+    match verdict.action {
+        AutomodAction::Hold => {
+            hide_flagged_content(&mut tx, subject.target_type, subject.target_id).await?;
+        }
+        AutomodAction::ShadowHide => {
+            shadow_hide_content(&mut tx, subject.target_type, subject.target_id).await?;
+        }
+        _ => {}
+    }
+    automod::record_hits(&mut tx, subject, &verdict).await?;
+    tx.commit().await?;
+    if verdict.action == AutomodAction::Hold {
+        return Err(
+            async_graphql::Error::new("Your post is held until a moderator reviews it")
+                .extend_with(|_, e| e.set("code", "HELD_FOR_REVIEW")),
+        );
     }
+    Ok(())
+}
 
-    async fn verify_token(&self, context: &Context<'_>) -> Result<Claims> {
-        let cookies = context.data::<Cookies>()?;
-        let cookie = cookies.get("cerv_token");
-        if let Some(token) = cookie {
-            let key = DecodingKey::from_secret(env::var("CLIENT_SECRET")?.as_bytes());
-            let decoded = decode::<Claims>(&token.value(), &key, &Validation::default());
-            if decoded.is_err() {
-                return Err(decoded.err().unwrap().to_string().into());
-            }
-            Ok(decoded.unwrap().claims)
-        } else {
-            Err(async_graphql::Error::new("No token found"))
+// Closes every open report against the same content as the given one
+async fn close_reports(
+    context: &Context<'_>,
+    id: Uuid,
+    status: ReportStatus,
+    resolution: &Option<String>,
+) -> Result<ContentReport> {
+    let admin_id = require_admin(context).await?;
+    let Some(report) = storage::get_report(context, id).await? else {
+        return Err("Report not found".into());
+    };
+    if report.status != ReportStatus::Open {
+        return Err("Report has already been handled".into());
+    }
+    let pool = context.data_unchecked::<PgPool>();
+    let mut tx = pool.begin().await?;
+    if status == ReportStatus::ActionTaken && report.target_type == ReportTarget::User {
+        suspend_user(&mut tx, report.target_id, admin_id).await?;
+    }
+    let changed = match status {
+        ReportStatus::ActionTaken => {
+            remove_flagged_content(&mut tx, report.target_type, report.target_id, admin_id).await?
         }
+        _ => unhide_flagged_content(&mut tx, report.target_type, report.target_id).await?,
+    };
+    query!(
+        r#"
+        UPDATE Content_Report SET status = $3, handled_by = $4, resolution = $5, resolved_at = NOW()
+         WHERE target_type = $1 AND target_id = $2 AND status = 'Open'"#,
+        report.target_type as ReportTarget,
+        report.target_id,
+        status as ReportStatus,
+        admin_id,
+        moderation_note(resolution),
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    if changed {
+        refresh_flagged_scores(pool, report.target_type, report.target_id).await?;
     }
+    storage::get_report(context, id)
+        .await?
+        .ok_or_else(|| "Report not found".into())
+}
 
-    async fn deer_connections(
-        &self,
-        context: &Context<'_>,
+// Locks a reported account out and ends its sessions. Unlike deletion this can be undone
+async fn suspend_user(conn: &mut PgConnection, user_id: Uuid, moderator_id: Uuid) -> Result<()> {
+    let suspended = query_scalar!(
+        r#"
+        UPDATE Users SET suspended_at = COALESCE(suspended_at, NOW()),
+            suspended_by = COALESCE(suspended_by, $2)
+         WHERE id = $1 AND NOT is_admin AND NOT is_reserved_user(id)
+         RETURNING id"#,
+        user_id,
+        moderator_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    if suspended.is_none() {
+        return Err("Only regular accounts can be suspended".into());
+    }
+    query!("DELETE FROM User_Session WHERE user_id = $1", user_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+// Appeals a submitter may file across all their deer in APPEAL_WINDOW_DAYS
+const MAX_APPEALS_PER_WINDOW: i64 = 3;
+const APPEAL_WINDOW_DAYS: i64 = 7;
+
+// Returns the deer if its derived kill count changed
+async fn sync_kill_count(
+    conn: &mut PgConnection,
+    deer_id: Uuid,
+    actor: Uuid,
+) -> Result<Option<Deer>> {
+    let deer = query_as("SELECT * FROM sync_kill_count($1, $2)")
+        .bind(deer_id)
+        .bind(actor)
+        .fetch_optional(conn)
+        .await?;
+    Ok(deer)
+}
+
+// Deleting or restoring a crime adds or removes its incidents from derived kill counts
+async fn sync_crime_kill_counts(
+    conn: &mut PgConnection,
+    crime_id: Uuid,
+    actor: Uuid,
+) -> Result<()> {
+    query!(
+        r#"
+        SELECT synced.id FROM (SELECT DISTINCT cervidae_id FROM Incident WHERE crime_id = $1) AS deer,
+         LATERAL sync_kill_count(deer.cervidae_id, $2) AS synced"#,
+        crime_id,
+        actor,
+    )
+    .fetch_all(conn)
+    .await?;
+    Ok(())
+}
+
+// Coordinates are optional on incidents but always come as a pair
+fn incident_coordinates(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+) -> Result<Option<(f64, f64)>> {
+    match (latitude, longitude) {
+        (Some(latitude), Some(longitude)) => {
+            check_coordinates(latitude, longitude)?;
+            Ok(Some((latitude, longitude)))
+        }
+        (None, None) => Ok(None),
+        _ => Err(async_graphql::Error::new(
+            "Invalid arguments: latitude and longitude must be given together",
+        )),
+    }
+}
+
+fn incident_sources(sources: Option<Vec<String>>) -> Vec<String> {
+    sources
+        .unwrap_or_default()
+        .iter()
+        .map(|source| source.trim().to_string())
+        .filter(|source| !source.is_empty())
+        .collect()
+}
+
+// Geographic searches return at most this many sightings, within this radius
+const MAX_SIGHTING_RESULTS: i64 = 500;
+const MAX_SIGHTING_RADIUS_KM: f64 = 1000.0;
+
+fn check_coordinates(latitude: f64, longitude: f64) -> Result<()> {
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return Err(async_graphql::Error::new(
+            "Invalid arguments: latitude must be within ±90 and longitude within ±180",
+        ));
+    }
+    Ok(())
+}
+
+// Photos are linked from sighting pages, so only web links are accepted
+fn check_photo_url(photo_url: Option<&str>) -> Result<()> {
+    let is_web_url = |url: &str| {
+        let url = url.to_ascii_lowercase();
+        url.starts_with("https://") || url.starts_with("http://")
+    };
+    if photo_url.is_some_and(|url| !is_web_url(url)) {
+        return Err(async_graphql::Error::new(
+            "Invalid arguments: photoUrl must be an http or https URL",
+        ));
+    }
+    Ok(())
+}
+
+fn check_bbox(bbox: BoundingBox) -> Result<()> {
+    check_coordinates(bbox.south, bbox.west)?;
+    check_coordinates(bbox.north, bbox.east)?;
+    if bbox.south > bbox.north {
+        return Err(async_graphql::Error::new(
+            "Invalid arguments: south must not exceed north",
+        ));
+    }
+    Ok(())
+}
+
+// Zoom 0 splits the globe into 180° cells, and each level halves them
+const MAX_HEATMAP_CELLS: f64 = 10000.0;
+
+fn heatmap_cell_size(bbox: BoundingBox, zoom: i32) -> Result<f64> {
+    let cell_size = 180.0 / f64::from(1 << zoom);
+    let width = if bbox.west <= bbox.east {
+        bbox.east - bbox.west
+    } else {
+        360.0 - (bbox.west - bbox.east)
+    };
+    let cells = ((bbox.north - bbox.south) / cell_size + 1.0) * (width / cell_size + 1.0);
+    if cells > MAX_HEATMAP_CELLS {
+        return Err(async_graphql::Error::new(
+            "Invalid arguments: zoom is too fine for a box this large",
+        ));
+    }
+    Ok(cell_size)
+}
+
+fn sighting_limit(first: Option<i64>) -> Result<i64> {
+    match first {
+        Some(first) if first <= 0 || first > MAX_SIGHTING_RESULTS => {
+            Err(async_graphql::Error::new(format!(
+                "Invalid arguments: first must be between 1 and {}",
+                MAX_SIGHTING_RESULTS
+            )))
+        }
+        Some(first) => Ok(first),
+        None => Ok(storage::DEFAULT_PAGE_SIZE),
+    }
+}
+
+const MAX_BULK_ITEMS: usize = 500;
+
+fn check_bulk_size(size: usize) -> Result<()> {
+    if size == 0 || size > MAX_BULK_ITEMS {
+        return Err(async_graphql::Error::new(format!(
+            "Invalid arguments: a batch must hold between 1 and {} items",
+            MAX_BULK_ITEMS
+        )));
+    }
+    Ok(())
+}
+
+fn bulk_deer_failure(id: Uuid, code: BulkErrorCode, message: &str) -> BulkDeerResult {
+    BulkDeerResult {
+        deer_id: id.into(),
+        ok: false,
+        error_code: Some(code),
+        message: Some(message.to_string()),
+    }
+}
+
+// Atomic batches (the default) only commit when every item succeeded
+async fn finish_bulk(
+    tx: Transaction<'_, Postgres>,
+    atomic: Option<bool>,
+    all_ok: bool,
+) -> Result<bool> {
+    if atomic.unwrap_or(true) && !all_ok {
+        tx.rollback().await?;
+        Ok(false)
+    } else {
+        tx.commit().await?;
+        Ok(true)
+    }
+}
+
+const MAX_THREAD_DEPTH: i32 = 32;
+
+fn build_comment_thread(
+    children: &mut HashMap<Option<Uuid>, Vec<CommentThreadRow>>,
+    parent_id: Option<Uuid>,
+    sort: CommentSort,
+) -> Vec<CommentThreadNode> {
+    let mut nodes: Vec<CommentThreadNode> = children
+        .remove(&parent_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|row| {
+            let replies = build_comment_thread(children, Some(row.comment.id), sort);
+            CommentThreadNode {
+                descendant_count: replies.iter().map(|reply| reply.descendant_count + 1).sum(),
+                comment: row.comment,
+                depth: row.depth,
+                replies,
+            }
+        })
+        .collect();
+    match sort {
+        CommentSort::New => nodes.sort_by_key(|node| Reverse(node.comment.created_at)),
+        CommentSort::Old => nodes.sort_by_key(|node| node.comment.created_at),
+        CommentSort::Top => nodes.sort_by(|a, b| {
+            b.comment
+                .score
+                .cmp(&a.comment.score)
+                .then(b.descendant_count.cmp(&a.descendant_count))
+                .then(b.comment.created_at.cmp(&a.comment.created_at))
+        }),
+        CommentSort::Controversial => nodes.sort_by(|a, b| {
+            b.comment
+                .controversy
+                .total_cmp(&a.comment.controversy)
+                .then(b.comment.created_at.cmp(&a.comment.created_at))
+        }),
+    }
+    nodes
+}
+
+#[Object]
+impl QueryRoot {
+    // Add your query resolvers here
+    async fn users(&self, context: &Context<'_>) -> Result<Vec<User>> {
+        let users = query_as!(User, "SELECT * FROM Users WHERE NOT is_reserved_user(id)")
+            .fetch_all(context.data_unchecked::<PgPool>())
+            .await?;
+
+        Ok(users)
+    }
+
+    async fn user(&self, context: &Context<'_>, id: UuidScalar) -> Result<Option<User>> {
+        let id: Uuid = id.into();
+        let user = query_as!(User, "SELECT * FROM Users WHERE id = $1", id)
+            .fetch_optional(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(user)
+    }
+
+    async fn deer(&self, context: &Context<'_>, id: UuidScalar) -> Result<Option<Deer>> {
+        get_deer(context, id.into()).await
+    }
+
+    async fn deer_all(&self, context: &Context<'_>) -> Result<Vec<Deer>> {
+        let deer =
+            query_as("SELECT * FROM Cervidae WHERE status = 'Approved' AND deleted_at IS NULL")
+                .fetch_all(context.data_unchecked::<PgPool>())
+                .await?;
+
+        Ok(deer)
+    }
+
+    async fn deer_pending(&self, context: &Context<'_>) -> Result<Vec<Deer>> {
+        let deer =
+            query_as("SELECT * FROM Cervidae WHERE status = 'Pending' AND deleted_at IS NULL")
+                .fetch_all(context.data_unchecked::<PgPool>())
+                .await?;
+
+        Ok(deer)
+    }
+
+    async fn deer_drafts(&self, context: &Context<'_>) -> Result<Vec<Deer>> {
+        let user_id = current_user_id(context).await?;
+        let deer = query_as(
+            r#"
+            SELECT * FROM Cervidae WHERE status = 'Draft' AND created_by = $1 AND deleted_at IS NULL
+             ORDER BY updated_at DESC"#,
+        )
+        .bind(user_id)
+        .fetch_all(context.data_unchecked::<PgPool>())
+        .await?;
+
+        Ok(deer)
+    }
+
+    async fn deer_reviews(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Review>> {
+        storage::get_reviews_by_deer(context, id.into()).await
+    }
+
+    async fn user_reviews(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Review>> {
+        storage::get_reviews_by_user(context, id.into()).await
+    }
+
+    async fn deer_comments(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Comment>> {
+        let id: Uuid = id.into();
+        let comments = query_as!(
+            Comment,
+            r#"
+            SELECT * FROM comment WHERE cervidae_id = $1
+             AND (deleted_at IS NULL OR comment_has_live_descendant(id))
+             AND (NOT shadow_hidden OR user_id = $2)
+             ORDER BY created_at DESC"#,
+            id,
+            storage::viewer_id(context)
+        )
+        .fetch_all(context.data_unchecked::<PgPool>())
+        .await?;
+        Ok(comments)
+    }
+
+    async fn comment_thread(
+        &self,
+        context: &Context<'_>,
+        deer_id: UuidScalar,
+        max_depth: Option<i32>,
+        sort: Option<CommentSort>,
+    ) -> Result<Vec<CommentThreadNode>> {
+        let max_depth = max_depth.unwrap_or(MAX_THREAD_DEPTH);
+        if !(0..=MAX_THREAD_DEPTH).contains(&max_depth) {
+            return Err(async_graphql::Error::new(format!(
+                "Invalid arguments: maxDepth must be between 0 and {}",
+                MAX_THREAD_DEPTH
+            )));
+        }
+        let rows = get_comment_thread(context, deer_id.into(), max_depth).await?;
+        let mut children: HashMap<Option<Uuid>, Vec<CommentThreadRow>> = HashMap::new();
+        for row in rows {
+            children.entry(row.comment.parent_id).or_default().push(row);
+        }
+        Ok(build_comment_thread(
+            &mut children,
+            None,
+            sort.unwrap_or(CommentSort::New),
+        ))
+    }
+
+    async fn user_comments(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Comment>> {
+        storage::get_comments_by_user(context, id.into()).await
+    }
+
+    async fn crimes(&self, context: &Context<'_>) -> Result<Vec<Crime>> {
+        let crimes = query_as!(Crime, "SELECT * FROM crime WHERE deleted_at IS NULL")
+            .fetch_all(context.data_unchecked::<PgPool>())
+            .await?;
+        Ok(crimes)
+    }
+
+    async fn deer_crimes(&self, context: &Context<'_>, id: UuidScalar) -> Result<Vec<Crime>> {
+        storage::get_crimes_by_deer(context, id.into()).await
+    }
+
+    /// Deer linked to one crime, or to any live crime in a category and its subcategories
+    async fn crime_deer(
+        &self,
+        context: &Context<'_>,
+        id: Option<UuidScalar>,
+        category_id: Option<UuidScalar>,
+    ) -> Result<Vec<Deer>> {
+        let deer = match (id, category_id) {
+            (Some(id), None) => {
+                query_as("SELECT * FROM Cervidae WHERE id IN (SELECT cervidae_id FROM Crime_Cervidae WHERE crime_id = $1) AND deleted_at IS NULL")
+                    .bind(Uuid::from(id))
+                    .fetch_all(context.data_unchecked::<PgPool>())
+                    .await?
+            }
+            (None, Some(category_id)) => {
+                query_as(
+                    r#"
+                    SELECT * FROM Cervidae WHERE id IN (
+                        SELECT cervidae_id FROM Crime_Cervidae
+                        JOIN Crime ON Crime.id = Crime_Cervidae.crime_id
+                        WHERE Crime.deleted_at IS NULL
+                         AND Crime.category_id IN (SELECT crime_category_subtree($1))
+                    ) AND deleted_at IS NULL"#,
+                )
+                .bind(Uuid::from(category_id))
+                .fetch_all(context.data_unchecked::<PgPool>())
+                .await?
+            }
+            _ => return Err("Provide exactly one of id or categoryId".into()),
+        };
+        Ok(deer)
+    }
+
+    async fn deer_revision_diff(
+        &self,
+        context: &Context<'_>,
+        a: UuidScalar,
+        b: UuidScalar,
+    ) -> Result<Vec<RevisionFieldDiff>> {
+        let (Some(a), Some(b)) = (
+            get_revision(context, a.into()).await?,
+            get_revision(context, b.into()).await?,
+        ) else {
+            return Err("Revision not found".into());
+        };
+        if a.cervidae_id != b.cervidae_id {
+            return Err("Revisions belong to different deer".into());
+        }
+        Ok(a.fields()
+            .into_iter()
+            .zip(b.fields())
+            .filter(|((_, before), (_, after))| before != after)
+            .map(|((field, before), (_, after))| RevisionFieldDiff {
+                field: field.to_string(),
+                before,
+                after,
+            })
+            .collect())
+    }
+
+    async fn report_queue(
+        &self,
+        context: &Context<'_>,
+        target_type: Option<ReportTarget>,
+        first: Option<i64>,
         after: Option<UuidScalar>,
-        before: Option<UuidScalar>,
+    ) -> Result<ContentReportConnection> {
+        require_admin(context).await?;
+        storage::get_report_queue(context, target_type, first, after.map(Uuid::from)).await
+    }
+
+    async fn automod_rules(&self, context: &Context<'_>) -> Result<Vec<AutomodRule>> {
+        require_admin(context).await?;
+        let rules = query_as("SELECT * FROM Automod_Rule ORDER BY priority DESC, created_at")
+            .fetch_all(context.data_unchecked::<PgPool>())
+            .await?;
+        Ok(rules)
+    }
+
+    async fn automod_hits(
+        &self,
+        context: &Context<'_>,
+        target_type: Option<ReportTarget>,
+        target_id: Option<UuidScalar>,
+        action: Option<AutomodAction>,
         first: Option<i64>,
-        last: Option<i64>,
-    ) -> Result<Vec<DeerConnection>> {
-        let after = after.map(|x| x.into());
-        let before = before.map(|x| x.into());
-        deer_page(
+    ) -> Result<Vec<AutomodHit>> {
+        require_admin(context).await?;
+        storage::get_automod_hits(
             context,
-            after,
-            before,
+            target_type,
+            target_id.map(Uuid::from),
+            action,
             first,
-            last,
-            DeerEntryStatus::Approved,
-            None,
         )
-        .await
+        .await
+    }
+
+    async fn automod_hold_queue(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+    ) -> Result<Vec<AutomodHit>> {
+        require_admin(context).await?;
+        storage::get_automod_hold_queue(context, first).await
+    }
+
+    /// The families when no parent is given
+    async fn taxa(
+        &self,
+        context: &Context<'_>,
+        parent_id: Option<UuidScalar>,
+    ) -> Result<Vec<Taxon>> {
+        storage::get_taxon_children(context, parent_id.map(Uuid::from)).await
+    }
+
+    async fn taxon(&self, context: &Context<'_>, id: UuidScalar) -> Result<Option<Taxon>> {
+        storage::get_taxon(context, id.into()).await
+    }
+
+    async fn genus_stats(&self, context: &Context<'_>) -> Result<Vec<GenusStats>> {
+        storage::get_genus_stats(context).await
+    }
+
+    /// The top-level categories when no parent is given
+    async fn crime_categories(
+        &self,
+        context: &Context<'_>,
+        parent_id: Option<UuidScalar>,
+    ) -> Result<Vec<CrimeCategoryNode>> {
+        storage::get_crime_category_children(context, parent_id.map(Uuid::from)).await
+    }
+
+    async fn crime_category(
+        &self,
+        context: &Context<'_>,
+        id: Option<UuidScalar>,
+        slug: Option<String>,
+    ) -> Result<Option<CrimeCategoryNode>> {
+        match (id, slug) {
+            (Some(id), None) => storage::get_crime_category(context, id.into()).await,
+            (None, Some(slug)) => storage::get_crime_category_by_slug(context, &slug).await,
+            _ => Err("Provide exactly one of id or slug".into()),
+        }
+    }
+
+    async fn statutes(
+        &self,
+        context: &Context<'_>,
+        crime_id: Option<UuidScalar>,
+        jurisdiction: Option<String>,
+    ) -> Result<Vec<Statute>> {
+        storage::get_statutes(context, crime_id.map(Uuid::from), jurisdiction.as_deref()).await
+    }
+
+    async fn statute(&self, context: &Context<'_>, id: UuidScalar) -> Result<Option<Statute>> {
+        storage::get_statute(context, id.into()).await
+    }
+
+    async fn case(&self, context: &Context<'_>, id: UuidScalar) -> Result<Option<Case>> {
+        storage::get_case(context, id.into()).await
+    }
+
+    /// Looks the tag up by any spelling of it or of a synonym
+    async fn tag(&self, context: &Context<'_>, slug: String) -> Result<Option<Tag>> {
+        storage::get_tag_by_slug(context, &tag_slug(&slug)).await
+    }
+
+    /// Tags describing the most approved entries, banned tags aside
+    async fn popular_tags(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+    ) -> Result<Vec<PopularTag>> {
+        storage::get_popular_tags(context, first).await
+    }
+
+    async fn sighting(&self, context: &Context<'_>, id: UuidScalar) -> Result<Option<Sighting>> {
+        storage::get_sighting(context, id.into()).await
+    }
+
+    /// Most recently observed first; a west edge east of the east edge wraps the antimeridian
+    #[allow(clippy::too_many_arguments)]
+    async fn sightings_in_box(
+        &self,
+        context: &Context<'_>,
+        south: f64,
+        west: f64,
+        north: f64,
+        east: f64,
+        filter: Option<SightingFilter>,
+        first: Option<i64>,
+    ) -> Result<Vec<Sighting>> {
+        let bbox = BoundingBox {
+            south,
+            west,
+            north,
+            east,
+        };
+        check_bbox(bbox)?;
+        let limit = sighting_limit(first)?;
+        storage::get_sightings_in_box(context, bbox, &filter.unwrap_or_default(), limit).await
+    }
+
+    /// Counts sightings per grid cell inside the box, for drawing heatmaps
+    async fn sighting_heatmap(
+        &self,
+        context: &Context<'_>,
+        bbox: BoundingBox,
+        #[graphql(validator(minimum = 0, maximum = 16))] zoom: i32,
+        since: Option<NaiveDateTimeScalar>,
+        #[graphql(validator(max_items = 500))] deer_ids: Option<Vec<UuidScalar>>,
+    ) -> Result<SightingHeatmap> {
+        check_bbox(bbox)?;
+        let cell_size = heatmap_cell_size(bbox, zoom)?;
+        let filter = SightingFilter {
+            observed_after: since,
+            ..Default::default()
+        };
+        let deer_ids: Vec<Uuid> = deer_ids
+            .unwrap_or_default()
+            .into_iter()
+            .map(Uuid::from)
+            .collect();
+        let cells =
+            storage::get_sighting_heatmap(context, bbox, cell_size, &filter, &deer_ids).await?;
+        Ok(SightingHeatmap {
+            cell_size_degrees: cell_size,
+            total_count: cells.iter().map(|cell| cell.count).sum(),
+            cells,
+        })
+    }
+
+    /// Nearest first
+    async fn sightings_near(
+        &self,
+        context: &Context<'_>,
+        latitude: f64,
+        longitude: f64,
+        radius_km: f64,
+        filter: Option<SightingFilter>,
+        first: Option<i64>,
+    ) -> Result<Vec<NearbySighting>> {
+        check_coordinates(latitude, longitude)?;
+        if !(radius_km > 0.0 && radius_km <= MAX_SIGHTING_RADIUS_KM) {
+            return Err(format!(
+                "Invalid arguments: radiusKm must be above 0 and at most {}",
+                MAX_SIGHTING_RADIUS_KM
+            )
+            .into());
+        }
+        let limit = sighting_limit(first)?;
+        storage::get_sightings_near(
+            context,
+            (latitude, longitude),
+            radius_km,
+            &filter.unwrap_or_default(),
+            limit,
+        )
+        .await
+    }
+
+    async fn appeal_queue(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+    ) -> Result<Vec<DeerAppeal>> {
+        let admin_id = require_admin(context).await?;
+        storage::get_appeal_queue(context, admin_id, first).await
+    }
+
+    async fn threat_config(&self, context: &Context<'_>) -> Result<ThreatConfig> {
+        require_admin(context).await?;
+        storage::get_threat_config(context).await
+    }
+
+    async fn moderation_config(&self, context: &Context<'_>) -> Result<ModerationConfig> {
+        require_admin(context).await?;
+        storage::get_moderation_config(context).await
+    }
+
+    async fn leaderboards(&self, period: Option<LeaderboardPeriod>) -> Leaderboards {
+        Leaderboards {
+            period: period.unwrap_or(LeaderboardPeriod::AllTime),
+        }
+    }
+
+    async fn verify_token(&self, context: &Context<'_>) -> Result<Claims> {
+        current_claims(context).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn deer_connections(
+        &self,
+        context: &Context<'_>,
+        after: Option<UuidScalar>,
+        before: Option<UuidScalar>,
+        first: Option<i64>,
+        last: Option<i64>,
+        filter: Option<DeerFilter>,
+        sort: Option<DeerSort>,
+        descending: Option<bool>,
+    ) -> Result<Vec<DeerConnection>> {
+        let after = after.map(|x| x.into());
+        let before = before.map(|x| x.into());
+        deer_page(
+            context,
+            after,
+            before,
+            first,
+            last,
+            DeerPageQuery {
+                status: DeerEntryStatus::Approved,
+                created_by: None,
+                filter: filter.unwrap_or_default(),
+                sort: sort.unwrap_or(DeerSort::Id),
+                descending: descending.unwrap_or(false),
+            },
+        )
+        .await
+    }
+
+    async fn ranked_deer(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+        after: Option<UuidScalar>,
+        weighted: Option<bool>,
+    ) -> Result<Vec<DeerConnection>> {
+        let sort = if weighted.unwrap_or(true) {
+            DeerSort::WeightedDangerScore
+        } else {
+            DeerSort::DangerScore
+        };
+        deer_page(
+            context,
+            after.map(|x| x.into()),
+            None,
+            Some(first.unwrap_or(storage::DEFAULT_PAGE_SIZE)),
+            None,
+            DeerPageQuery {
+                status: DeerEntryStatus::Approved,
+                created_by: None,
+                filter: DeerFilter::default(),
+                sort,
+                descending: true,
+            },
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn deer_pending_connections(
+        &self,
+        context: &Context<'_>,
+        after: Option<UuidScalar>,
+        before: Option<UuidScalar>,
+        first: Option<i64>,
+        last: Option<i64>,
+        filter: Option<DeerFilter>,
+        sort: Option<DeerSort>,
+        descending: Option<bool>,
+    ) -> Result<Vec<DeerConnection>> {
+        let after = after.map(|x| x.into());
+        let before = before.map(|x| x.into());
+        deer_page(
+            context,
+            after,
+            before,
+            first,
+            last,
+            DeerPageQuery {
+                status: DeerEntryStatus::Pending,
+                created_by: None,
+                filter: filter.unwrap_or_default(),
+                sort: sort.unwrap_or(DeerSort::Id),
+                descending: descending.unwrap_or(false),
+            },
+        )
+        .await
+    }
+    #[allow(clippy::too_many_arguments)]
+    async fn deer_rejected_connections(
+        &self,
+        context: &Context<'_>,
+        after: Option<UuidScalar>,
+        before: Option<UuidScalar>,
+        first: Option<i64>,
+        last: Option<i64>,
+        id: Option<UuidScalar>,
+        filter: Option<DeerFilter>,
+        sort: Option<DeerSort>,
+        descending: Option<bool>,
+    ) -> Result<Vec<DeerConnection>> {
+        let after = after.map(|x| x.into());
+        let before = before.map(|x| x.into());
+        let id: Option<Uuid> = id.map(|x| x.into());
+        deer_page(
+            context,
+            after,
+            before,
+            first,
+            last,
+            DeerPageQuery {
+                status: DeerEntryStatus::Rejected,
+                created_by: id,
+                filter: filter.unwrap_or_default(),
+                sort: sort.unwrap_or(DeerSort::Id),
+                descending: descending.unwrap_or(false),
+            },
+        )
+        .await
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    // Add your mutation resolvers here
+    async fn create_user(&self, context: &Context<'_>, input: CreateUserInput) -> Result<User> {
+        let user_id = uuid::Uuid::new_v4();
+        let name = normalize_name(&input.name)?;
+        let hashed = hash(input.password, 10)?;
+        let user = query_as!(
+            User,
+            r#"
+            INSERT INTO Users (id, name, email, password)
+             VALUES ($1, $2, $3, $4) RETURNING *"#,
+            user_id,
+            name,
+            input.email.trim(),
+            hashed,
+        )
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(email_taken)?;
+        Ok(user)
+    }
+
+    async fn update_user(&self, context: &Context<'_>, input: UpdateUserInput) -> Result<User> {
+        if input.is_empty() {
+            return Err(async_graphql::Error::new_with_source(
+                "No update fields provided",
+            ));
+        }
+        let user_id = Uuid::from(input.id);
+        let name = input.name.as_deref().map(normalize_name).transpose()?;
+        let email = input.email.as_deref().map(str::trim);
+        let mut query = sqlx::QueryBuilder::new("UPDATE Users SET updated_at = NOW()");
+        if let Some(name) = &name {
+            add_to_query(&mut query, "name", name);
+        }
+        if let Some(email) = &email {
+            add_to_query(&mut query, "email", email);
+        }
+        query.push(" WHERE id = ");
+        query.push_bind(user_id);
+        query.push(" RETURNING *;");
+        let user: User = query
+            .build_query_as()
+            .fetch_one(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(email_taken)?;
+        Ok(user)
+    }
+
+    async fn approve_deer(
+        &self,
+        context: &Context<'_>,
+        id: UuidScalar,
+        approve: bool,
+        #[graphql(validator(chars_max_length = 2000))] reason: Option<String>,
+        #[graphql(validator(chars_max_length = 2000))] note: Option<String>,
+    ) -> Result<Deer> {
+        let decision = if approve {
+            ModerationDecision::Approve
+        } else {
+            ModerationDecision::Reject
+        };
+        vote_on_deer(context, id.into(), decision, &reason, &note).await
+    }
+
+    async fn cast_moderation_vote(
+        &self,
+        context: &Context<'_>,
+        deer_id: UuidScalar,
+        vote: ModerationDecision,
+        #[graphql(validator(chars_max_length = 2000))] reason: Option<String>,
+        #[graphql(validator(chars_max_length = 2000))] note: Option<String>,
+    ) -> Result<Deer> {
+        vote_on_deer(context, deer_id.into(), vote, &reason, &note).await
+    }
+
+    async fn update_threat_config(
+        &self,
+        context: &Context<'_>,
+        input: UpdateThreatConfigInput,
+    ) -> Result<ThreatConfig> {
+        let admin_id = require_admin(context).await?;
+        let weights = [
+            input.severity_weight,
+            input.frequency_weight,
+            input.kill_weight,
+            input.danger_weight,
+        ];
+        if weights
+            .iter()
+            .flatten()
+            .any(|weight| !weight.is_finite() || *weight < 0.0)
+        {
+            return Err("Invalid arguments: weights must be zero or positive".into());
+        }
+        let mut query = QueryBuilder::new("UPDATE Threat_Config SET updated_at = NOW()");
+        add_to_query(&mut query, "updated_by", &admin_id);
+        if let Some(severity_weight) = &input.severity_weight {
+            add_to_query(&mut query, "severity_weight", severity_weight);
+        }
+        if let Some(frequency_weight) = &input.frequency_weight {
+            add_to_query(&mut query, "frequency_weight", frequency_weight);
+        }
+        if let Some(kill_weight) = &input.kill_weight {
+            add_to_query(&mut query, "kill_weight", kill_weight);
+        }
+        if let Some(danger_weight) = &input.danger_weight {
+            add_to_query(&mut query, "danger_weight", danger_weight);
+        }
+        if let Some(frequency_window_days) = &input.frequency_window_days {
+            add_to_query(&mut query, "frequency_window_days", frequency_window_days);
+        }
+        if let Some(frequency_half_count) = &input.frequency_half_count {
+            add_to_query(&mut query, "frequency_half_count", frequency_half_count);
+        }
+        if let Some(kill_count_cap) = &input.kill_count_cap {
+            add_to_query(&mut query, "kill_count_cap", kill_count_cap);
+        }
+        query.push(" RETURNING *;");
+        let config = query
+            .build_query_as()
+            .fetch_one(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(e) if e.is_check_violation() => {
+                    async_graphql::Error::new("At least one weight must be positive")
+                }
+                e => e.into(),
+            })?;
+        Ok(config)
+    }
+
+    async fn update_moderation_config(
+        &self,
+        context: &Context<'_>,
+        input: UpdateModerationConfigInput,
+    ) -> Result<ModerationConfig> {
+        let admin_id = require_admin(context).await?;
+        let mut query = QueryBuilder::new("UPDATE Moderation_Config SET updated_at = NOW()");
+        add_to_query(&mut query, "updated_by", &admin_id);
+        if let Some(approve_quorum) = &input.approve_quorum {
+            add_to_query(&mut query, "approve_quorum", approve_quorum);
+        }
+        if let Some(voting_window_hours) = &input.voting_window_hours {
+            add_to_query(&mut query, "voting_window_hours", voting_window_hours);
+        }
+        if let Some(min_window_votes) = &input.min_window_votes {
+            add_to_query(&mut query, "min_window_votes", min_window_votes);
+        }
+        if let Some(report_hide_threshold) = &input.report_hide_threshold {
+            add_to_query(&mut query, "report_hide_threshold", report_hide_threshold);
+        }
+        query.push(" RETURNING *;");
+        let config = query
+            .build_query_as()
+            .fetch_one(context.data_unchecked::<PgPool>())
+            .await?;
+        Ok(config)
+    }
+
+    async fn submit_deer(&self, context: &Context<'_>, id: UuidScalar) -> Result<Deer> {
+        transition_deer(context, id.into(), DeerEntryStatus::Pending, None, None).await
+    }
+
+    async fn archive_deer(
+        &self,
+        context: &Context<'_>,
+        id: UuidScalar,
+        #[graphql(validator(chars_max_length = 2000))] reason: Option<String>,
+    ) -> Result<Deer> {
+        transition_deer(
+            context,
+            id.into(),
+            DeerEntryStatus::Archived,
+            moderation_reason(&reason, false)?,
+            None,
+        )
+        .await
+    }
+
+    async fn bulk_moderate_deer(
+        &self,
+        context: &Context<'_>,
+        ids: Vec<UuidScalar>,
+        decision: ModerationDecision,
+        #[graphql(validator(chars_max_length = 2000))] reason: Option<String>,
+        #[graphql(validator(chars_max_length = 2000))] note: Option<String>,
+        atomic: Option<bool>,
+    ) -> Result<BulkModerationOutcome> {
+        let admin_id = require_admin(context).await?;
+        check_bulk_size(ids.len())?;
+        let vote = ModerationVote {
+            voter_id: admin_id,
+            decision,
+            reason: moderation_reason(&reason, decision == ModerationDecision::Reject)?,
+            note: moderation_note(&note),
+        };
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let mut seen = HashSet::new();
+        let mut results = Vec::with_capacity(ids.len());
+        for id in ids.into_iter().map(Uuid::from) {
+            if !seen.insert(id) {
+                results.push(bulk_deer_failure(
+                    id,
+                    BulkErrorCode::Duplicate,
+                    "Deer listed more than once",
+                ));
+                continue;
+            }
+            // Each item runs in its own savepoint so one failure doesn't poison the batch
+            let mut savepoint = tx.begin().await?;
+            let result = match cast_vote(&mut savepoint, id, &vote).await {
+                Ok(VoteOutcome::Counted) => {
+                    savepoint.commit().await?;
+                    BulkDeerResult {
+                        deer_id: id.into(),
+                        ok: true,
+                        error_code: None,
+                        message: None,
+                    }
+                }
+                Ok(VoteOutcome::NotFound) => {
+                    bulk_deer_failure(id, BulkErrorCode::NotFound, "Deer not found")
+                }
+                Ok(VoteOutcome::NotPending) => {
+                    bulk_deer_failure(id, BulkErrorCode::InvalidStatus, "Deer is not pending")
+                }
+                Ok(VoteOutcome::OwnSubmission) => bulk_deer_failure(
+                    id,
+                    BulkErrorCode::OwnSubmission,
+                    "You cannot vote on your own submission",
+                ),
+                Err(e) => bulk_deer_failure(id, BulkErrorCode::DatabaseError, &e.to_string()),
+            };
+            results.push(result);
+        }
+        let committed = finish_bulk(tx, atomic, results.iter().all(|result| result.ok)).await?;
+        if !committed {
+            for result in results.iter_mut().filter(|result| result.ok) {
+                result.ok = false;
+                result.error_code = Some(BulkErrorCode::RolledBack);
+                result.message = Some("Rolled back because another item failed".to_string());
+            }
+        }
+        info!(
+            "Admin {} cast {} moderation votes",
+            admin_id,
+            results.iter().filter(|result| result.ok).count()
+        );
+        Ok(BulkModerationOutcome { committed, results })
+    }
+
+    async fn bulk_assign_crimes(
+        &self,
+        context: &Context<'_>,
+        deer_ids: Vec<UuidScalar>,
+        crime_ids: Vec<UuidScalar>,
+        atomic: Option<bool>,
+    ) -> Result<BulkAssignmentOutcome> {
+        require_admin(context).await?;
+        check_bulk_size(deer_ids.len() * crime_ids.len())?;
+        let deer_ids: Vec<Uuid> = deer_ids.into_iter().map(Uuid::from).collect();
+        let crime_ids: Vec<Uuid> = crime_ids.into_iter().map(Uuid::from).collect();
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let known_deer: HashSet<Uuid> = query_scalar!(
+            "SELECT id FROM Cervidae WHERE id = ANY($1) AND deleted_at IS NULL",
+            &deer_ids
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
+        let known_crimes: HashSet<Uuid> = query_scalar!(
+            "SELECT id FROM Crime WHERE id = ANY($1) AND deleted_at IS NULL",
+            &crime_ids
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
+
+        let mut seen = HashSet::new();
+        let mut results = Vec::with_capacity(deer_ids.len() * crime_ids.len());
+        for &deer_id in &deer_ids {
+            for &crime_id in &crime_ids {
+                let failure = |code, message: &str| BulkCrimeAssignmentResult {
+                    deer_id: deer_id.into(),
+                    crime_id: crime_id.into(),
+                    ok: false,
+                    error_code: Some(code),
+                    message: Some(message.to_string()),
+                };
+                if !seen.insert((deer_id, crime_id)) {
+                    results.push(failure(
+                        BulkErrorCode::Duplicate,
+                        "Pair listed more than once",
+                    ));
+                    continue;
+                }
+                if !known_deer.contains(&deer_id) {
+                    results.push(failure(BulkErrorCode::NotFound, "Deer not found"));
+                    continue;
+                }
+                if !known_crimes.contains(&crime_id) {
+                    results.push(failure(BulkErrorCode::NotFound, "Crime not found"));
+                    continue;
+                }
+                let mut savepoint = tx.begin().await?;
+                let inserted =
+                    query("INSERT INTO crime_cervidae (crime_id, cervidae_id) VALUES ($1, $2)")
+                        .bind(crime_id)
+                        .bind(deer_id)
+                        .execute(&mut *savepoint)
+                        .await;
+                let result = match inserted {
+                    Ok(_) => {
+                        savepoint.commit().await?;
+                        BulkCrimeAssignmentResult {
+                            deer_id: deer_id.into(),
+                            crime_id: crime_id.into(),
+                            ok: true,
+                            error_code: None,
+                            message: None,
+                        }
+                    }
+                    Err(sqlx::Error::Database(e)) if e.is_unique_violation() => failure(
+                        BulkErrorCode::AlreadyAssigned,
+                        "Crime is already assigned to this deer",
+                    ),
+                    Err(e) => failure(BulkErrorCode::DatabaseError, &e.to_string()),
+                };
+                results.push(result);
+            }
+        }
+        let committed = finish_bulk(tx, atomic, results.iter().all(|result| result.ok)).await?;
+        if !committed {
+            for result in results.iter_mut().filter(|result| result.ok) {
+                result.ok = false;
+                result.error_code = Some(BulkErrorCode::RolledBack);
+                result.message = Some("Rolled back because another item failed".to_string());
+            }
+        }
+        Ok(BulkAssignmentOutcome { committed, results })
+    }
+
+    async fn resubmit_deer(
+        &self,
+        context: &Context<'_>,
+        id: UuidScalar,
+        #[graphql(validator(chars_max_length = 2000))] reason: Option<String>,
+    ) -> Result<Deer> {
+        let id = Uuid::from(id);
+        let appeal_open = query_scalar!(
+            "SELECT EXISTS (SELECT 1 FROM Deer_Appeal WHERE cervidae_id = $1 AND status = 'Open')",
+            id
+        )
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await?;
+        if appeal_open == Some(true) {
+            return Err("An appeal against this rejection is still open".into());
+        }
+        transition_deer(
+            context,
+            id,
+            DeerEntryStatus::Pending,
+            moderation_reason(&reason, false)?,
+            None,
+        )
+        .await
+    }
+
+    async fn vote_review(
+        &self,
+        context: &Context<'_>,
+        review_id: UuidScalar,
+        vote: Option<ReviewVote>,
+    ) -> Result<Review> {
+        let user_id = current_user_id(context).await?;
+        let review_id = Uuid::from(review_id);
+        let Some(review) = storage::get_review_by_id(context, review_id).await? else {
+            return Err("Review not found".into());
+        };
+        if review.user_id == user_id {
+            return Err("You cannot vote on your own review".into());
+        }
+        let pool = context.data_unchecked::<PgPool>();
+        match vote {
+            Some(vote) => {
+                query!(
+                    r#"
+                    INSERT INTO Review_Vote (user_id, review_id, value) VALUES ($1, $2, $3)
+                     ON CONFLICT (user_id, review_id) DO UPDATE SET
+                        value = EXCLUDED.value,
+                        updated_at = NOW()
+                     WHERE Review_Vote.value <> EXCLUDED.value"#,
+                    user_id,
+                    review_id,
+                    vote.value()
+                )
+                .execute(pool)
+                .await?;
+            }
+            None => {
+                query!(
+                    "DELETE FROM Review_Vote WHERE user_id = $1 AND review_id = $2",
+                    user_id,
+                    review_id
+                )
+                .execute(pool)
+                .await?;
+            }
+        }
+        storage::get_review_by_id(context, review_id)
+            .await?
+            .ok_or_else(|| "Review not found".into())
+    }
+
+    async fn vote_comment(
+        &self,
+        context: &Context<'_>,
+        comment_id: UuidScalar,
+        vote: Option<CommentVote>,
+    ) -> Result<Comment> {
+        let user_id = current_user_id(context).await?;
+        let comment_id = Uuid::from(comment_id);
+        let comment = get_comment(context, comment_id)
+            .await?
+            .filter(|comment| comment.deleted_at.is_none());
+        let Some(comment) = comment else {
+            return Err("Comment not found".into());
+        };
+        if comment.user_id == user_id {
+            return Err("You cannot vote on your own comment".into());
+        }
+        let pool = context.data_unchecked::<PgPool>();
+        match vote {
+            Some(vote) => {
+                query!(
+                    r#"
+                    INSERT INTO Comment_Vote (user_id, comment_id, value) VALUES ($1, $2, $3)
+                     ON CONFLICT (user_id, comment_id) DO UPDATE SET
+                        value = EXCLUDED.value,
+                        updated_at = NOW()
+                     WHERE Comment_Vote.value <> EXCLUDED.value"#,
+                    user_id,
+                    comment_id,
+                    vote.value()
+                )
+                .execute(pool)
+                .await?;
+            }
+            None => {
+                query!(
+                    "DELETE FROM Comment_Vote WHERE user_id = $1 AND comment_id = $2",
+                    user_id,
+                    comment_id
+                )
+                .execute(pool)
+                .await?;
+            }
+        }
+        get_comment(context, comment_id)
+            .await?
+            .ok_or_else(|| "Comment not found".into())
+    }
+
+    async fn report_content(
+        &self,
+        context: &Context<'_>,
+        target_type: ReportTarget,
+        target_id: UuidScalar,
+        reason: ReportReason,
+        #[graphql(validator(chars_max_length = 2000))] details: Option<String>,
+    ) -> Result<ContentReport> {
+        let user_id = current_user_id(context).await?;
+        let target_id = Uuid::from(target_id);
+        if target_type == ReportTarget::User && target_id == user_id {
+            return Err("You cannot report yourself".into());
+        }
+        let pool = context.data_unchecked::<PgPool>();
+        let mut tx = pool.begin().await?;
+        if !report_target_exists(&mut tx, target_type, target_id).await? {
+            return Err(format!("{:?} not found", target_type).into());
+        }
+        let report: Option<ContentReport> = query_as(
+            r#"
+            INSERT INTO Content_Report (id, target_type, target_id, reporter_id, reason, details)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (target_type, target_id, reporter_id) WHERE status = 'Open' DO NOTHING
+             RETURNING *"#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(target_type)
+        .bind(target_id)
+        .bind(user_id)
+        .bind(reason)
+        .bind(moderation_note(&details))
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(report) = report else {
+            return Err("You have already reported this".into());
+        };
+        let reporters = query_scalar!(
+            r#"
+            SELECT COUNT(DISTINCT reporter_id) FROM Content_Report
+             WHERE target_type = $1 AND target_id = $2 AND status = 'Open'"#,
+            target_type as ReportTarget,
+            target_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        let threshold = query_scalar!("SELECT report_hide_threshold FROM Moderation_Config")
+            .fetch_one(&mut *tx)
+            .await?;
+        let hidden = reporters.unwrap_or(0) >= threshold as i64
+            && hide_flagged_content(&mut tx, target_type, target_id).await?;
+        tx.commit().await?;
+        if hidden {
+            info!("Hid reported {:?} {}", target_type, target_id);
+            refresh_flagged_scores(pool, target_type, target_id).await?;
+        }
+        Ok(report)
+    }
+
+    async fn resolve_report(
+        &self,
+        context: &Context<'_>,
+        id: UuidScalar,
+        #[graphql(validator(chars_max_length = 2000))] resolution: Option<String>,
+    ) -> Result<ContentReport> {
+        close_reports(context, id.into(), ReportStatus::Resolved, &resolution).await
+    }
+
+    async fn dismiss_report(
+        &self,
+        context: &Context<'_>,
+        id: UuidScalar,
+        #[graphql(validator(chars_max_length = 2000))] resolution: Option<String>,
+    ) -> Result<ContentReport> {
+        close_reports(context, id.into(), ReportStatus::Dismissed, &resolution).await
+    }
+
+    async fn take_report_action(
+        &self,
+        context: &Context<'_>,
+        id: UuidScalar,
+        #[graphql(validator(chars_max_length = 2000))] resolution: Option<String>,
+    ) -> Result<ContentReport> {
+        close_reports(context, id.into(), ReportStatus::ActionTaken, &resolution).await
+    }
+
+    async fn create_automod_rule(
+        &self,
+        context: &Context<'_>,
+        input: CreateAutomodRuleInput,
+    ) -> Result<AutomodRule> {
+        let admin_id = require_admin(context).await?;
+        let pool = context.data_unchecked::<PgPool>();
+        let mut tx = pool.begin().await?;
+        let rule: AutomodRule = query_as(
+            r#"
+            INSERT INTO Automod_Rule (id, name, kind, action, patterns, threshold, window_minutes,
+                applies_to_reviews, applies_to_comments, enabled, priority, created_by, updated_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $12)
+             RETURNING *"#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(&input.name)
+        .bind(input.kind)
+        .bind(input.action)
+        .bind(input.patterns.unwrap_or_default())
+        .bind(input.threshold)
+        .bind(input.window_minutes)
+        .bind(input.applies_to_reviews.unwrap_or(true))
+        .bind(input.applies_to_comments.unwrap_or(true))
+        .bind(input.enabled.unwrap_or(true))
+        .bind(input.priority.unwrap_or(0))
+        .bind(admin_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        automod::check_rule(&rule)?;
+        tx.commit().await?;
+        Ok(rule)
+    }
+
+    async fn update_automod_rule(
+        &self,
+        context: &Context<'_>,
+        input: UpdateAutomodRuleInput,
+    ) -> Result<AutomodRule> {
+        let admin_id = require_admin(context).await?;
+        let mut query = QueryBuilder::new("UPDATE Automod_Rule SET updated_at = NOW()");
+        add_to_query(&mut query, "updated_by", &admin_id);
+        if let Some(name) = &input.name {
+            add_to_query(&mut query, "name", name);
+        }
+        if let Some(action) = &input.action {
+            add_to_query(&mut query, "action", action);
+        }
+        if let Some(patterns) = &input.patterns {
+            add_to_query(&mut query, "patterns", patterns);
+        }
+        if let Some(threshold) = &input.threshold {
+            add_to_query(&mut query, "threshold", threshold);
+        }
+        if let Some(window_minutes) = &input.window_minutes {
+            add_to_query(&mut query, "window_minutes", window_minutes);
+        }
+        if let Some(applies_to_reviews) = &input.applies_to_reviews {
+            add_to_query(&mut query, "applies_to_reviews", applies_to_reviews);
+        }
+        if let Some(applies_to_comments) = &input.applies_to_comments {
+            add_to_query(&mut query, "applies_to_comments", applies_to_comments);
+        }
+        if let Some(enabled) = &input.enabled {
+            add_to_query(&mut query, "enabled", enabled);
+        }
+        if let Some(priority) = &input.priority {
+            add_to_query(&mut query, "priority", priority);
+        }
+        query.push(" WHERE id = ");
+        query.push_bind(Uuid::from(input.id));
+        query.push(" RETURNING *;");
+
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let rule: Option<AutomodRule> = query.build_query_as().fetch_optional(&mut *tx).await?;
+        let Some(rule) = rule else {
+            return Err("Rule not found".into());
+        };
+        automod::check_rule(&rule)?;
+        tx.commit().await?;
+        Ok(rule)
+    }
+
+    async fn delete_automod_rule(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        require_admin(context).await?;
+        let result = query!("DELETE FROM Automod_Rule WHERE id = $1", Uuid::from(id))
+            .execute(context.data_unchecked::<PgPool>())
+            .await?;
+        match result.rows_affected() {
+            0 => Err("Rule not found".into()),
+            _ => Ok("Rule deleted successfully".to_string()),
+        }
+    }
+
+    // Shows held or shadow-hidden content again, or removes it, and closes its other holds
+    async fn review_automod_hold(
+        &self,
+        context: &Context<'_>,
+        hit_id: UuidScalar,
+        approve: bool,
+    ) -> Result<AutomodHit> {
+        let admin_id = require_admin(context).await?;
+        let hit_id = Uuid::from(hit_id);
+        let hit = storage::get_automod_hit(context, hit_id)
+            .await?
+            .filter(|hit| {
+                matches!(hit.action, AutomodAction::Hold | AutomodAction::ShadowHide)
+                    && hit.reviewed_at.is_none()
+            });
+        let Some((target_type, Some(target_id))) = hit.map(|hit| (hit.target_type, hit.target_id))
+        else {
+            return Err("No pending hold found".into());
+        };
+        let pool = context.data_unchecked::<PgPool>();
+        let mut tx = pool.begin().await?;
+        let changed = if approve {
+            let shown = unhide_flagged_content(&mut tx, target_type, target_id).await?;
+            unshadow_content(&mut tx, target_type, target_id).await? || shown
+        } else {
+            remove_flagged_content(&mut tx, target_type, target_id, admin_id).await?
+        };
+        query!(
+            r#"
+            UPDATE Automod_Hit SET reviewed_by = $3, reviewed_at = NOW()
+             WHERE target_type = $1 AND target_id = $2 AND action IN ('Hold', 'ShadowHide')
+             AND reviewed_at IS NULL"#,
+            target_type as ReportTarget,
+            target_id,
+            admin_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        if changed {
+            refresh_flagged_scores(pool, target_type, target_id).await?;
+        }
+        storage::get_automod_hit(context, hit_id)
+            .await?
+            .ok_or_else(|| "Automod hit not found".into())
+    }
+
+    async fn appeal_rejection(
+        &self,
+        context: &Context<'_>,
+        deer_id: UuidScalar,
+        #[graphql(validator(chars_min_length = 1, chars_max_length = 5000))] justification: String,
+    ) -> Result<DeerAppeal> {
+        let user_id = current_user_id(context).await?;
+        let Some(deer) = get_deer(context, deer_id.into()).await? else {
+            return Err("Deer not found".into());
+        };
+        if deer.created_by != user_id {
+            return Err("Only the submitter can appeal a rejection".into());
+        }
+        if deer.status != DeerEntryStatus::Rejected {
+            return Err("Only rejected deer can be appealed".into());
+        }
+        let justification = justification.trim();
+        if justification.is_empty() {
+            return Err("A justification is required to appeal".into());
+        }
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        // Appeals by one user are filed one at a time, so the count below stays true
+        query!("SELECT id FROM Users WHERE id = $1 FOR UPDATE", user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let rejection_event_id = query_scalar!(
+            r#"
+            SELECT id FROM Deer_Moderation_Event
+             WHERE cervidae_id = $1 AND to_status = 'Rejected'
+             ORDER BY created_at DESC LIMIT 1"#,
+            deer.id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(rejection_event_id) = rejection_event_id else {
+            return Err("No rejection was recorded for this deer".into());
+        };
+        let recent = query_scalar!(
+            r#"
+            SELECT COUNT(*) FROM Deer_Appeal
+             WHERE appellant_id = $1 AND created_at > NOW() - make_interval(days => $2)"#,
+            user_id,
+            APPEAL_WINDOW_DAYS as i32
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if recent.unwrap_or(0) >= MAX_APPEALS_PER_WINDOW {
+            return Err(format!(
+                "You can file at most {} appeals every {} days",
+                MAX_APPEALS_PER_WINDOW, APPEAL_WINDOW_DAYS
+            )
+            .into());
+        }
+        let appeal = query_as(
+            r#"
+            INSERT INTO Deer_Appeal (id, cervidae_id, appellant_id, rejection_event_id, round, justification)
+            VALUES ($1, $2, $3, $4, moderation_round($2), $5)
+             ON CONFLICT (rejection_event_id) DO NOTHING
+             RETURNING *"#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(deer.id)
+        .bind(user_id)
+        .bind(rejection_event_id)
+        .bind(justification)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(appeal) = appeal else {
+            return Err("This rejection has already been appealed".into());
+        };
+        tx.commit().await?;
+        Ok(appeal)
+    }
+
+    async fn resolve_appeal(
+        &self,
+        context: &Context<'_>,
+        appeal_id: UuidScalar,
+        overturn: bool,
+        #[graphql(validator(chars_max_length = 2000))] resolution: String,
+    ) -> Result<DeerAppeal> {
+        let appeal_id = Uuid::from(appeal_id);
+        let admin_id = require_admin(context).await?;
+        let resolution = resolution.trim();
+        if resolution.is_empty() {
+            return Err("A resolution is required to close an appeal".into());
+        }
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let appeal = query!(
+            r#"
+            SELECT cervidae_id, status AS "status: AppealStatus",
+                took_part_in_rejection(id, $2) AS "rejector!"
+             FROM Deer_Appeal WHERE id = $1 FOR UPDATE"#,
+            appeal_id,
+            admin_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(appeal) = appeal else {
+            return Err("Appeal not found".into());
+        };
+        if appeal.status != AppealStatus::Open {
+            return Err("Appeal has already been resolved".into());
+        }
+        if appeal.rejector {
+            return Err(
+                "An appeal must be handled by a moderator who did not reject the deer".into(),
+            );
+        }
+        let status = if overturn {
+            check_transition(
+                DeerEntryStatus::Rejected,
+                DeerEntryStatus::Approved,
+                &[AppealReviewer],
+            )?;
+            let change = StatusChange {
+                to: DeerEntryStatus::Approved,
+                from: DeerEntryStatus::Rejected,
+                actor_id: admin_id,
+                reason: Some(resolution),
+                note: None,
+            };
+            if change_deer_status(&mut *tx, appeal.cervidae_id, &change)
+                .await?
+                .is_none()
+            {
+                return Err("Deer is no longer rejected".into());
+            }
+            AppealStatus::Overturned
+        } else {
+            AppealStatus::Upheld
+        };
+        let appeal = query_as(
+            r#"
+            UPDATE Deer_Appeal SET status = $2, handled_by = $3, resolution = $4, resolved_at = NOW()
+             WHERE id = $1 RETURNING *"#,
+        )
+        .bind(appeal_id)
+        .bind(status)
+        .bind(admin_id)
+        .bind(resolution)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(appeal)
+    }
+
+    async fn reset_user_password(
+        &self,
+        context: &Context<'_>,
+        input: ResetPasswordInput,
+    ) -> Result<String> {
+        let user_id = Uuid::from(input.id);
+        let user = query_as!(User, "SELECT * FROM Users WHERE id = $1", user_id)
+            .fetch_one(context.data_unchecked::<PgPool>())
+            .await?;
+        let password_match = verify(input.current_password, &user.password).unwrap();
+        if password_match {
+            let hashed = hash(input.new_password, 10)?;
+            let _ = query("UPDATE Users SET password = $1 WHERE id = $2")
+                .bind(hashed)
+                .bind(user_id)
+                .execute(context.data_unchecked::<PgPool>())
+                .await?;
+            Ok("Password reset successfully".to_string())
+        } else {
+            Err("Current password is incorrect".into())
+        }
+    }
+
+    async fn unsuspend_user(&self, context: &Context<'_>, id: UuidScalar) -> Result<User> {
+        require_admin(context).await?;
+        let user = query_as!(
+            User,
+            "UPDATE Users SET suspended_at = NULL, suspended_by = NULL WHERE id = $1 RETURNING *",
+            Uuid::from(id)
+        )
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await?;
+        user.ok_or_else(|| "User not found".into())
+    }
+
+    async fn delete_user(
+        &self,
+        context: &Context<'_>,
+        id: UuidScalar,
+        policy: Option<AccountContentPolicy>,
+    ) -> Result<String> {
+        let admin_id = require_admin(context).await?;
+        let policy = policy.unwrap_or(AccountContentPolicy::Keep);
+        delete_account(context, id.into(), admin_id, policy).await?;
+        Ok("User deleted successfully".to_string())
+    }
+
+    async fn delete_account(
+        &self,
+        context: &Context<'_>,
+        #[graphql(secret)] password: String,
+        policy: AccountContentPolicy,
+    ) -> Result<String> {
+        let user_id = current_user_id(context).await?;
+        let user = query_as!(User, "SELECT * FROM Users WHERE id = $1", user_id)
+            .fetch_one(context.data_unchecked::<PgPool>())
+            .await?;
+        if !verify(password, &user.password).unwrap_or(false) {
+            return Err("Password is incorrect".into());
+        }
+        delete_account(context, user_id, user_id, policy).await?;
+        expire_session_cookie(context)?;
+        Ok("Account deleted successfully".to_string())
+    }
+
+    async fn create_deer(&self, context: &Context<'_>, input: CreateDeerInput) -> Result<Deer> {
+        let deer_id = uuid::Uuid::new_v4();
+        let user_id = current_user_id(context).await?;
+        let name = normalize_name(&input.name)?;
+        let status = if input.draft.unwrap_or(false) {
+            DeerEntryStatus::Draft
+        } else {
+            DeerEntryStatus::Pending
+        };
+        let deer: Deer = query_as(
+            r#"
+            INSERT INTO Cervidae (id, name, description, image_url, kill_count, created_by, updated_by, status, taxon_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING *"#,
+        )
+        .bind(deer_id)
+        .bind(name)
+        .bind(&input.description)
+        .bind(input.image_url)
+        .bind(input.kill_count)
+        .bind(user_id)
+        .bind(user_id)
+        .bind(status)
+        .bind(input.taxon_id.map(Uuid::from))
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(taxon_error)?;
+        refresh_danger_scores(context.data_unchecked::<PgPool>(), Some(deer_id)).await?;
+        Ok(deer)
+    }
+
+    async fn update_deer(&self, context: &Context<'_>, input: UpdateDeerInput) -> Result<Deer> {
+        if input.is_empty() {
+            return Err(async_graphql::Error::new_with_source(
+                "No update fields provided",
+            ));
+        }
+        let deer_id = Uuid::from(input.id);
+        let user_id = current_user_id(context).await?;
+        let name = input.name.as_deref().map(normalize_name).transpose()?;
+        let taxon_id = input.taxon_id.map(Uuid::from);
+        if input.kill_count.is_some() {
+            let derived = match input.kill_count_derived {
+                Some(derived) => derived,
+                None => get_deer(context, deer_id)
+                    .await?
+                    .is_some_and(|deer| deer.kill_count_derived),
+            };
+            if derived {
+                return Err("The kill count is derived from incidents and cannot be edited".into());
+            }
+        }
+        let mut query = QueryBuilder::new(
+            "UPDATE Cervidae SET updated_at = NOW(), version = version + 1, updated_by = ",
+        );
+        query.push_bind(user_id);
+        if let Some(name) = &name {
+            add_to_query(&mut query, "name", name);
+        }
+        if let Some(description) = &input.description {
+            add_to_query(&mut query, "description", description);
+        }
+        if let Some(kill_count) = &input.kill_count {
+            add_to_query(&mut query, "kill_count", kill_count);
+        }
+        if let Some(image_url) = &input.image_url {
+            add_to_query(&mut query, "image_url", image_url);
+        }
+        if let Some(taxon_id) = &taxon_id {
+            add_to_query(&mut query, "taxon_id", taxon_id);
+        }
+        if let Some(kill_count_derived) = &input.kill_count_derived {
+            add_to_query(&mut query, "kill_count_derived", kill_count_derived);
+        }
+        query.push(" WHERE id = ");
+        query.push_bind(deer_id);
+        query.push(" AND deleted_at IS NULL AND version = ");
+        query.push_bind(input.expected_version);
+        query.push(" RETURNING *;");
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let deer: Option<Deer> = query
+            .build_query_as()
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(taxon_error)?;
+        let Some(mut deer) = deer else {
+            return Err(stale_write(
+                get_deer(context, deer_id).await?,
+                "Deer not found",
+            ));
+        };
+        if input.kill_count_derived == Some(true) {
+            deer = sync_kill_count(&mut tx, deer_id, user_id)
+                .await?
+                .unwrap_or(deer);
+        }
+        tx.commit().await?;
+        Ok(deer)
+    }
+
+    async fn revert_deer(&self, context: &Context<'_>, revision_id: UuidScalar) -> Result<Deer> {
+        let admin_id = require_admin(context).await?;
+        let Some(revision) = get_revision(context, revision_id.into()).await? else {
+            return Err("Revision not found".into());
+        };
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let deer: Option<Deer> = query_as(
+            r#"
+            UPDATE Cervidae SET updated_at = NOW(), version = version + 1, updated_by = $2,
+                name = $3, description = $4, image_url = $5,
+                kill_count = CASE WHEN kill_count_derived THEN kill_count ELSE $6 END
+             WHERE id = $1 AND deleted_at IS NULL RETURNING *"#,
+        )
+        .bind(revision.cervidae_id)
+        .bind(admin_id)
+        .bind(&revision.name)
+        .bind(&revision.description)
+        .bind(&revision.image_url)
+        .bind(revision.kill_count)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(deer) = deer else {
+            return Err("Deer not found".into());
+        };
+        // Tags the revision the trigger just wrote, if the content actually changed
+        query!(
+            "UPDATE Cervidae_Revision SET reverted_from = $3 WHERE cervidae_id = $1 AND revision = $2",
+            deer.id,
+            deer.version,
+            revision.id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(deer)
+    }
+
+    async fn delete_deer(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        let id: Uuid = id.into();
+        let Some(deer) = get_deer(context, id).await? else {
+            return Err("Deer not found".into());
+        };
+        let user_id = require_owner_or_admin(context, deer.created_by).await?;
+        let result = query(
+            r#"
+            UPDATE Cervidae SET deleted_at = NOW(), deleted_by = $2, version = version + 1
+             WHERE id = $1 AND deleted_at IS NULL"#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(context.data_unchecked::<PgPool>())
+        .await?;
+        match result.rows_affected() {
+            0 => Err("Deer not found".into()),
+            _ => Ok("Deer deleted successfully".to_string()),
+        }
+    }
+
+    async fn restore_deer(&self, context: &Context<'_>, id: UuidScalar) -> Result<Deer> {
+        require_admin(context).await?;
+        let id: Uuid = id.into();
+        let deer: Option<Deer> = query_as(
+            r#"
+            UPDATE Cervidae SET deleted_at = NULL, deleted_by = NULL, version = version + 1
+             WHERE id = $1 AND deleted_at IS NOT NULL RETURNING *"#,
+        )
+        .bind(id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await?;
+        let Some(deer) = deer else {
+            return Err("Deleted deer not found".into());
+        };
+        refresh_danger_scores(context.data_unchecked::<PgPool>(), Some(id)).await?;
+        Ok(deer)
+    }
+
+    async fn create_taxon(&self, context: &Context<'_>, input: CreateTaxonInput) -> Result<Taxon> {
+        require_admin(context).await?;
+        if (input.rank == TaxonRank::Family) != input.parent_id.is_none() {
+            return Err("Families, and only families, have no parent".into());
+        }
+        let taxon = query_as(
+            r#"
+            INSERT INTO Taxon (id, parent_id, rank, scientific_name, common_name)
+             VALUES ($1, $2, $3, $4, $5) RETURNING *"#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(input.parent_id.map(Uuid::from))
+        .bind(input.rank)
+        .bind(input.scientific_name.trim())
+        .bind(input.common_name.as_deref().map(str::trim))
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(taxon_error)?;
+        Ok(taxon)
+    }
+
+    async fn update_taxon(&self, context: &Context<'_>, input: UpdateTaxonInput) -> Result<Taxon> {
+        require_admin(context).await?;
+        if input.is_empty() {
+            return Err("No update fields provided".into());
+        }
+        let id = Uuid::from(input.id);
+        let Some(taxon) = storage::get_taxon(context, id).await? else {
+            return Err("Taxon not found".into());
+        };
+        if taxon.rank == TaxonRank::Family && input.parent_id.is_some() {
+            return Err("A family cannot have a parent".into());
+        }
+        let parent_id = input.parent_id.map(Uuid::from);
+        let scientific_name = input.scientific_name.as_deref().map(str::trim);
+        let common_name = input.common_name.as_deref().map(str::trim);
+        let mut query = QueryBuilder::new("UPDATE Taxon SET ");
+        let mut set = query.separated(", ");
+        if let Some(parent_id) = &parent_id {
+            add_to_set(&mut set, "parent_id", parent_id);
+        }
+        if let Some(scientific_name) = &scientific_name {
+            add_to_set(&mut set, "scientific_name", scientific_name);
+        }
+        if let Some(common_name) = &common_name {
+            add_to_set(&mut set, "common_name", common_name);
+        }
+        query.push(" WHERE id = ");
+        query.push_bind(id);
+        query.push(" RETURNING *");
+        let taxon: Option<Taxon> = query
+            .build_query_as()
+            .fetch_optional(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(taxon_error)?;
+        taxon.ok_or_else(|| "Taxon not found".into())
+    }
+
+    // Entries in a deleted taxon are left unclassified
+    async fn delete_taxon(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        require_admin(context).await?;
+        let result = query!("DELETE FROM Taxon WHERE id = $1", Uuid::from(id))
+            .execute(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+                    async_graphql::Error::new("Taxon still has child taxa")
+                }
+                e => e.into(),
+            })?;
+        match result.rows_affected() {
+            0 => Err("Taxon not found".into()),
+            _ => Ok("Taxon deleted successfully".to_string()),
+        }
+    }
+
+    async fn add_deer_alias(
+        &self,
+        context: &Context<'_>,
+        deer_id: UuidScalar,
+        #[graphql(validator(chars_min_length = 1, chars_max_length = 200))] alias: String,
+    ) -> Result<Deer> {
+        let deer_id = Uuid::from(deer_id);
+        let Some(deer) = get_deer(context, deer_id).await? else {
+            return Err("Deer not found".into());
+        };
+        require_owner_or_admin(context, deer.created_by).await?;
+        let alias = normalize_name(&alias)?;
+        query!(
+            "INSERT INTO Deer_Alias (id, cervidae_id, alias) VALUES ($1, $2, $3)",
+            Uuid::new_v4(),
+            deer_id,
+            alias,
+        )
+        .execute(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.is_unique_violation() => {
+                async_graphql::Error::new("Deer already has that alias")
+            }
+            e => e.into(),
+        })?;
+        Ok(deer)
+    }
+
+    async fn remove_deer_alias(
+        &self,
+        context: &Context<'_>,
+        deer_id: UuidScalar,
+        alias: String,
+    ) -> Result<Deer> {
+        let deer_id = Uuid::from(deer_id);
+        let Some(deer) = get_deer(context, deer_id).await? else {
+            return Err("Deer not found".into());
+        };
+        require_owner_or_admin(context, deer.created_by).await?;
+        let result = query!(
+            "DELETE FROM Deer_Alias WHERE cervidae_id = $1 AND lower(alias) = lower($2)",
+            deer_id,
+            alias.trim(),
+        )
+        .execute(context.data_unchecked::<PgPool>())
+        .await?;
+        match result.rows_affected() {
+            0 => Err("Alias not found".into()),
+            _ => Ok(deer),
+        }
+    }
+
+    async fn create_sighting(
+        &self,
+        context: &Context<'_>,
+        input: CreateSightingInput,
+    ) -> Result<Sighting> {
+        let user_id = current_user_id(context).await?;
+        check_coordinates(input.latitude, input.longitude)?;
+        check_photo_url(input.photo_url.as_deref())?;
+        if input
+            .accuracy_meters
+            .is_some_and(|accuracy| accuracy <= 0.0)
+        {
+            return Err("Invalid arguments: accuracyMeters must be positive".into());
+        }
+        let observed_at = NaiveDateTime::from(input.observed_at);
+        if observed_at > Utc::now().naive_utc() + chrono::Duration::minutes(5) {
+            return Err("A sighting cannot be observed in the future".into());
+        }
+        let deer = get_deer(context, input.cervidae_id.into())
+            .await?
+            .filter(|deer| deer.status == DeerEntryStatus::Approved);
+        let Some(deer) = deer else {
+            return Err("Deer not found".into());
+        };
+        let sighting = query_as(
+            r#"
+            INSERT INTO Sighting (id, cervidae_id, reporter_id, latitude, longitude, accuracy_meters, observed_at, notes, photo_url)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING *"#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(deer.id)
+        .bind(user_id)
+        .bind(input.latitude)
+        .bind(input.longitude)
+        .bind(input.accuracy_meters)
+        .bind(observed_at)
+        .bind(input.notes.as_deref().map(str::trim).filter(|notes| !notes.is_empty()))
+        .bind(input.photo_url)
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await?;
+        Ok(sighting)
+    }
+
+    async fn verify_sighting(&self, context: &Context<'_>, id: UuidScalar) -> Result<Sighting> {
+        let admin_id = require_admin(context).await?;
+        let sighting = query_as(
+            r#"
+            UPDATE Sighting SET status = 'Verified', verified_by = $2, verified_at = NOW()
+             WHERE id = $1 RETURNING *"#,
+        )
+        .bind(Uuid::from(id))
+        .bind(admin_id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await?;
+        sighting.ok_or_else(|| "Sighting not found".into())
+    }
+
+    // A verified sighting keeps its status; the dispute is still recorded for moderators
+    async fn dispute_sighting(
+        &self,
+        context: &Context<'_>,
+        id: UuidScalar,
+        #[graphql(validator(chars_min_length = 1, chars_max_length = 2000))] reason: String,
+    ) -> Result<Sighting> {
+        let user_id = current_user_id(context).await?;
+        let id = Uuid::from(id);
+        let Some(existing) = storage::get_sighting(context, id).await? else {
+            return Err("Sighting not found".into());
+        };
+        if existing.reporter_id == Some(user_id) {
+            return Err("You cannot dispute your own sighting".into());
+        }
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err("A reason is required to dispute a sighting".into());
+        }
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let inserted = query!(
+            r#"
+            INSERT INTO Sighting_Dispute (sighting_id, user_id, reason) VALUES ($1, $2, $3)
+             ON CONFLICT (sighting_id, user_id) DO NOTHING"#,
+            id,
+            user_id,
+            reason,
+        )
+        .execute(&mut *tx)
+        .await?;
+        if inserted.rows_affected() == 0 {
+            return Err("You have already disputed this sighting".into());
+        }
+        let disputed: Option<Sighting> = query_as(
+            r#"
+            UPDATE Sighting SET status = 'Disputed'
+             WHERE id = $1 AND status = 'Unverified' RETURNING *"#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(disputed.unwrap_or(existing))
+    }
+
+    async fn create_review(
+        &self,
+        context: &Context<'_>,
+        input: CreateReviewInput,
+    ) -> Result<Review> {
+        let user_id = current_user_id(context).await?;
+        let cervidae_id: Uuid = input.cervidae_id.into();
+        if get_deer(context, cervidae_id).await?.is_none() {
+            return Err("Deer not found".into());
+        }
+        // Reviewing again after a deletion revives the deleted row in place
+        let pool = context.data_unchecked::<PgPool>();
+        let mut tx = pool.begin().await?;
+        let review = query_as!(
+            Review,
+            r#"
+            INSERT INTO review (user_id, cervidae_id, danger_level, title, body)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (user_id, cervidae_id) WHERE user_id <> ghost_user_id() DO UPDATE SET
+                danger_level = EXCLUDED.danger_level,
+                title = EXCLUDED.title,
+                body = EXCLUDED.body,
+                created_at = NOW(),
+                updated_at = NOW(),
+                version = review.version + 1,
+                deleted_at = NULL,
+                deleted_by = NULL,
+                shadow_hidden = FALSE
+             WHERE review.deleted_at IS NOT NULL
+             RETURNING *"#,
+            user_id,
+            cervidae_id,
+            &input.danger_level,
+            &input.title,
+            &input.body,
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(review) = review else {
+            return Err("Review already exists".into());
+        };
+        let text = format!("{}\n{}", review.title, review.body);
+        let subject = AutomodSubject {
+            target_type: ReportTarget::Review,
+            target_id: review.id,
+            user_id,
+            text: &text,
+            is_new: true,
+        };
+        commit_moderated_write(pool, tx, &subject).await?;
+        refresh_danger_scores(pool, Some(cervidae_id)).await?;
+        Ok(review)
+    }
+
+    async fn update_review(
+        &self,
+        context: &Context<'_>,
+        input: UpdateReviewInput,
+    ) -> Result<Review> {
+        if input.is_empty() {
+            return Err(async_graphql::Error::new_with_source(
+                "No update fields provided",
+            ));
+        }
+        let user_id: Uuid = input.user_id.into();
+        let cervidae_id: Uuid = input.cervidae_id.into();
+        require_owner_or_admin(context, user_id).await?;
+        let mut query =
+            QueryBuilder::new("UPDATE review SET updated_at = NOW(), version = version + 1");
+        if let Some(danger_level) = &input.danger_level {
+            add_to_query(&mut query, "danger_level", danger_level);
+        }
+        if let Some(title) = &input.title {
+            add_to_query(&mut query, "title", title);
+        }
+        if let Some(body) = &input.body {
+            add_to_query(&mut query, "body", body);
+        }
+        query.push(" WHERE user_id = ");
+        query.push_bind(user_id);
+        query.push(" AND cervidae_id = ");
+        query.push_bind(cervidae_id);
+        query.push(" AND deleted_at IS NULL AND version = ");
+        query.push_bind(input.expected_version);
+        query.push(" RETURNING *;");
+
+        let pool = context.data_unchecked::<PgPool>();
+        let mut tx = pool.begin().await?;
+        let review: Option<Review> = query.build_query_as().fetch_optional(&mut *tx).await?;
+        let Some(review) = review else {
+            let current = get_review(context, user_id, cervidae_id).await?;
+            return Err(stale_write(current, "Review not found"));
+        };
+        if input.title.is_some() || input.body.is_some() {
+            let text = format!("{}\n{}", review.title, review.body);
+            let subject = AutomodSubject {
+                target_type: ReportTarget::Review,
+                target_id: review.id,
+                user_id: review.user_id,
+                text: &text,
+                is_new: false,
+            };
+            // A held edit hides the review, which moves the score before the error goes out
+            let moderated = commit_moderated_write(pool, tx, &subject).await;
+            refresh_danger_scores(pool, Some(cervidae_id)).await?;
+            moderated?;
+        } else {
+            tx.commit().await?;
+            refresh_danger_scores(pool, Some(cervidae_id)).await?;
+        }
+        Ok(review)
+    }
+
+    async fn delete_review(
+        &self,
+        context: &Context<'_>,
+        input: UpdateReviewInput,
+    ) -> Result<String> {
+        let user_id = Uuid::from(input.user_id);
+        let cervidae_id = Uuid::from(input.cervidae_id);
+        let deleted_by = require_owner_or_admin(context, user_id).await?;
+        let result = query(
+            r#"
+            UPDATE review SET deleted_at = NOW(), deleted_by = $4, version = version + 1
+             WHERE user_id = $1 AND cervidae_id = $2 AND version = $3 AND deleted_at IS NULL"#,
+        )
+        .bind(user_id)
+        .bind(cervidae_id)
+        .bind(input.expected_version)
+        .bind(deleted_by)
+        .execute(context.data_unchecked::<PgPool>())
+        .await?;
+        match result.rows_affected() {
+            0 => Err(stale_write(
+                get_review(context, user_id, cervidae_id).await?,
+                "Review not found",
+            )),
+            _ => {
+                refresh_danger_scores(context.data_unchecked::<PgPool>(), Some(cervidae_id))
+                    .await?;
+                Ok("Review deleted successfully".to_string())
+            }
+        }
+    }
+
+    async fn restore_review(
+        &self,
+        context: &Context<'_>,
+        user_id: UuidScalar,
+        cervidae_id: UuidScalar,
+    ) -> Result<Review> {
+        require_admin(context).await?;
+        let user_id: Uuid = user_id.into();
+        let cervidae_id: Uuid = cervidae_id.into();
+        let review = query_as!(
+            Review,
+            r#"
+            UPDATE review SET deleted_at = NULL, deleted_by = NULL, version = version + 1
+             WHERE user_id = $1 AND cervidae_id = $2 AND deleted_at IS NOT NULL RETURNING *"#,
+            user_id,
+            cervidae_id,
+        )
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await?;
+        let Some(review) = review else {
+            return Err("Deleted review not found".into());
+        };
+        refresh_danger_scores(context.data_unchecked::<PgPool>(), Some(cervidae_id)).await?;
+        Ok(review)
+    }
+
+    async fn create_comment(
+        &self,
+        context: &Context<'_>,
+        input: CreateCommentInput,
+    ) -> Result<Comment> {
+        let comment_id = uuid::Uuid::new_v4();
+        let user_id = current_user_id(context).await?;
+        let cervidae_id: Uuid = input.cervidae_id.into();
+        let parent_id: Option<Uuid> = input.parent_id.map(|id| id.into());
+        if get_deer(context, cervidae_id).await?.is_none() {
+            return Err("Deer not found".into());
+        }
+        if let Some(parent_id) = parent_id {
+            let parent = query!(
+                "SELECT cervidae_id FROM comment WHERE id = $1 AND deleted_at IS NULL",
+                parent_id
+            )
+            .fetch_optional(context.data_unchecked::<PgPool>())
+            .await?;
+            match parent {
+                None => return Err("Parent comment not found".into()),
+                Some(parent) if parent.cervidae_id != cervidae_id => {
+                    return Err("Parent comment belongs to a different deer".into())
+                }
+                Some(_) => {}
+            }
+        }
+        let pool = context.data_unchecked::<PgPool>();
+        let mut tx = pool.begin().await?;
+        let comment = query_as!(
+            Comment,
+            r#"
+            INSERT INTO comment (id, user_id, cervidae_id, parent_id, content)
+             VALUES ($1, $2, $3, $4, $5) RETURNING *"#,
+            comment_id,
+            user_id,
+            cervidae_id,
+            parent_id,
+            &input.content,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        let subject = AutomodSubject {
+            target_type: ReportTarget::Comment,
+            target_id: comment.id,
+            user_id,
+            text: &comment.content,
+            is_new: true,
+        };
+        commit_moderated_write(pool, tx, &subject).await?;
+        Ok(comment)
+    }
+
+    async fn update_comment(
+        &self,
+        context: &Context<'_>,
+        input: UpdateCommentInput,
+    ) -> Result<Comment> {
+        if input.is_empty() {
+            return Err(async_graphql::Error::new_with_source(
+                "No update fields provided",
+            ));
+        }
+        let comment_id = Uuid::from(input.id);
+        let existing = get_comment(context, comment_id)
+            .await?
+            .filter(|comment| comment.deleted_at.is_none());
+        let Some(existing) = existing else {
+            return Err("Comment not found".into());
+        };
+        require_owner_or_admin(context, existing.user_id).await?;
+        let mut query =
+            QueryBuilder::new("UPDATE comment SET updated_at = NOW(), version = version + 1");
+        if let Some(content) = &input.content {
+            add_to_query(&mut query, "content", content);
+        }
+        query.push(" WHERE id = ");
+        query.push_bind(comment_id);
+        query.push(" AND deleted_at IS NULL AND version = ");
+        query.push_bind(input.expected_version);
+        query.push(" RETURNING *;");
+
+        let pool = context.data_unchecked::<PgPool>();
+        let mut tx = pool.begin().await?;
+        let comment: Option<Comment> = query.build_query_as().fetch_optional(&mut *tx).await?;
+        let Some(comment) = comment else {
+            return Err(stale_write(
+                get_comment(context, comment_id)
+                    .await?
+                    .filter(|comment| comment.deleted_at.is_none()),
+                "Comment not found",
+            ));
+        };
+        let subject = AutomodSubject {
+            target_type: ReportTarget::Comment,
+            target_id: comment.id,
+            user_id: comment.user_id,
+            text: &comment.content,
+            is_new: false,
+        };
+        commit_moderated_write(pool, tx, &subject).await?;
+        Ok(comment)
+    }
+
+    async fn delete_comment(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        let id: Uuid = id.into();
+        let comment = get_comment(context, id)
+            .await?
+            .filter(|comment| comment.deleted_at.is_none());
+        let Some(comment) = comment else {
+            return Err("Comment not found".into());
+        };
+        let user_id = require_owner_or_admin(context, comment.user_id).await?;
+        let result = query(
+            r#"
+            UPDATE comment SET deleted_at = NOW(), deleted_by = $2, version = version + 1
+             WHERE id = $1 AND deleted_at IS NULL"#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(context.data_unchecked::<PgPool>())
+        .await?;
+        match result.rows_affected() {
+            0 => Err("Comment not found".into()),
+            _ => Ok("Comment deleted successfully".to_string()),
+        }
+    }
+
+    async fn restore_comment(&self, context: &Context<'_>, id: UuidScalar) -> Result<Comment> {
+        require_admin(context).await?;
+        let id: Uuid = id.into();
+        let comment = query_as!(
+            Comment,
+            r#"
+            UPDATE comment SET deleted_at = NULL, deleted_by = NULL, version = version + 1
+             WHERE id = $1 AND deleted_at IS NOT NULL RETURNING *"#,
+            id,
+        )
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await?;
+        comment.ok_or_else(|| "Deleted comment not found".into())
+    }
+
+    async fn create_crime(&self, context: &Context<'_>, input: CreateCrimeInput) -> Result<Crime> {
+        let crime_id = uuid::Uuid::new_v4();
+        let name = normalize_name(&input.name)?;
+        let crime = query_as!(
+            Crime,
+            r#"
+            INSERT INTO crime (id, name, description, severity, category_id)
+             VALUES ($1, $2, $3, $4, COALESCE($5, (SELECT id FROM Crime_Category WHERE slug = 'other')))
+             RETURNING *"#,
+            crime_id,
+            name,
+            input.description,
+            input.severity.unwrap_or(5),
+            input.category_id.map(Uuid::from)
+        )
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| match e {
+            // Without an "other" category to fall back on the column is left null
+            sqlx::Error::Database(e)
+                if e.is_foreign_key_violation()
+                    || matches!(e.kind(), sqlx::error::ErrorKind::NotNullViolation) =>
+            {
+                async_graphql::Error::new("Crime category not found")
+            }
+            e => e.into(),
+        })?;
+        Ok(crime)
+    }
+
+    async fn update_crime(&self, context: &Context<'_>, input: UpdateCrimeInput) -> Result<Crime> {
+        if input.is_empty() {
+            return Err(async_graphql::Error::new_with_source(
+                "No update fields provided",
+            ));
+        }
+        let crime_id = Uuid::from(input.id);
+        let name = input.name.as_deref().map(normalize_name).transpose()?;
+        let category_id = input.category_id.map(Uuid::from);
+        let mut query =
+            QueryBuilder::new("UPDATE crime SET updated_at = NOW(), version = version + 1");
+        if let Some(name) = &name {
+            add_to_query(&mut query, "name", name);
+        }
+        if let Some(description) = &input.description {
+            add_to_query(&mut query, "description", description);
+        }
+        if let Some(severity) = &input.severity {
+            add_to_query(&mut query, "severity", severity);
+        }
+        if let Some(category_id) = &category_id {
+            add_to_query(&mut query, "category_id", category_id);
+        }
+        query.push(" WHERE id = ");
+        query.push_bind(crime_id);
+        query.push(" AND deleted_at IS NULL AND version = ");
+        query.push_bind(input.expected_version);
+        query.push(" RETURNING *;");
+
+        let crime: Option<Crime> = query
+            .build_query_as()
+            .fetch_optional(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(crime_category_error)?;
+        match crime {
+            Some(crime) => Ok(crime),
+            None => Err(stale_write(
+                get_crime(context, crime_id).await?,
+                "Crime not found",
+            )),
+        }
+    }
+
+    async fn delete_crime(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        let admin_id = require_admin(context).await?;
+        let id: Uuid = id.into();
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let result = query(
+            r#"
+            UPDATE crime SET deleted_at = NOW(), deleted_by = $2, version = version + 1
+             WHERE id = $1 AND deleted_at IS NULL"#,
+        )
+        .bind(id)
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err("Crime not found".into());
+        }
+        sync_crime_kill_counts(&mut tx, id, admin_id).await?;
+        tx.commit().await?;
+        Ok("Crime deleted successfully".to_string())
+    }
+
+    async fn restore_crime(&self, context: &Context<'_>, id: UuidScalar) -> Result<Crime> {
+        let admin_id = require_admin(context).await?;
+        let id: Uuid = id.into();
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let crime = query_as!(
+            Crime,
+            r#"
+            UPDATE crime SET deleted_at = NULL, deleted_by = NULL, version = version + 1
+             WHERE id = $1 AND deleted_at IS NOT NULL RETURNING *"#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(crime) = crime else {
+            return Err("Deleted crime not found".into());
+        };
+        sync_crime_kill_counts(&mut tx, id, admin_id).await?;
+        tx.commit().await?;
+        Ok(crime)
+    }
+
+    async fn assign_crime(
+        &self,
+        context: &Context<'_>,
+        input: CrimeCervidaeInput,
+    ) -> Result<String> {
+        let crime_id: Uuid = input.crime_id.into();
+        let cervidae_id: Uuid = input.cervidae_id.into();
+        if get_crime(context, crime_id).await?.is_none() {
+            return Err("Crime not found".into());
+        }
+        if get_deer(context, cervidae_id).await?.is_none() {
+            return Err("Deer not found".into());
+        }
+        let crime_cervidae = query_as!(
+            CrimeCervidae,
+            r#"
+            INSERT INTO crime_cervidae (crime_id, cervidae_id)
+             VALUES ($1, $2) RETURNING crime_id, cervidae_id, created_at"#,
+            crime_id,
+            cervidae_id,
+        )
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await?;
+        Ok(format!(
+            "Crime {} assigned to deer {}",
+            crime_cervidae.crime_id, crime_cervidae.cervidae_id
+        ))
+    }
+
+    async fn drop_crime(&self, context: &Context<'_>, input: CrimeCervidaeInput) -> Result<String> {
+        let crime_id: Uuid = input.crime_id.into();
+        let cervidae_id: Uuid = input.cervidae_id.into();
+        let has_incidents = query_scalar!(
+            "SELECT EXISTS (SELECT 1 FROM Incident WHERE crime_id = $1 AND cervidae_id = $2)",
+            crime_id,
+            cervidae_id,
+        )
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await?;
+        if has_incidents.unwrap_or(false) {
+            return Err("Delete the incidents linking this deer to the crime first".into());
+        }
+        let convicted = query_scalar!(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM Charge JOIN Court_Case ON Court_Case.id = Charge.case_id
+             WHERE Charge.crime_id = $1 AND Court_Case.cervidae_id = $2 AND Charge.verdict = 'Guilty')"#,
+            crime_id,
+            cervidae_id,
+        )
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await?;
+        if convicted.unwrap_or(false) {
+            return Err("The deer was convicted of this crime".into());
+        }
+        let result = query("DELETE FROM crime_cervidae WHERE crime_id = $1 AND cervidae_id = $2")
+            .bind(crime_id)
+            .bind(cervidae_id)
+            .execute(context.data_unchecked::<PgPool>())
+            .await?;
+        match result.rows_affected() {
+            0 => Err("Crime assignment not found".into()),
+            _ => Ok("Crime dropped successfully".to_string()),
+        }
     }
 
-    async fn deer_pending_connections(
+    async fn create_crime_category(
         &self,
         context: &Context<'_>,
-        after: Option<UuidScalar>,
-        before: Option<UuidScalar>,
-        first: Option<i64>,
-        last: Option<i64>,
-    ) -> Result<Vec<DeerConnection>> {
-        let after = after.map(|x| x.into());
-        let before = before.map(|x| x.into());
-        deer_page(
-            context,
-            after,
-            before,
-            first,
-            last,
-            DeerEntryStatus::Pending,
-            None,
+        input: CreateCrimeCategoryInput,
+    ) -> Result<CrimeCategoryNode> {
+        require_admin(context).await?;
+        let category = query_as!(
+            CrimeCategoryNode,
+            r#"
+            INSERT INTO Crime_Category (id, parent_id, slug, name, description)
+             VALUES ($1, $2, $3, $4, $5) RETURNING *"#,
+            Uuid::new_v4(),
+            input.parent_id.map(Uuid::from),
+            input.slug,
+            normalize_name(&input.name)?,
+            input.description,
         )
+        .fetch_one(context.data_unchecked::<PgPool>())
         .await
+        .map_err(crime_category_error)?;
+        Ok(category)
     }
-    async fn deer_rejected_connections(
+
+    async fn update_crime_category(
         &self,
         context: &Context<'_>,
-        after: Option<UuidScalar>,
-        before: Option<UuidScalar>,
-        first: Option<i64>,
-        last: Option<i64>,
-        id: Option<UuidScalar>,
-    ) -> Result<Vec<DeerConnection>> {
-        let after = after.map(|x| x.into());
-        let before = before.map(|x| x.into());
-        let id: Option<Uuid> = id.map(|x| x.into());
-        deer_page(
-            context,
-            after,
-            before,
-            first,
-            last,
-            DeerEntryStatus::Rejected,
-            id,
-        )
-        .await
+        input: UpdateCrimeCategoryInput,
+    ) -> Result<CrimeCategoryNode> {
+        require_admin(context).await?;
+        if input.is_empty() {
+            return Err("No update fields provided".into());
+        }
+        let id = Uuid::from(input.id);
+        let parent_id = input.parent_id.map(Uuid::from);
+        if parent_id == Some(id) {
+            return Err("A category cannot be its own parent".into());
+        }
+        let name = input.name.as_deref().map(normalize_name).transpose()?;
+        let mut query = QueryBuilder::new("UPDATE Crime_Category SET ");
+        let mut set = query.separated(", ");
+        if let Some(parent_id) = &parent_id {
+            add_to_set(&mut set, "parent_id", parent_id);
+        }
+        if let Some(slug) = &input.slug {
+            add_to_set(&mut set, "slug", slug);
+        }
+        if let Some(name) = &name {
+            add_to_set(&mut set, "name", name);
+        }
+        if let Some(description) = &input.description {
+            add_to_set(&mut set, "description", description);
+        }
+        query.push(" WHERE id = ");
+        query.push_bind(id);
+        query.push(" RETURNING *");
+        let category: Option<CrimeCategoryNode> = query
+            .build_query_as()
+            .fetch_optional(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(crime_category_error)?;
+        category.ok_or_else(|| "Crime category not found".into())
     }
-}
 
-pub struct MutationRoot;
+    async fn delete_crime_category(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        require_admin(context).await?;
+        let result = query!("DELETE FROM Crime_Category WHERE id = $1", Uuid::from(id))
+            .execute(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+                    async_graphql::Error::new("Category still has subcategories or crimes")
+                }
+                e => e.into(),
+            })?;
+        match result.rows_affected() {
+            0 => Err("Crime category not found".into()),
+            _ => Ok("Crime category deleted successfully".to_string()),
+        }
+    }
 
-#[Object]
-impl MutationRoot {
-    // Add your mutation resolvers here
-    async fn create_user(&self, context: &Context<'_>, input: CreateUserInput) -> Result<User> {
-        let user_id = uuid::Uuid::new_v4();
-        let hashed = hash(input.password, 10)?;
-        let user = query_as!(
-            User,
+    async fn create_statute(
+        &self,
+        context: &Context<'_>,
+        input: CreateStatuteInput,
+    ) -> Result<Statute> {
+        require_admin(context).await?;
+        let crime_id = Uuid::from(input.crime_id);
+        if get_crime(context, crime_id).await?.is_none() {
+            return Err("Crime not found".into());
+        }
+        let statute = query_as!(
+            Statute,
             r#"
-            INSERT INTO Users (id, name, email, password)
-             VALUES ($1, $2, $3, $4) RETURNING *"#,
-            user_id,
-            &input.name,
-            &input.email,
-            hashed,
+            INSERT INTO Statute (id, crime_id, jurisdiction, code, title, text, source_url)
+             VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *"#,
+            Uuid::new_v4(),
+            crime_id,
+            input.jurisdiction.trim(),
+            input.code.trim(),
+            input.title.as_deref().map(str::trim),
+            input.text,
+            input.source_url,
         )
         .fetch_one(context.data_unchecked::<PgPool>())
-        .await?;
-        Ok(user)
+        .await
+        .map_err(statute_error)?;
+        Ok(statute)
     }
 
-    async fn update_user(&self, context: &Context<'_>, input: UpdateUserInput) -> Result<User> {
+    async fn update_statute(
+        &self,
+        context: &Context<'_>,
+        input: UpdateStatuteInput,
+    ) -> Result<Statute> {
+        require_admin(context).await?;
         if input.is_empty() {
-            return Err(async_graphql::Error::new_with_source(
-                "No update fields provided",
-            ));
+            return Err("No update fields provided".into());
         }
-        let user_id = Uuid::from(input.id);
-        let mut query = sqlx::QueryBuilder::new("UPDATE Users SET updated_at = NOW()");
-        if let Some(name) = &input.name {
-            add_to_query(&mut query, "name", name);
+        let crime_id = input.crime_id.map(Uuid::from);
+        if let Some(crime_id) = crime_id {
+            if get_crime(context, crime_id).await?.is_none() {
+                return Err("Crime not found".into());
+            }
         }
-        if let Some(email) = &input.email {
-            add_to_query(&mut query, "email", email);
+        let jurisdiction = input.jurisdiction.as_deref().map(str::trim);
+        let code = input.code.as_deref().map(str::trim);
+        let title = input.title.as_deref().map(str::trim);
+        let mut query = QueryBuilder::new("UPDATE Statute SET updated_at = NOW()");
+        if let Some(crime_id) = &crime_id {
+            add_to_query(&mut query, "crime_id", crime_id);
+        }
+        if let Some(jurisdiction) = &jurisdiction {
+            add_to_query(&mut query, "jurisdiction", jurisdiction);
+        }
+        if let Some(code) = &code {
+            add_to_query(&mut query, "code", code);
+        }
+        if let Some(title) = &title {
+            add_to_query(&mut query, "title", title);
+        }
+        if let Some(text) = &input.text {
+            add_to_query(&mut query, "text", text);
+        }
+        if let Some(source_url) = &input.source_url {
+            add_to_query(&mut query, "source_url", source_url);
         }
         query.push(" WHERE id = ");
-        query.push_bind(user_id);
-        query.push(" RETURNING *;");
-        let user: User = query
+        query.push_bind(Uuid::from(input.id));
+        query.push(" RETURNING *");
+        let statute: Option<Statute> = query
             .build_query_as()
-            .fetch_one(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(user)
+            .fetch_optional(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(statute_error)?;
+        statute.ok_or_else(|| "Statute not found".into())
     }
 
-    async fn approve_deer(
-        &self,
-        context: &Context<'_>,
-        id: UuidScalar,
-        approve: bool,
-    ) -> Result<Deer> {
-        let id: Uuid = id.into();
-        let status = if approve {
-            DeerEntryStatus::Approved
-        } else {
-            DeerEntryStatus::Rejected
-        };
-        let deer = query_as("UPDATE Cervidae SET status = $1 WHERE id = $2 RETURNING *")
-            .bind(&status)
-            .bind(id)
-            .fetch_one(context.data_unchecked::<PgPool>())
+    async fn delete_statute(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        require_admin(context).await?;
+        let result = query!("DELETE FROM Statute WHERE id = $1", Uuid::from(id))
+            .execute(context.data_unchecked::<PgPool>())
             .await?;
-        Ok(deer)
+        match result.rows_affected() {
+            0 => Err("Statute not found".into()),
+            _ => Ok("Statute deleted successfully".to_string()),
+        }
     }
 
-    async fn resubmit_deer(&self, context: &Context<'_>, id: UuidScalar) -> Result<Deer> {
-        let id: Uuid = id.into();
-        let deer = query_as("UPDATE Cervidae SET status = $1 WHERE id = $2 RETURNING *")
-            .bind(DeerEntryStatus::Pending)
-            .bind(id)
-            .fetch_one(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(deer)
+    async fn open_case(&self, context: &Context<'_>, input: OpenCaseInput) -> Result<Case> {
+        let admin_id = require_admin(context).await?;
+        let cervidae_id = Uuid::from(input.cervidae_id);
+        if get_deer(context, cervidae_id).await?.is_none() {
+            return Err("Deer not found".into());
+        }
+        let jurisdiction = trim_non_blank(&input.jurisdiction, "jurisdiction")?;
+        let docket_number = input
+            .docket_number
+            .as_deref()
+            .map(|docket_number| trim_non_blank(docket_number, "docketNumber"))
+            .transpose()?;
+        let case = query_as!(
+            Case,
+            r#"
+            INSERT INTO Court_Case (id, cervidae_id, jurisdiction, docket_number, summary, opened_by)
+             VALUES ($1, $2, $3, $4, $5, $6) RETURNING *"#,
+            Uuid::new_v4(),
+            cervidae_id,
+            jurisdiction,
+            docket_number,
+            input.summary,
+            admin_id,
+        )
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(case_error)?;
+        Ok(case)
     }
 
-    async fn reset_user_password(
-        &self,
-        context: &Context<'_>,
-        input: ResetPasswordInput,
-    ) -> Result<String> {
-        let user_id = Uuid::from(input.id);
-        let user = query_as!(User, "SELECT * FROM Users WHERE id = $1", user_id)
+    async fn update_case(&self, context: &Context<'_>, input: UpdateCaseInput) -> Result<Case> {
+        require_admin(context).await?;
+        if input.is_empty() {
+            return Err("No update fields provided".into());
+        }
+        let id = Uuid::from(input.id);
+        let jurisdiction = input
+            .jurisdiction
+            .as_deref()
+            .map(|jurisdiction| trim_non_blank(jurisdiction, "jurisdiction"))
+            .transpose()?;
+        if let Some(jurisdiction) = jurisdiction {
+            let cited = query_scalar!(
+                r#"
+                SELECT EXISTS (SELECT 1 FROM Charge JOIN Statute ON Statute.id = Charge.statute_id
+                 WHERE Charge.case_id = $1 AND lower(Statute.jurisdiction) <> lower($2))"#,
+                id,
+                jurisdiction,
+            )
             .fetch_one(context.data_unchecked::<PgPool>())
             .await?;
-        let password_match = verify(input.current_password, &user.password).unwrap();
-        if password_match {
-            let hashed = hash(input.new_password, 10)?;
-            let _ = query("UPDATE Users SET password = $1 WHERE id = $2")
-                .bind(hashed)
-                .bind(user_id)
-                .execute(context.data_unchecked::<PgPool>())
-                .await?;
-            Ok("Password reset successfully".to_string())
-        } else {
-            Err("Current password is incorrect".into())
+            if cited.unwrap_or(false) {
+                return Err("Charges in the case cite statutes of another jurisdiction".into());
+            }
+        }
+        let docket_number = input
+            .docket_number
+            .as_deref()
+            .map(|docket_number| trim_non_blank(docket_number, "docketNumber"))
+            .transpose()?;
+        let mut query = QueryBuilder::new("UPDATE Court_Case SET updated_at = NOW()");
+        if let Some(jurisdiction) = &jurisdiction {
+            add_to_query(&mut query, "jurisdiction", jurisdiction);
         }
+        if let Some(docket_number) = &docket_number {
+            add_to_query(&mut query, "docket_number", docket_number);
+        }
+        if let Some(summary) = &input.summary {
+            add_to_query(&mut query, "summary", summary);
+        }
+        query.push(" WHERE id = ");
+        query.push_bind(id);
+        query.push(" RETURNING *");
+        let case: Option<Case> = query
+            .build_query_as()
+            .fetch_optional(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(case_error)?;
+        case.ok_or_else(|| "Case not found".into())
     }
 
-    async fn delete_user(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
-        let id: Uuid = id.into();
-        let result = query!("DELETE FROM Users WHERE id = $1", id)
-            .execute(context.data_unchecked::<PgPool>())
+    // Takes its charges and hearings with it, so convictions from it no longer count
+    async fn delete_case(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        require_admin(context).await?;
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let cervidae_id = query_scalar!(
+            "DELETE FROM Court_Case WHERE id = $1 RETURNING cervidae_id",
+            Uuid::from(id)
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(cervidae_id) = cervidae_id else {
+            return Err("Case not found".into());
+        };
+        // The charges go by cascade, which the verdict trigger never sees
+        query!("SELECT drop_unbacked_conviction_links($1)", cervidae_id)
+            .execute(&mut *tx)
             .await?;
-
-        match result.rows_affected() {
-            0 => Err("User not found".into()),
-            _ => Ok("User deleted successfully".to_string()),
-        }
+        tx.commit().await?;
+        Ok("Case deleted successfully".to_string())
     }
 
-    async fn create_deer(&self, context: &Context<'_>, input: CreateDeerInput) -> Result<Deer> {
-        let deer_id = uuid::Uuid::new_v4();
-        let user_id: Uuid = input.user_id.into();
-        let deer: Deer = query_as(
+    async fn file_charge(&self, context: &Context<'_>, input: FileChargeInput) -> Result<Charge> {
+        require_admin(context).await?;
+        let crime_id = Uuid::from(input.crime_id);
+        let Some(case) = storage::get_case(context, input.case_id.into()).await? else {
+            return Err("Case not found".into());
+        };
+        if get_crime(context, crime_id).await?.is_none() {
+            return Err("Crime not found".into());
+        }
+        let incident_id = input.incident_id.map(Uuid::from);
+        if let Some(incident_id) = incident_id {
+            let Some(incident) = storage::get_incident(context, incident_id).await? else {
+                return Err("Incident not found".into());
+            };
+            if incident.cervidae_id != case.cervidae_id || incident.crime_id != crime_id {
+                return Err("The incident is not this deer's commission of the crime".into());
+            }
+        }
+        let statute_id = input.statute_id.map(Uuid::from);
+        if let Some(statute_id) = statute_id {
+            let Some(statute) = storage::get_statute(context, statute_id).await? else {
+                return Err("Statute not found".into());
+            };
+            if statute.crime_id != crime_id {
+                return Err("The statute defines a different crime".into());
+            }
+            if statute.jurisdiction.to_lowercase() != case.jurisdiction.to_lowercase() {
+                return Err("The statute is from a different jurisdiction".into());
+            }
+        }
+        let charge = query_as(
             r#"
-            INSERT INTO Cervidae (id, name, description, image_url, kill_count, created_by, updated_by)
-             VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *"#,
+            INSERT INTO Charge (id, case_id, crime_id, incident_id, statute_id)
+             VALUES ($1, $2, $3, $4, $5) RETURNING *"#,
         )
-        .bind(deer_id)
-        .bind(&input.name)
-        .bind(&input.description)
-        .bind(input.image_url)
-        .bind(input.kill_count)
-        .bind(user_id)
-        .bind(user_id)
+        .bind(Uuid::new_v4())
+        .bind(case.id)
+        .bind(crime_id)
+        .bind(incident_id)
+        .bind(statute_id)
         .fetch_one(context.data_unchecked::<PgPool>())
         .await?;
-        Ok(deer.into())
+        Ok(charge)
     }
 
-    async fn update_deer(&self, context: &Context<'_>, input: UpdateDeerInput) -> Result<Deer> {
-        if input.is_empty() {
-            return Err(async_graphql::Error::new_with_source(
-                "No update fields provided",
-            ));
-        }
-        let deer_id = Uuid::from(input.id);
-        let user_id: Uuid = input.user_id.into();
-        let mut query = QueryBuilder::new("UPDATE Cervidae SET updated_at = NOW(), updated_by = ");
-        query.push_bind(user_id);
-        if let Some(name) = &input.name {
-            add_to_query(&mut query, "name", name);
-        }
-        if let Some(description) = &input.description {
-            add_to_query(&mut query, "description", description);
+    // Decided charges stay on the record; a mistaken verdict is set back to pending first
+    async fn withdraw_charge(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        require_admin(context).await?;
+        let id = Uuid::from(id);
+        let result = query!(
+            "DELETE FROM Charge WHERE id = $1 AND verdict = 'Pending'",
+            id
+        )
+        .execute(context.data_unchecked::<PgPool>())
+        .await?;
+        if result.rows_affected() > 0 {
+            return Ok("Charge withdrawn successfully".to_string());
         }
-        if let Some(kill_count) = &input.kill_count {
-            add_to_query(&mut query, "kill_count", kill_count);
+        match storage::get_charge(context, id).await? {
+            Some(_) => Err("Only pending charges can be withdrawn".into()),
+            None => Err("Charge not found".into()),
         }
-        if let Some(image_url) = &input.image_url {
-            add_to_query(&mut query, "image_url", image_url);
+    }
+
+    /// Overturning a conviction also drops the deer's link to the crime if the
+    /// conviction made it and no incident or other conviction still backs it
+    async fn record_verdict(
+        &self,
+        context: &Context<'_>,
+        input: RecordVerdictInput,
+    ) -> Result<Charge> {
+        require_admin(context).await?;
+        let sentence = input
+            .sentence
+            .as_deref()
+            .map(|sentence| trim_non_blank(sentence, "sentence"))
+            .transpose()?;
+        if sentence.is_some() && input.verdict != Verdict::Guilty {
+            return Err("Only a guilty verdict carries a sentence".into());
         }
-        query.push(" WHERE id = ");
-        query.push_bind(deer_id);
-        query.push(" RETURNING *;");
-        let deer: Deer = query
-            .build_query_as()
-            .fetch_one(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(deer.into())
+        let charge: Option<Charge> = query_as(
+            r#"
+            UPDATE Charge SET verdict = $2, sentence = $3, updated_at = NOW(),
+                decided_at = CASE WHEN $2 = 'Pending' THEN NULL ELSE NOW() END
+             WHERE id = $1 RETURNING *"#,
+        )
+        .bind(Uuid::from(input.charge_id))
+        .bind(input.verdict)
+        .bind(sentence)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await?;
+        charge.ok_or_else(|| "Charge not found".into())
     }
 
-    async fn delete_deer(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
-        let id: Uuid = id.into();
-        let result = query("DELETE FROM Cervidae WHERE id = $1")
-            .bind(id)
+    async fn schedule_hearing(
+        &self,
+        context: &Context<'_>,
+        input: ScheduleHearingInput,
+    ) -> Result<Hearing> {
+        require_admin(context).await?;
+        let court = input
+            .court
+            .as_deref()
+            .map(|court| trim_non_blank(court, "court"))
+            .transpose()?;
+        let hearing = query_as!(
+            Hearing,
+            r#"
+            INSERT INTO Hearing (id, case_id, scheduled_at, court, notes)
+             VALUES ($1, $2, $3, $4, $5) RETURNING *"#,
+            Uuid::new_v4(),
+            Uuid::from(input.case_id),
+            NaiveDateTime::from(input.scheduled_at),
+            court,
+            input.notes,
+        )
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+                async_graphql::Error::new("Case not found")
+            }
+            e => e.into(),
+        })?;
+        Ok(hearing)
+    }
+
+    async fn cancel_hearing(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        require_admin(context).await?;
+        let result = query!("DELETE FROM Hearing WHERE id = $1", Uuid::from(id))
             .execute(context.data_unchecked::<PgPool>())
             .await?;
-        match result.rows_affected() {
-            0 => Err("Deer not found".into()),
-            _ => Ok("Deer deleted successfully".to_string()),
+        match result.rows_affected() {
+            0 => Err("Hearing not found".into()),
+            _ => Ok("Hearing cancelled successfully".to_string()),
+        }
+    }
+
+    /// Applies a tag, creating it on first use; tagging counts as an up vote for it
+    async fn tag_deer(
+        &self,
+        context: &Context<'_>,
+        deer_id: UuidScalar,
+        #[graphql(validator(chars_min_length = 1, chars_max_length = 100))] tag: String,
+    ) -> Result<DeerTag> {
+        let user_id = current_user_id(context).await?;
+        let deer = get_deer(context, deer_id.into())
+            .await?
+            .filter(|deer| deer.status == DeerEntryStatus::Approved);
+        let Some(deer) = deer else {
+            return Err("Deer not found".into());
+        };
+        let slug = tag_slug(&tag);
+        if slug.is_empty() {
+            return Err("A tag needs at least one letter or digit".into());
+        }
+        let name = normalize_name(&tag)?;
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        query!(
+            r#"
+            INSERT INTO Tag (id, slug, name, created_by)
+             SELECT $1, $2, $3, $4 WHERE resolve_tag($2) IS NULL
+             ON CONFLICT (slug) DO NOTHING"#,
+            Uuid::new_v4(),
+            slug,
+            name,
+            user_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        let tag = query_as!(Tag, "SELECT * FROM Tag WHERE id = resolve_tag($1)", slug)
+            .fetch_one(&mut *tx)
+            .await?;
+        if tag.banned {
+            return Err("That tag is not allowed".into());
         }
+        query!(
+            r#"
+            INSERT INTO Deer_Tag (cervidae_id, tag_id, tagged_by) VALUES ($1, $2, $3)
+             ON CONFLICT DO NOTHING"#,
+            deer.id,
+            tag.id,
+            user_id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        query!(
+            r#"
+            INSERT INTO Deer_Tag_Vote (user_id, cervidae_id, tag_id, value) VALUES ($1, $2, $3, 1)
+             ON CONFLICT (user_id, cervidae_id, tag_id) DO UPDATE SET
+                value = EXCLUDED.value,
+                updated_at = NOW()
+             WHERE Deer_Tag_Vote.value <> EXCLUDED.value"#,
+            user_id,
+            deer.id,
+            tag.id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        storage::get_deer_tag(context, deer.id, tag.id)
+            .await?
+            .ok_or_else(|| "Tag not found".into())
     }
 
-    async fn create_review(
+    // The tagger may take a tag back until someone else votes on it; others use voteDeerTag
+    async fn untag_deer(
         &self,
         context: &Context<'_>,
-        input: CreateReviewInput,
-    ) -> Result<Review> {
-        let user_id: Uuid = input.user_id.into();
-        let cervidae_id: Uuid = input.cervidae_id.into();
-        let review = query_as!(
-            Review,
+        deer_id: UuidScalar,
+        tag_id: UuidScalar,
+    ) -> Result<String> {
+        let deer_id = Uuid::from(deer_id);
+        let tag_id = Uuid::from(tag_id);
+        if get_deer(context, deer_id).await?.is_none() {
+            return Err("Deer not found".into());
+        }
+        let Some(deer_tag) = storage::get_deer_tag(context, deer_id, tag_id).await? else {
+            return Err("Tag not found".into());
+        };
+        let claims = current_claims(context).await?;
+        let user_id = Uuid::parse_str(&claims.sub)?;
+        if !claims.is_admin && deer_tag.tagged_by != Some(user_id) {
+            return Err("Only the tagger or an admin can do this".into());
+        }
+        let result = query!(
             r#"
-            INSERT INTO review (user_id, cervidae_id, danger_level, title, body)
-             VALUES ($1, $2, $3, $4, $5) RETURNING *"#,
-            user_id,
-            cervidae_id,
-            &input.danger_level,
-            &input.title,
-            &input.body,
+            DELETE FROM Deer_Tag WHERE cervidae_id = $1 AND tag_id = $2
+             AND ($3 OR NOT EXISTS (SELECT 1 FROM Deer_Tag_Vote
+                WHERE cervidae_id = $1 AND tag_id = $2 AND user_id <> $4))"#,
+            deer_id,
+            tag_id,
+            claims.is_admin,
+            user_id
         )
-        .fetch_one(context.data_unchecked::<PgPool>())
+        .execute(context.data_unchecked::<PgPool>())
         .await?;
-        Ok(review)
+        if result.rows_affected() == 0 {
+            return Err("Others have voted on this tag, so only an admin can remove it".into());
+        }
+        Ok("Tag removed successfully".to_string())
     }
 
-    async fn update_review(
+    async fn vote_deer_tag(
         &self,
         context: &Context<'_>,
-        input: UpdateReviewInput,
-    ) -> Result<Review> {
+        deer_id: UuidScalar,
+        tag_id: UuidScalar,
+        vote: Option<TagVote>,
+    ) -> Result<DeerTag> {
+        let user_id = current_user_id(context).await?;
+        let deer_id = Uuid::from(deer_id);
+        let tag_id = Uuid::from(tag_id);
+        if get_deer(context, deer_id).await?.is_none() {
+            return Err("Deer not found".into());
+        }
+        if storage::get_deer_tag(context, deer_id, tag_id)
+            .await?
+            .is_none()
+        {
+            return Err("Tag not found".into());
+        }
+        let pool = context.data_unchecked::<PgPool>();
+        match vote {
+            Some(vote) => {
+                query!(
+                    r#"
+                    INSERT INTO Deer_Tag_Vote (user_id, cervidae_id, tag_id, value) VALUES ($1, $2, $3, $4)
+                     ON CONFLICT (user_id, cervidae_id, tag_id) DO UPDATE SET
+                        value = EXCLUDED.value,
+                        updated_at = NOW()
+                     WHERE Deer_Tag_Vote.value <> EXCLUDED.value"#,
+                    user_id,
+                    deer_id,
+                    tag_id,
+                    vote.value()
+                )
+                .execute(pool)
+                .await
+                .map_err(tag_error)?;
+            }
+            None => {
+                query!(
+                    "DELETE FROM Deer_Tag_Vote WHERE user_id = $1 AND cervidae_id = $2 AND tag_id = $3",
+                    user_id,
+                    deer_id,
+                    tag_id
+                )
+                .execute(pool)
+                .await?;
+            }
+        }
+        storage::get_deer_tag(context, deer_id, tag_id)
+            .await?
+            .ok_or_else(|| "Tag not found".into())
+    }
+
+    async fn update_tag(&self, context: &Context<'_>, input: UpdateTagInput) -> Result<Tag> {
+        require_admin(context).await?;
         if input.is_empty() {
-            return Err(async_graphql::Error::new_with_source(
-                "No update fields provided",
-            ));
+            return Err("No update fields provided".into());
         }
-        let user_id: Uuid = input.user_id.into();
-        let cervidae_id: Uuid = input.cervidae_id.into();
-        let mut query = QueryBuilder::new("UPDATE review SET updated_at = NOW()");
-        if let Some(danger_level) = &input.danger_level {
-            add_to_query(&mut query, "danger_level", danger_level);
+        let name = input.name.as_deref().map(normalize_name).transpose()?;
+        let mut query = QueryBuilder::new("UPDATE Tag SET ");
+        let mut set = query.separated(", ");
+        if let Some(name) = &name {
+            add_to_set(&mut set, "name", name);
         }
-        if let Some(title) = &input.title {
-            add_to_query(&mut query, "title", title);
+        if let Some(description) = &input.description {
+            add_to_set(&mut set, "description", description);
         }
-        if let Some(body) = &input.body {
-            add_to_query(&mut query, "body", body);
+        if let Some(banned) = &input.banned {
+            add_to_set(&mut set, "banned", banned);
         }
-        query.push(" WHERE user_id = ");
-        query.push_bind(user_id);
-        query.push(" AND cervidae_id = ");
-        query.push_bind(cervidae_id);
-        query.push(" RETURNING *;");
-
-        let review: Review = query
+        query.push(" WHERE id = ");
+        query.push_bind(Uuid::from(input.id));
+        query.push(" RETURNING *");
+        let tag: Option<Tag> = query
             .build_query_as()
-            .fetch_one(context.data_unchecked::<PgPool>())
+            .fetch_optional(context.data_unchecked::<PgPool>())
             .await?;
-        Ok(review)
+        tag.ok_or_else(|| "Tag not found".into())
     }
 
-    async fn delete_review(
+    async fn add_tag_synonym(
         &self,
         context: &Context<'_>,
-        input: UpdateReviewInput,
-    ) -> Result<String> {
-        let user_id = Uuid::from(input.user_id);
-        let cervidae_id = Uuid::from(input.cervidae_id);
-        let result = query("DELETE FROM review WHERE user_id = $1 AND cervidae_id = $2")
-            .bind(user_id)
-            .bind(cervidae_id)
-            .execute(context.data_unchecked::<PgPool>())
-            .await?;
+        tag_id: UuidScalar,
+        #[graphql(validator(chars_min_length = 1, chars_max_length = 100))] synonym: String,
+    ) -> Result<Tag> {
+        require_admin(context).await?;
+        let tag_id = Uuid::from(tag_id);
+        let slug = tag_slug(&synonym);
+        if slug.is_empty() {
+            return Err("A tag needs at least one letter or digit".into());
+        }
+        query!(
+            "INSERT INTO Tag_Synonym (slug, tag_id) VALUES ($1, $2)",
+            slug,
+            tag_id
+        )
+        .execute(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(tag_error)?;
+        storage::get_tag(context, tag_id)
+            .await?
+            .ok_or_else(|| "Tag not found".into())
+    }
+
+    async fn remove_tag_synonym(&self, context: &Context<'_>, synonym: String) -> Result<String> {
+        require_admin(context).await?;
+        let result = query!(
+            "DELETE FROM Tag_Synonym WHERE slug = $1",
+            tag_slug(&synonym)
+        )
+        .execute(context.data_unchecked::<PgPool>())
+        .await?;
         match result.rows_affected() {
-            0 => Err("Review not found".into()),
-            _ => Ok("Review deleted successfully".to_string()),
+            0 => Err("Synonym not found".into()),
+            _ => Ok("Synonym removed successfully".to_string()),
         }
     }
 
-    async fn create_comment(
+    /// Folds one tag into another: its uses, votes and synonyms move over and its slug becomes a synonym
+    async fn merge_tags(
         &self,
         context: &Context<'_>,
-        input: CreateCommentInput,
-    ) -> Result<Comment> {
-        let comment_id = uuid::Uuid::new_v4();
-        let user_id: Uuid = input.user_id.into();
-        let cervidae_id: Uuid = input.cervidae_id.into();
-        let parent_id: Option<Uuid> = input.parent_id.map(|id| id.into());
-        let comment = query_as!(
-            Comment,
+        source_id: UuidScalar,
+        target_id: UuidScalar,
+    ) -> Result<Tag> {
+        require_admin(context).await?;
+        let source_id = Uuid::from(source_id);
+        let target_id = Uuid::from(target_id);
+        if source_id == target_id {
+            return Err("A tag cannot be merged into itself".into());
+        }
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let Some(target) = query_as!(Tag, "SELECT * FROM Tag WHERE id = $1 FOR UPDATE", target_id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Err("Tag not found".into());
+        };
+        let Some(source) = query_as!(Tag, "SELECT * FROM Tag WHERE id = $1 FOR UPDATE", source_id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Err("Tag not found".into());
+        };
+        // Where a deer carries both tags, votes on the source join those on the target
+        query!(
             r#"
-            INSERT INTO comment (id, user_id, cervidae_id, parent_id, content)
-             VALUES ($1, $2, $3, $4, $5) RETURNING *"#,
-            comment_id,
-            user_id,
-            cervidae_id,
-            parent_id,
-            &input.content,
+            INSERT INTO Deer_Tag_Vote (user_id, cervidae_id, tag_id, value, created_at, updated_at)
+             SELECT Deer_Tag_Vote.user_id, Deer_Tag_Vote.cervidae_id, $2, Deer_Tag_Vote.value,
+                Deer_Tag_Vote.created_at, Deer_Tag_Vote.updated_at
+             FROM Deer_Tag_Vote
+             JOIN Deer_Tag ON Deer_Tag.cervidae_id = Deer_Tag_Vote.cervidae_id AND Deer_Tag.tag_id = $2
+             WHERE Deer_Tag_Vote.tag_id = $1
+             ON CONFLICT DO NOTHING"#,
+            source.id,
+            target.id,
         )
-        .fetch_one(context.data_unchecked::<PgPool>())
+        .execute(&mut *tx)
         .await?;
-        Ok(comment)
+        // Elsewhere the deer's tag is repointed and its votes follow
+        query!(
+            r#"
+            UPDATE Deer_Tag SET tag_id = $2 WHERE tag_id = $1
+             AND cervidae_id NOT IN (SELECT cervidae_id FROM Deer_Tag WHERE tag_id = $2)"#,
+            source.id,
+            target.id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        query!(
+            "UPDATE Tag_Synonym SET tag_id = $2 WHERE tag_id = $1",
+            source.id,
+            target.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        query!("DELETE FROM Tag WHERE id = $1", source.id)
+            .execute(&mut *tx)
+            .await?;
+        query!(
+            "INSERT INTO Tag_Synonym (slug, tag_id) VALUES ($1, $2)",
+            source.slug,
+            target.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(target)
     }
 
-    async fn update_comment(
+    async fn create_incident(
         &self,
         context: &Context<'_>,
-        input: UpdateCommentInput,
-    ) -> Result<Comment> {
-        if input.is_empty() {
-            return Err(async_graphql::Error::new_with_source(
-                "No update fields provided",
-            ));
+        input: CreateIncidentInput,
+    ) -> Result<Incident> {
+        let user_id = current_user_id(context).await?;
+        let cervidae_id = Uuid::from(input.cervidae_id);
+        let crime_id = Uuid::from(input.crime_id);
+        let coordinates = incident_coordinates(input.latitude, input.longitude)?;
+        let occurred_at = NaiveDateTime::from(input.occurred_at);
+        if occurred_at > Utc::now().naive_utc() {
+            return Err("An incident cannot occur in the future".into());
         }
-        let comment_id = Uuid::from(input.id);
-        let mut query = QueryBuilder::new("UPDATE comment SET updated_at = NOW()");
-        if let Some(content) = &input.content {
-            add_to_query(&mut query, "content", content);
+        if get_deer(context, cervidae_id).await?.is_none() {
+            return Err("Deer not found".into());
         }
-        query.push(" WHERE id = ");
-        query.push_bind(comment_id);
-        query.push(" RETURNING *;");
-
-        let comment: Comment = query
-            .build_query_as()
-            .fetch_one(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(comment.into())
-    }
-
-    async fn delete_comment(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
-        let id: Uuid = id.into();
-        let result = query("DELETE FROM comment WHERE id = $1")
-            .bind(id)
-            .execute(context.data_unchecked::<PgPool>())
-            .await?;
-        match result.rows_affected() {
-            0 => Err("Comment not found".into()),
-            _ => Ok("Comment deleted successfully".to_string()),
+        if get_crime(context, crime_id).await?.is_none() {
+            return Err("Crime not found".into());
         }
-    }
-
-    async fn create_crime(&self, context: &Context<'_>, input: CreateCrimeInput) -> Result<Crime> {
-        let crime_id = uuid::Uuid::new_v4();
-        let crime = query_as!(
-            Crime,
+        let incident = query_as!(
+            Incident,
             r#"
-            INSERT INTO crime (id, name, description)
-             VALUES ($1, $2, $3) RETURNING *"#,
+            INSERT INTO Incident (id, cervidae_id, crime_id, occurred_at, location_name, latitude, longitude,
+                casualties, description, sources, created_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) RETURNING *"#,
+            Uuid::new_v4(),
+            cervidae_id,
             crime_id,
-            &input.name,
-            &input.description,
+            occurred_at,
+            input.location_name.as_deref().map(str::trim),
+            coordinates.map(|(latitude, _)| latitude),
+            coordinates.map(|(_, longitude)| longitude),
+            input.casualties.unwrap_or(0),
+            input.description.as_deref(),
+            &incident_sources(input.sources),
+            user_id,
         )
         .fetch_one(context.data_unchecked::<PgPool>())
         .await?;
-        Ok(crime.into())
+        Ok(incident)
     }
 
-    async fn update_crime(&self, context: &Context<'_>, input: UpdateCrimeInput) -> Result<Crime> {
+    async fn update_incident(
+        &self,
+        context: &Context<'_>,
+        input: UpdateIncidentInput,
+    ) -> Result<Incident> {
         if input.is_empty() {
-            return Err(async_graphql::Error::new_with_source(
-                "No update fields provided",
-            ));
+            return Err("No update fields provided".into());
         }
-        let crime_id = Uuid::from(input.id);
-        let mut query = QueryBuilder::new("UPDATE crime SET updated_at = NOW()");
-        if let Some(name) = &input.name {
-            add_to_query(&mut query, "name", name);
+        let id = Uuid::from(input.id);
+        let Some(incident) = storage::get_incident(context, id).await? else {
+            return Err("Incident not found".into());
+        };
+        let claims = current_claims(context).await?;
+        let user_id = Uuid::parse_str(&claims.sub)?;
+        if incident.created_by != Some(user_id) && !claims.is_admin {
+            return Err("Only the author or an admin can do this".into());
+        }
+        let coordinates = incident_coordinates(input.latitude, input.longitude)?;
+        let occurred_at = input.occurred_at.map(NaiveDateTime::from);
+        if occurred_at.is_some_and(|occurred_at| occurred_at > Utc::now().naive_utc()) {
+            return Err("An incident cannot occur in the future".into());
+        }
+        let location_name = input.location_name.as_deref().map(str::trim);
+        let sources = input.sources.map(|sources| incident_sources(Some(sources)));
+        let mut query = QueryBuilder::new("UPDATE Incident SET updated_at = NOW()");
+        if let Some(occurred_at) = &occurred_at {
+            add_to_query(&mut query, "occurred_at", occurred_at);
+        }
+        if let Some(location_name) = &location_name {
+            add_to_query(&mut query, "location_name", location_name);
+        }
+        if let Some((latitude, longitude)) = &coordinates {
+            add_to_query(&mut query, "latitude", latitude);
+            add_to_query(&mut query, "longitude", longitude);
+        }
+        if let Some(casualties) = &input.casualties {
+            add_to_query(&mut query, "casualties", casualties);
         }
         if let Some(description) = &input.description {
             add_to_query(&mut query, "description", description);
         }
+        if let Some(sources) = &sources {
+            add_to_query(&mut query, "sources", sources);
+        }
+        // Reporters editing a verified incident send it back for verification
+        if !claims.is_admin {
+            query.push(", verified_by = NULL, verified_at = NULL");
+        }
         query.push(" WHERE id = ");
-        query.push_bind(crime_id);
-        query.push(" RETURNING *;");
-
-        let crime: Crime = query
-            .build_query_as()
-            .fetch_one(context.data_unchecked::<PgPool>())
-            .await?;
-        Ok(crime.into())
+        query.push_bind(id);
+        query.push(" RETURNING *");
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let updated: Option<Incident> = query.build_query_as().fetch_optional(&mut *tx).await?;
+        let Some(updated) = updated else {
+            return Err("Incident not found".into());
+        };
+        if incident.verified_at.is_some() {
+            sync_kill_count(&mut tx, updated.cervidae_id, user_id).await?;
+        }
+        tx.commit().await?;
+        Ok(updated)
     }
 
-    async fn delete_crime(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
-        let id: Uuid = id.into();
-        let result = query("DELETE FROM crime WHERE id = $1")
-            .bind(id)
-            .execute(context.data_unchecked::<PgPool>())
+    async fn delete_incident(&self, context: &Context<'_>, id: UuidScalar) -> Result<String> {
+        let id = Uuid::from(id);
+        let Some(incident) = storage::get_incident(context, id).await? else {
+            return Err("Incident not found".into());
+        };
+        let user_id =
+            require_owner_or_admin(context, incident.created_by.unwrap_or(Uuid::nil())).await?;
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let result = query!("DELETE FROM Incident WHERE id = $1", id)
+            .execute(&mut *tx)
             .await?;
-        match result.rows_affected() {
-            0 => Err("Crime not found".into()),
-            _ => Ok("Crime deleted successfully".to_string()),
+        if result.rows_affected() == 0 {
+            return Err("Incident not found".into());
+        }
+        if incident.verified_at.is_some() {
+            sync_kill_count(&mut tx, incident.cervidae_id, user_id).await?;
         }
+        tx.commit().await?;
+        Ok("Incident deleted successfully".to_string())
     }
 
-    async fn assign_crime(
+    // Only verified incidents count towards a derived kill count
+    async fn verify_incident(
         &self,
         context: &Context<'_>,
-        input: CrimeCervidaeInput,
-    ) -> Result<String> {
-        let crime_id: Uuid = input.crime_id.into();
-        let cervidae_id: Uuid = input.cervidae_id.into();
-        let crime_cervidae = query_as!(
-            CrimeCervidae,
+        id: UuidScalar,
+        verified: bool,
+    ) -> Result<Incident> {
+        let admin_id = require_admin(context).await?;
+        let mut tx = context.data_unchecked::<PgPool>().begin().await?;
+        let incident: Option<Incident> = query_as(
             r#"
-            INSERT INTO crime_cervidae (crime_id, cervidae_id)
-             VALUES ($1, $2) RETURNING *"#,
-            crime_id,
-            cervidae_id,
+            UPDATE Incident SET
+                verified_by = CASE WHEN $2 THEN $3 END,
+                verified_at = CASE WHEN $2 THEN NOW() END
+             WHERE id = $1 RETURNING *"#,
         )
-        .fetch_one(context.data_unchecked::<PgPool>())
+        .bind(Uuid::from(id))
+        .bind(verified)
+        .bind(admin_id)
+        .fetch_optional(&mut *tx)
         .await?;
-        Ok(format!(
-            "Crime {} assigned to deer {}",
-            crime_cervidae.crime_id, crime_cervidae.cervidae_id
-        ))
-    }
-
-    async fn drop_crime(&self, context: &Context<'_>, input: CrimeCervidaeInput) -> Result<String> {
-        let crime_id: Uuid = input.crime_id.into();
-        let cervidae_id: Uuid = input.cervidae_id.into();
-        let result = query("DELETE FROM crime_cervidae WHERE crime_id = $1 AND cervidae_id = $2")
-            .bind(crime_id)
-            .bind(cervidae_id)
-            .execute(context.data_unchecked::<PgPool>())
-            .await?;
-        match result.rows_affected() {
-            0 => Err("Crime assignment not found".into()),
-            _ => Ok("Crime dropped successfully".to_string()),
-        }
+        let Some(incident) = incident else {
+            return Err("Incident not found".into());
+        };
+        sync_kill_count(&mut tx, incident.cervidae_id, admin_id).await?;
+        tx.commit().await?;
+        Ok(incident)
     }
 
     async fn login(&self, context: &Context<'_>, input: LoginInput) -> Result<String> {
-        let user = query_as!(User, "SELECT * FROM Users WHERE email = $1", input.email)
-            .fetch_one(context.data_unchecked::<PgPool>())
-            .await?;
+        let user = query_as!(
+            User,
+            "SELECT * FROM Users WHERE LOWER(email) = LOWER($1) AND NOT is_reserved_user(id)",
+            input.email.trim()
+        )
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await?;
         let password_match = verify(input.password, &user.password).unwrap();
+        if password_match && user.suspended_at.is_some() {
+            return Err("This account is suspended".into());
+        }
         if password_match {
             let _ = query("UPDATE Users SET last_login = NOW() WHERE id = $1")
                 .bind(user.id)
                 .execute(context.data_unchecked::<PgPool>())
                 .await?;
+            let session_id = Uuid::new_v4().to_string();
+            query!(
+                r#"
+                WITH expired AS (
+                    DELETE FROM User_Session WHERE user_id = $2 AND expires_at <= NOW()
+                )
+                INSERT INTO User_Session (id, user_id, expires_at)
+                 VALUES ($1, $2, NOW() + make_interval(secs => $3))"#,
+                session_id,
+                user.id,
+                SESSION_SECS as f64,
+            )
+            .execute(context.data_unchecked::<PgPool>())
+            .await?;
             let header = Header::default();
             let claims = Claims {
                 sub: user.id.to_string(),
-                exp: (Utc::now().timestamp() + 86400) as usize,
+                exp: (Utc::now().timestamp() + SESSION_SECS) as usize,
                 iat: Utc::now().timestamp() as usize,
                 iss: "National Cervidae Analystics Association".to_string(),
                 is_admin: user.is_admin,
+                jti: session_id,
             };
             let key = EncodingKey::from_secret(env::var("CLIENT_SECRET")?.as_bytes());
             let token = encode(&header, &claims, &key)?;
@@ -753,28 +4555,18 @@ impl MutationRoot {
             let cookie_value = format!("cerv_token={}; Path=/; HttpOnly;", token);
             context.append_http_header(SET_COOKIE, HeaderValue::from_str(&cookie_value)?);
 
-            return Ok(token);
+            Ok(token)
         } else {
-            return Err("Login failed".into());
+            Err("Login failed".into())
         }
     }
     async fn logout(&self, context: &Context<'_>) -> Result<String> {
-        let header = Header::default();
-        let claims = Claims {
-            sub: "".to_string(),
-            exp: (Utc::now().timestamp() - 86400) as usize,
-            iat: Utc::now().timestamp() as usize,
-            iss: "National Cervidae Analystics Association".to_string(),
-            is_admin: false,
-        };
-        let key = EncodingKey::from_secret(env::var("CLIENT_SECRET")?.as_bytes());
-        let token = encode(&header, &claims, &key)?;
-
-        // Set the cookie in the response
-        let cookie_value = format!("cerv_token={}; Path=/; HttpOnly;", token);
-        context.append_http_header(SET_COOKIE, HeaderValue::from_str(&cookie_value)?);
-
-        return Ok(token);
+        if let Ok(claims) = decode_session_cookie(context) {
+            query!("DELETE FROM User_Session WHERE id = $1", claims.jti)
+                .execute(context.data_unchecked::<PgPool>())
+                .await?;
+        }
+        expire_session_cookie(context)
     }
 
     async fn get_upload_url(
@@ -801,3 +4593,121 @@ impl MutationRoot {
         Ok(presigned_request.uri().to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_transition_allows_listed_roles() {
+        use DeerEntryStatus::*;
+        assert!(check_transition(Draft, Pending, &[Submitter]).is_ok());
+        assert!(check_transition(Pending, Approved, &[System]).is_ok());
+        assert!(check_transition(Rejected, Approved, &[AppealReviewer]).is_ok());
+        assert!(check_transition(Approved, Archived, &[Admin]).is_ok());
+        assert!(check_transition(Approved, Archived, &[Submitter, Admin]).is_ok());
+    }
+
+    #[test]
+    fn check_transition_rejects_other_roles() {
+        use DeerEntryStatus::*;
+        let err = check_transition(Pending, Approved, &[Admin]).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Only a moderation vote can move a Pending deer to Approved"
+        );
+        assert!(check_transition(Draft, Pending, &[]).is_err());
+        assert!(check_transition(Rejected, Approved, &[Submitter, Admin]).is_err());
+    }
+
+    #[test]
+    fn check_transition_rejects_unlisted_moves() {
+        use DeerEntryStatus::*;
+        let err = check_transition(Draft, Approved, &[Admin, System]).unwrap_err();
+        assert_eq!(err.message, "Cannot move a deer from Draft to Approved");
+        assert!(check_transition(Archived, Approved, &[Admin]).is_err());
+        assert!(check_transition(Pending, Pending, &[System]).is_err());
+    }
+
+    #[test]
+    fn check_photo_url_accepts_only_web_links() {
+        assert!(check_photo_url(None).is_ok());
+        assert!(check_photo_url(Some("https://example.com/deer.jpg")).is_ok());
+        assert!(check_photo_url(Some("HTTP://example.com/deer.jpg")).is_ok());
+        let err = check_photo_url(Some("javascript:alert(1)")).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Invalid arguments: photoUrl must be an http or https URL"
+        );
+        assert!(check_photo_url(Some("file:///etc/passwd")).is_err());
+    }
+
+    fn bbox(south: f64, west: f64, north: f64, east: f64) -> BoundingBox {
+        BoundingBox {
+            south,
+            west,
+            north,
+            east,
+        }
+    }
+
+    #[test]
+    fn heatmap_cell_size_halves_per_zoom_level() {
+        let world = bbox(-90.0, -180.0, 90.0, 180.0);
+        assert_eq!(heatmap_cell_size(world, 0).unwrap(), 180.0);
+        assert_eq!(heatmap_cell_size(world, 1).unwrap(), 90.0);
+        let small = bbox(45.0, 7.0, 45.01, 7.01);
+        assert_eq!(heatmap_cell_size(small, 16).unwrap(), 180.0 / 65536.0);
+    }
+
+    #[test]
+    fn heatmap_cell_size_rejects_too_many_cells() {
+        let world = bbox(-90.0, -180.0, 90.0, 180.0);
+        let err = heatmap_cell_size(world, 16).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Invalid arguments: zoom is too fine for a box this large"
+        );
+    }
+
+    #[test]
+    fn heatmap_cell_size_measures_width_across_the_antimeridian() {
+        // 20 degrees wide when wrapped, 340 when read west to east
+        assert!(heatmap_cell_size(bbox(0.0, 170.0, 1.0, -170.0), 10).is_ok());
+        assert!(heatmap_cell_size(bbox(0.0, -170.0, 1.0, 170.0), 10).is_err());
+    }
+
+    #[test]
+    fn crime_category_reads_top_level_slugs() {
+        assert_eq!(CrimeCategory::from_slug("violent"), CrimeCategory::Violent);
+        assert_eq!(
+            CrimeCategory::from_slug("property"),
+            CrimeCategory::Property
+        );
+        assert_eq!(CrimeCategory::from_slug("state"), CrimeCategory::State);
+        assert_eq!(CrimeCategory::from_slug("other"), CrimeCategory::Other);
+        assert_eq!(CrimeCategory::from_slug("wildlife"), CrimeCategory::Other);
+    }
+
+    #[test]
+    fn trim_non_blank_trims_and_refuses_whitespace() {
+        assert_eq!(trim_non_blank("  Bern ", "jurisdiction").unwrap(), "Bern");
+        let err = trim_non_blank(" \t\n", "sentence").unwrap_err();
+        assert_eq!(err.message, "Invalid arguments: sentence cannot be blank");
+    }
+
+    #[test]
+    fn tag_slug_joins_words_with_hyphens() {
+        assert_eq!(tag_slug("Night Prowler"), "night-prowler");
+        assert_eq!(tag_slug("  --Red_Nosed!!  deer-- "), "red-nosed-deer");
+        assert_eq!(tag_slug("Rudolph2"), "rudolph2");
+    }
+
+    #[test]
+    fn tag_slug_folds_accents_and_drops_other_scripts() {
+        assert_eq!(tag_slug("Élan Vital"), "elan-vital");
+        assert_eq!(tag_slug("Ｆｕｌｌ width"), "full-width");
+        assert_eq!(tag_slug("鹿"), "");
+        assert_eq!(tag_slug("  !? "), "");
+    }
+}
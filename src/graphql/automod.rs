@@ -0,0 +1,373 @@
+use crate::graphql::models::{AutomodAction, AutomodRule, AutomodRuleKind, ReportTarget};
+use regex::{Regex, RegexBuilder};
+use sqlx::{query, query_as, query_scalar, PgConnection};
+use uuid::Uuid;
+
+const MAX_REGEX_SIZE: usize = 1 << 20;
+const EXCERPT_CHARS: usize = 500;
+
+// A review or comment that was just written, inside the write's transaction
+pub struct AutomodSubject<'a> {
+    pub target_type: ReportTarget,
+    pub target_id: Uuid,
+    pub user_id: Uuid,
+    pub text: &'a str,
+    // Rate limits count new posts, not edits
+    pub is_new: bool,
+}
+
+pub struct RuleHit {
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub action: AutomodAction,
+    pub matched: String,
+}
+
+pub struct AutomodVerdict {
+    pub action: AutomodAction,
+    pub hits: Vec<RuleHit>,
+}
+
+impl AutomodVerdict {
+    // Adds a hit, keeping the most severe action. False once an Allow hit ends the run
+    fn record(&mut self, rule: AutomodRule, matched: String) -> bool {
+        if severity(rule.action) > severity(self.action) {
+            self.action = rule.action;
+        }
+        self.hits.push(RuleHit {
+            rule_id: rule.id,
+            rule_name: rule.name,
+            action: rule.action,
+            matched,
+        });
+        rule.action != AutomodAction::Allow
+    }
+}
+
+fn severity(action: AutomodAction) -> u8 {
+    match action {
+        AutomodAction::Allow => 0,
+        AutomodAction::ShadowHide => 1,
+        AutomodAction::Hold => 2,
+        AutomodAction::Reject => 3,
+    }
+}
+
+fn build_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .size_limit(MAX_REGEX_SIZE)
+        .build()
+}
+
+fn word_list_regex(words: &[String]) -> Result<Regex, regex::Error> {
+    let words: Vec<String> = words
+        .iter()
+        .map(|word| regex::escape(word.trim()))
+        .collect();
+    build_regex(&format!(r"(?i)\b(?:{})\b", words.join("|")))
+}
+
+// Checks a rule carries the settings its kind needs before it is saved
+pub fn check_rule(rule: &AutomodRule) -> Result<(), String> {
+    if !rule.applies_to_reviews && !rule.applies_to_comments {
+        return Err("A rule must apply to reviews, comments or both".to_string());
+    }
+    match rule.kind {
+        AutomodRuleKind::WordList | AutomodRuleKind::Regex
+            if rule.patterns.is_empty() || rule.patterns.iter().any(|p| p.trim().is_empty()) =>
+        {
+            return Err("Word and regex rules need at least one non-empty pattern".to_string());
+        }
+        AutomodRuleKind::WordList => {
+            word_list_regex(&rule.patterns).map_err(|e| e.to_string())?;
+        }
+        AutomodRuleKind::Regex => {
+            for pattern in &rule.patterns {
+                build_regex(pattern)
+                    .map_err(|e| format!("Invalid pattern {:?}: {}", pattern, e))?;
+            }
+        }
+        AutomodRuleKind::LinkLimit if rule.threshold.is_none() => {
+            return Err("Link limit rules need a threshold".to_string());
+        }
+        AutomodRuleKind::NewAccount if rule.threshold.unwrap_or(0) < 1 => {
+            return Err("New account rules need a threshold of at least one hour".to_string());
+        }
+        AutomodRuleKind::Duplicate if rule.window_minutes.is_none() => {
+            return Err("Duplicate rules need a window".to_string());
+        }
+        AutomodRuleKind::RateLimit
+            if rule.threshold.unwrap_or(0) < 1 || rule.window_minutes.is_none() =>
+        {
+            return Err("Rate limit rules need a threshold and a window".to_string());
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+// What a rule that only reads the text matched on
+fn match_text(rule: &AutomodRule, text: &str) -> Option<String> {
+    match rule.kind {
+        AutomodRuleKind::WordList => word_list_regex(&rule.patterns)
+            .ok()
+            .and_then(|regex| regex.find(text))
+            .map(|found| found.as_str().to_string()),
+        AutomodRuleKind::Regex => rule
+            .patterns
+            .iter()
+            .filter_map(|pattern| build_regex(pattern).ok())
+            .find_map(|regex| regex.find(text))
+            .map(|found| found.as_str().to_string()),
+        AutomodRuleKind::LinkLimit => {
+            let threshold = rule.threshold.unwrap_or(0);
+            let links = Regex::new(r"(?i)\bhttps?://|\bwww\.")
+                .map(|regex| regex.find_iter(text).count())
+                .unwrap_or(0);
+            (links > threshold as usize).then(|| format!("{} links", links))
+        }
+        _ => None,
+    }
+}
+
+// What the rule matched on, or None if the subject passes it
+async fn check_subject(
+    conn: &mut PgConnection,
+    rule: &AutomodRule,
+    subject: &AutomodSubject<'_>,
+) -> Result<Option<String>, sqlx::Error> {
+    let threshold = rule.threshold.unwrap_or(0);
+    let window_minutes = rule.window_minutes.unwrap_or(0);
+    let matched = match rule.kind {
+        AutomodRuleKind::WordList | AutomodRuleKind::Regex | AutomodRuleKind::LinkLimit => {
+            match_text(rule, subject.text)
+        }
+        AutomodRuleKind::NewAccount => {
+            let young = query_scalar!(
+                r#"
+                SELECT COALESCE(created_at > NOW() - make_interval(hours => $2), FALSE) AS "young!"
+                 FROM Users WHERE id = $1"#,
+                subject.user_id,
+                threshold
+            )
+            .fetch_optional(&mut *conn)
+            .await?;
+            young
+                .filter(|young| *young)
+                .map(|_| format!("account younger than {} hours", threshold))
+        }
+        AutomodRuleKind::Duplicate => {
+            let sql = match subject.target_type {
+                ReportTarget::Review => {
+                    "SELECT EXISTS (SELECT 1 FROM Review WHERE user_id = $1 AND id <> $2 \
+                     AND lower(btrim(title || E'\\n' || body)) = lower(btrim($3)) \
+                     AND created_at > NOW() - make_interval(mins => $4))"
+                }
+                _ => {
+                    "SELECT EXISTS (SELECT 1 FROM Comment WHERE user_id = $1 AND id <> $2 \
+                     AND lower(btrim(content)) = lower(btrim($3)) \
+                     AND created_at > NOW() - make_interval(mins => $4))"
+                }
+            };
+            let duplicate: bool = query_scalar(sql)
+                .bind(subject.user_id)
+                .bind(subject.target_id)
+                .bind(subject.text)
+                .bind(window_minutes)
+                .fetch_one(&mut *conn)
+                .await?;
+            duplicate.then(|| format!("same text posted within {} minutes", window_minutes))
+        }
+        AutomodRuleKind::RateLimit if subject.is_new => {
+            let sql = match subject.target_type {
+                ReportTarget::Review => {
+                    "SELECT COUNT(*) FROM Review WHERE user_id = $1 AND id <> $2 \
+                     AND created_at > NOW() - make_interval(mins => $3)"
+                }
+                _ => {
+                    "SELECT COUNT(*) FROM Comment WHERE user_id = $1 AND id <> $2 \
+                     AND created_at > NOW() - make_interval(mins => $3)"
+                }
+            };
+            let posts: i64 = query_scalar(sql)
+                .bind(subject.user_id)
+                .bind(subject.target_id)
+                .bind(window_minutes)
+                .fetch_one(&mut *conn)
+                .await?;
+            (posts >= threshold as i64)
+                .then(|| format!("{} posts within {} minutes", posts, window_minutes))
+        }
+        AutomodRuleKind::RateLimit => None,
+    };
+    Ok(matched)
+}
+
+// Runs the enabled rules from the highest priority down. The most severe
+// action among the hits wins; an Allow hit skips the remaining rules
+pub async fn evaluate(
+    conn: &mut PgConnection,
+    subject: &AutomodSubject<'_>,
+) -> Result<AutomodVerdict, sqlx::Error> {
+    let rules: Vec<AutomodRule> = query_as(
+        r#"
+        SELECT * FROM Automod_Rule
+         WHERE enabled AND CASE WHEN $1 = 'Review'::Report_Target THEN applies_to_reviews ELSE applies_to_comments END
+         ORDER BY priority DESC, created_at"#,
+    )
+    .bind(subject.target_type)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut verdict = AutomodVerdict {
+        action: AutomodAction::Allow,
+        hits: Vec::new(),
+    };
+    for rule in rules {
+        let Some(matched) = check_subject(conn, &rule, subject).await? else {
+            continue;
+        };
+        if !verdict.record(rule, matched) {
+            break;
+        }
+    }
+    Ok(verdict)
+}
+
+// Logs every hit; rejected writes are logged without a target since they left no row
+pub async fn record_hits(
+    conn: &mut PgConnection,
+    subject: &AutomodSubject<'_>,
+    verdict: &AutomodVerdict,
+) -> Result<(), sqlx::Error> {
+    let target_id = (verdict.action != AutomodAction::Reject).then_some(subject.target_id);
+    let excerpt: String = subject.text.chars().take(EXCERPT_CHARS).collect();
+    for hit in &verdict.hits {
+        query(
+            r#"
+            INSERT INTO Automod_Hit (id, rule_id, rule_name, action, target_type, target_id, user_id, matched, excerpt)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(hit.rule_id)
+        .bind(&hit.rule_name)
+        .bind(hit.action)
+        .bind(subject.target_type)
+        .bind(target_id)
+        .bind(subject.user_id)
+        .bind(&hit.matched)
+        .bind(&excerpt)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn rule(kind: AutomodRuleKind, action: AutomodAction, patterns: &[&str]) -> AutomodRule {
+        AutomodRule {
+            id: Uuid::new_v4(),
+            name: format!("{:?}", kind),
+            kind,
+            action,
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            threshold: None,
+            window_minutes: None,
+            applies_to_reviews: true,
+            applies_to_comments: true,
+            enabled: true,
+            priority: 0,
+            created_by: None,
+            updated_by: None,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn check_rule_requires_settings_per_kind() {
+        let hold = AutomodAction::Hold;
+        assert!(check_rule(&rule(AutomodRuleKind::WordList, hold, &["spam"])).is_ok());
+        assert!(check_rule(&rule(AutomodRuleKind::WordList, hold, &[])).is_err());
+        assert!(check_rule(&rule(AutomodRuleKind::Regex, hold, &["ok", " "])).is_err());
+        assert!(check_rule(&rule(AutomodRuleKind::Regex, hold, &["(unclosed"])).is_err());
+        assert!(check_rule(&rule(AutomodRuleKind::LinkLimit, hold, &[])).is_err());
+        assert!(check_rule(&rule(AutomodRuleKind::Duplicate, hold, &[])).is_err());
+
+        let mut new_account = rule(AutomodRuleKind::NewAccount, hold, &[]);
+        new_account.threshold = Some(0);
+        assert!(check_rule(&new_account).is_err());
+        new_account.threshold = Some(24);
+        assert!(check_rule(&new_account).is_ok());
+
+        let mut rate_limit = rule(AutomodRuleKind::RateLimit, hold, &[]);
+        rate_limit.threshold = Some(3);
+        assert!(check_rule(&rate_limit).is_err());
+        rate_limit.window_minutes = Some(10);
+        assert!(check_rule(&rate_limit).is_ok());
+    }
+
+    #[test]
+    fn check_rule_needs_a_target() {
+        let mut word_list = rule(AutomodRuleKind::WordList, AutomodAction::Hold, &["spam"]);
+        word_list.applies_to_reviews = false;
+        word_list.applies_to_comments = false;
+        assert!(check_rule(&word_list).is_err());
+    }
+
+    #[test]
+    fn word_lists_match_whole_words_case_insensitively() {
+        let word_list = rule(
+            AutomodRuleKind::WordList,
+            AutomodAction::Hold,
+            &["spam", "a.b"],
+        );
+        assert_eq!(
+            match_text(&word_list, "Buy SPAM now"),
+            Some("SPAM".to_string())
+        );
+        assert_eq!(match_text(&word_list, "spammer"), None);
+        assert_eq!(match_text(&word_list, "axb"), None);
+        assert_eq!(match_text(&word_list, "a.b"), Some("a.b".to_string()));
+    }
+
+    #[test]
+    fn link_limits_count_links_above_the_threshold() {
+        let mut link_limit = rule(AutomodRuleKind::LinkLimit, AutomodAction::Hold, &[]);
+        link_limit.threshold = Some(1);
+        assert_eq!(match_text(&link_limit, "see https://a.example"), None);
+        assert_eq!(
+            match_text(&link_limit, "https://a.example and www.b.example"),
+            Some("2 links".to_string())
+        );
+    }
+
+    #[test]
+    fn verdict_keeps_the_most_severe_action() {
+        let mut verdict = AutomodVerdict {
+            action: AutomodAction::Allow,
+            hits: Vec::new(),
+        };
+        let hold = rule(AutomodRuleKind::WordList, AutomodAction::Hold, &["x"]);
+        let shadow = rule(AutomodRuleKind::WordList, AutomodAction::ShadowHide, &["x"]);
+        assert!(verdict.record(hold, "x".to_string()));
+        assert!(verdict.record(shadow, "x".to_string()));
+        assert!(verdict.action == AutomodAction::Hold);
+        assert_eq!(verdict.hits.len(), 2);
+    }
+
+    #[test]
+    fn verdict_stops_at_an_allow_hit() {
+        let mut verdict = AutomodVerdict {
+            action: AutomodAction::Allow,
+            hits: Vec::new(),
+        };
+        let allow = rule(AutomodRuleKind::WordList, AutomodAction::Allow, &["x"]);
+        assert!(!verdict.record(allow, "x".to_string()));
+        assert!(verdict.action == AutomodAction::Allow);
+    }
+}
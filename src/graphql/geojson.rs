@@ -0,0 +1,135 @@
+use crate::graphql::models::{BoundingBox, NaiveDateTimeScalar, Sighting, SightingFilter};
+use crate::graphql::storage::{push_bbox_condition, push_sighting_filter};
+use axum::{
+    extract::Query,
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
+use tracing::error;
+use uuid::Uuid;
+
+// Larger exports should be narrowed with a smaller box or a since date
+const MAX_FEATURES: i64 = 5000;
+
+#[derive(Deserialize)]
+pub struct GeoJsonParams {
+    deer: Option<Uuid>,
+    /// west,south,east,north as in RFC 7946
+    bbox: Option<String>,
+    /// Same format as the GraphQL timestamps
+    since: Option<String>,
+}
+
+#[derive(FromRow)]
+struct SightingFeature {
+    #[sqlx(flatten)]
+    sighting: Sighting,
+    deer_name: String,
+}
+
+fn bad_request(message: &str) -> Response {
+    (StatusCode::BAD_REQUEST, message.to_string()).into_response()
+}
+
+fn parse_bbox(bbox: &str) -> Option<BoundingBox> {
+    let edges: Vec<f64> = bbox
+        .split(',')
+        .map(|edge| edge.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match edges[..] {
+        [west, south, east, north] => Some(BoundingBox {
+            south,
+            west,
+            north,
+            east,
+        }),
+        _ => None,
+    }
+}
+
+fn feature(row: SightingFeature) -> Value {
+    let sighting = row.sighting;
+    json!({
+        "type": "Feature",
+        "id": sighting.id,
+        "geometry": {
+            "type": "Point",
+            "coordinates": [sighting.longitude, sighting.latitude],
+        },
+        "properties": {
+            "deerId": sighting.cervidae_id,
+            "deerName": row.deer_name,
+            "observedAt": sighting.observed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "accuracyMeters": sighting.accuracy_meters,
+            "status": sighting.status,
+            "notes": sighting.notes,
+            "photoUrl": sighting.photo_url,
+        },
+    })
+}
+
+// Serves a deer's sightings, or every sighting in a box, as a FeatureCollection
+pub async fn sightings_geojson(
+    Extension(pool): Extension<PgPool>,
+    Query(params): Query<GeoJsonParams>,
+) -> Response {
+    let bbox = match params.bbox.as_deref().map(parse_bbox) {
+        Some(None) => return bad_request("bbox must be west,south,east,north"),
+        Some(Some(bbox)) => Some(bbox),
+        None => None,
+    };
+    if params.deer.is_none() && bbox.is_none() {
+        return bad_request("Either deer or bbox is required");
+    }
+    if let Some(bbox) = bbox {
+        if let Err(e) = super::check_bbox(bbox) {
+            return bad_request(&e.message);
+        }
+    }
+    let since = match params.since.as_deref() {
+        Some(since) => match NaiveDateTime::parse_from_str(since, "%Y-%m-%d %H:%M:%S") {
+            Ok(since) => Some(since),
+            Err(_) => return bad_request("since must look like 2025-01-31 12:00:00"),
+        },
+        None => None,
+    };
+
+    let mut query_builder: QueryBuilder<'_, Postgres> = QueryBuilder::new(
+        "SELECT Sighting.*, Cervidae.name AS deer_name FROM Sighting \
+         JOIN Cervidae ON Cervidae.id = Sighting.cervidae_id WHERE TRUE",
+    );
+    if let Some(bbox) = bbox {
+        query_builder.push(" AND ");
+        push_bbox_condition(&mut query_builder, bbox);
+    }
+    let filter = SightingFilter {
+        cervidae_id: params.deer.map(Into::into),
+        observed_after: since.map(NaiveDateTimeScalar::from),
+        ..Default::default()
+    };
+    push_sighting_filter(&mut query_builder, &filter);
+    query_builder.push(" ORDER BY Sighting.observed_at DESC, Sighting.id DESC LIMIT ");
+    query_builder.push_bind(MAX_FEATURES);
+    let rows: Vec<SightingFeature> = match query_builder.build_query_as().fetch_all(&pool).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!("Failed to export sightings: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let collection = json!({
+        "type": "FeatureCollection",
+        "features": rows.into_iter().map(feature).collect::<Vec<_>>(),
+    });
+    (
+        [(CONTENT_TYPE, "application/geo+json")],
+        collection.to_string(),
+    )
+        .into_response()
+}
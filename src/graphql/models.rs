@@ -1,3 +1,4 @@
+use crate::graphql::scoring::threat_score;
 use crate::graphql::storage::*;
 use async_graphql::*;
 use chrono::NaiveDateTime;
@@ -6,7 +7,7 @@ use sqlx::FromRow;
 use uuid::Uuid;
 
 //Scalar type for foreign types from external libraries
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct UuidScalar(Uuid);
 
 impl From<Uuid> for UuidScalar {
@@ -37,7 +38,7 @@ impl ScalarType for UuidScalar {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct NaiveDateTimeScalar(NaiveDateTime);
 
 impl NaiveDateTimeScalar {
@@ -46,6 +47,12 @@ impl NaiveDateTimeScalar {
     }
 }
 
+impl From<NaiveDateTimeScalar> for NaiveDateTime {
+    fn from(scalar: NaiveDateTimeScalar) -> Self {
+        scalar.0
+    }
+}
+
 #[Scalar]
 impl ScalarType for NaiveDateTimeScalar {
     fn parse(value: Value) -> InputValueResult<Self> {
@@ -74,6 +81,8 @@ pub struct User {
     pub updated_at: Option<NaiveDateTime>,
     pub is_admin: bool,
     pub last_login: Option<NaiveDateTime>,
+    pub suspended_at: Option<NaiveDateTime>,
+    pub suspended_by: Option<Uuid>,
 }
 
 #[Object]
@@ -117,20 +126,30 @@ impl User {
     pub async fn last_login(&self) -> Option<NaiveDateTimeScalar> {
         self.last_login.map(NaiveDateTimeScalar::from)
     }
+
+    /// When a moderator suspended the account, while it stays suspended
+    pub async fn suspended_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.suspended_at.map(NaiveDateTimeScalar::from)
+    }
 }
 
 #[derive(InputObject, Debug, Serialize, Deserialize)]
 pub struct CreateUserInput {
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
     pub name: String,
+    #[graphql(validator(email, chars_max_length = 254))]
     pub email: String,
-    #[graphql(secret)]
+    // bcrypt only reads the first 72 bytes, so the upper bound counts bytes
+    #[graphql(secret, validator(chars_min_length = 8, max_length = 72))]
     pub password: String,
 }
 
 #[derive(InputObject, Debug, Serialize, Deserialize)]
 pub struct UpdateUserInput {
     pub id: UuidScalar,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
     pub name: Option<String>,
+    #[graphql(validator(email, chars_max_length = 254))]
     pub email: Option<String>,
 }
 
@@ -145,7 +164,8 @@ pub struct ResetPasswordInput {
     pub id: UuidScalar,
     #[graphql(secret)]
     pub current_password: String,
-    #[graphql(secret)]
+    // bcrypt only reads the first 72 bytes, so the upper bound counts bytes
+    #[graphql(secret, validator(chars_min_length = 8, max_length = 72))]
     pub new_password: String,
 }
 
@@ -156,57 +176,53 @@ pub struct LoginInput {
     pub password: String,
 }
 
-#[derive(sqlx::Type, Serialize, Deserialize)]
+#[derive(Enum, sqlx::Type, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
 #[sqlx(type_name = "Deer_Entry_Status")]
+#[graphql(rename_items = "PascalCase")]
 pub enum DeerEntryStatus {
+    Draft,
     Pending,
     Approved,
     Rejected,
+    Archived,
 }
 
-impl DeerEntryStatus {
-    fn clone(&self) -> Self {
-        match self {
-            DeerEntryStatus::Pending => DeerEntryStatus::Pending,
-            DeerEntryStatus::Approved => DeerEntryStatus::Approved,
-            DeerEntryStatus::Rejected => DeerEntryStatus::Rejected,
-        }
-    }
-
-    fn to_string(&self) -> String {
+impl std::fmt::Display for DeerEntryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            DeerEntryStatus::Pending => "Pending".to_string(),
-            DeerEntryStatus::Approved => "Approved".to_string(),
-            DeerEntryStatus::Rejected => "Rejected".to_string(),
+            DeerEntryStatus::Draft => write!(f, "Draft"),
+            DeerEntryStatus::Pending => write!(f, "Pending"),
+            DeerEntryStatus::Approved => write!(f, "Approved"),
+            DeerEntryStatus::Rejected => write!(f, "Rejected"),
+            DeerEntryStatus::Archived => write!(f, "Archived"),
         }
     }
+}
 
-    fn from_str(value: &str) -> Result<Self> {
-        match value {
-            "Pending" => Ok(DeerEntryStatus::Pending),
-            "Approved" => Ok(DeerEntryStatus::Approved),
-            "Rejected" => Ok(DeerEntryStatus::Rejected),
-            _ => Err(Error::new("Invalid deer entry status")),
-        }
-    }
+/// Who may move a deer between two states
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum TransitionRole {
+    Submitter,
+    Admin,
+    // The quorum rules acting on moderation votes
+    System,
+    // A moderator upholding an appeal against a rejection they took no part in
+    AppealReviewer,
 }
 
-#[Scalar]
-impl ScalarType for DeerEntryStatus {
-    fn parse(value: Value) -> InputValueResult<Self> {
-        if let Value::String(value) = &value {
-            Ok(DeerEntryStatus::from_str(value).unwrap())
-        } else {
-            Err(InputValueError::expected_type(value))
+impl std::fmt::Display for TransitionRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransitionRole::Submitter => write!(f, "the submitter"),
+            TransitionRole::Admin => write!(f, "an admin"),
+            TransitionRole::System => write!(f, "a moderation vote"),
+            TransitionRole::AppealReviewer => write!(f, "an appeal"),
         }
     }
-
-    fn to_value(&self) -> Value {
-        Value::String(self.to_string())
-    }
 }
 
 #[derive(Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
 pub struct Deer {
     pub id: Uuid,
     pub name: String,
@@ -218,6 +234,11 @@ pub struct Deer {
     pub created_by: Uuid,
     pub updated_by: Uuid,
     pub status: DeerEntryStatus,
+    pub version: i32,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub deleted_by: Option<Uuid>,
+    pub taxon_id: Option<Uuid>,
+    pub kill_count_derived: bool,
 }
 
 impl Clone for Deer {
@@ -228,11 +249,16 @@ impl Clone for Deer {
             description: self.description.clone(),
             image_url: self.image_url.clone(),
             kill_count: self.kill_count,
-            created_at: self.created_at.clone(),
-            updated_at: self.updated_at.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
             created_by: self.created_by,
             updated_by: self.updated_by,
-            status: self.status.clone(),
+            status: self.status,
+            version: self.version,
+            deleted_at: self.deleted_at,
+            deleted_by: self.deleted_by,
+            taxon_id: self.taxon_id,
+            kill_count_derived: self.kill_count_derived,
         }
     }
 }
@@ -259,6 +285,11 @@ impl Deer {
         self.kill_count
     }
 
+    /// Whether the kill count is summed from verified incidents rather than edited by hand
+    pub async fn kill_count_derived(&self) -> bool {
+        self.kill_count_derived
+    }
+
     pub async fn created_at(&self) -> Option<NaiveDateTimeScalar> {
         self.created_at.map(NaiveDateTimeScalar::from)
     }
@@ -268,12 +299,15 @@ impl Deer {
     }
 
     pub async fn status(&self) -> DeerEntryStatus {
-        self.status.clone()
+        self.status
+    }
+
+    pub async fn version(&self) -> i32 {
+        self.version
     }
 
     pub async fn created_by(&self, context: &Context<'_>) -> Result<User> {
-        let created_by = Uuid::from(self.created_by);
-        let user = get_user(context, created_by).await?;
+        let user = get_user(context, self.created_by).await?;
         if let Some(user) = user {
             Ok(user)
         } else {
@@ -282,8 +316,7 @@ impl Deer {
     }
 
     pub async fn updated_by(&self, context: &Context<'_>) -> Result<User> {
-        let updated_by = Uuid::from(self.updated_by);
-        let user = get_user(context, updated_by).await?;
+        let user = get_user(context, self.updated_by).await?;
         if let Some(user) = user {
             Ok(user)
         } else {
@@ -301,263 +334,323 @@ impl Deer {
         Ok(comments)
     }
 
+    /// Crimes the entry is accused of; see criminalRecord for what it was convicted of
     pub async fn crimes(&self, context: &Context<'_>) -> Result<Vec<Crime>> {
         let crimes = get_crimes_by_deer(context, self.id).await?;
         Ok(crimes)
     }
-}
 
-#[derive(InputObject, Debug, Serialize, Deserialize)]
-pub struct CreateDeerInput {
-    pub user_id: UuidScalar,
-    pub name: String,
-    pub description: String,
-    pub image_url: Option<String>,
-    pub kill_count: Option<i64>,
-}
+    /// Court cases against the entry, most recently opened first
+    pub async fn cases(&self, context: &Context<'_>) -> Result<Vec<Case>> {
+        get_cases_by_deer(context, self.id).await
+    }
 
-#[derive(InputObject, Debug, Serialize, Deserialize)]
-pub struct UpdateDeerInput {
-    pub user_id: UuidScalar,
-    pub id: UuidScalar,
-    pub name: Option<String>,
-    pub description: Option<String>,
-    pub image_url: Option<String>,
-    pub kill_count: Option<i64>,
-}
+    pub async fn criminal_record(&self) -> CriminalRecord {
+        CriminalRecord {
+            cervidae_id: self.id,
+        }
+    }
 
-impl UpdateDeerInput {
-    pub fn is_empty(&self) -> bool {
-        self.name.is_none()
-            && self.description.is_none()
-            && self.image_url.is_none()
-            && self.kill_count.is_none()
+    /// Combined threat from crimes, incidents, kills and reviews, with its breakdown
+    pub async fn threat_score(&self, context: &Context<'_>) -> Result<ThreatScore> {
+        let config = get_threat_config(context).await?;
+        let inputs = get_threat_inputs(context, self.id, config.frequency_window_days).await?;
+        Ok(threat_score(&config, &inputs, self.kill_count))
     }
-}
 
-#[derive(Deserialize, FromRow)]
-pub struct Review {
-    pub user_id: Uuid,
-    pub cervidae_id: Uuid,
-    pub danger_level: i32,
-    pub title: String,
-    pub body: String,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
-}
+    /// Bayesian average danger, by default weighted by each reviewer's credibility
+    pub async fn danger_score(
+        &self,
+        context: &Context<'_>,
+        weighted: Option<bool>,
+    ) -> Result<Option<f64>> {
+        let score = get_danger_score(context, self.id).await?;
+        Ok(score.map(|score| {
+            if weighted.unwrap_or(true) {
+                score.weighted_score
+            } else {
+                score.score
+            }
+        }))
+    }
 
-#[Object]
-impl Review {
-    pub async fn user(&self, context: &Context<'_>) -> Result<User> {
-        let user = get_user(context, self.user_id).await?;
-        if let Some(user) = user {
-            Ok(user)
-        } else {
-            Err(Error::new("User not found"))
+    pub async fn review_stats(&self, context: &Context<'_>) -> Result<ReviewStats> {
+        let stats = get_review_stats(context, self.id).await?;
+        Ok(stats.unwrap_or_else(|| ReviewStats::empty(self.id)))
+    }
+
+    /// Status changes, newest first
+    pub async fn moderation_history(
+        &self,
+        context: &Context<'_>,
+    ) -> Result<Vec<DeerModerationEvent>> {
+        let mut events = get_moderation_history(context, self.id).await?;
+        // One session lookup for the whole list rather than one per note
+        if super::require_admin(context).await.is_err() {
+            events.iter_mut().for_each(|event| event.note = None);
         }
+        Ok(events)
     }
 
-    pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
-        let deer = get_deer(context, self.cervidae_id).await?;
-        if let Some(deer) = deer {
-            Ok(deer.into())
-        } else {
-            Err(Error::new("Deer not found"))
+    /// Why the entry was last rejected, while it stays rejected
+    pub async fn rejection_reason(&self, context: &Context<'_>) -> Result<Option<String>> {
+        if self.status != DeerEntryStatus::Rejected {
+            return Ok(None);
         }
+        get_latest_rejection_reason(context, self.id).await
     }
 
-    pub async fn danger_level(&self) -> i32 {
-        self.danger_level
+    /// Votes in the current moderation round, while the entry is pending
+    pub async fn moderation_tally(&self, context: &Context<'_>) -> Result<Option<ModerationTally>> {
+        if self.status != DeerEntryStatus::Pending {
+            return Ok(None);
+        }
+        Ok(Some(get_vote_tally(context, self.id).await?))
     }
 
-    pub async fn title(&self) -> &str {
-        &self.title
+    /// Edits to the entry, newest first
+    pub async fn revisions(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+        after: Option<UuidScalar>,
+    ) -> Result<DeerRevisionConnection> {
+        get_revisions_page(context, self.id, first, after.map(Uuid::from)).await
     }
 
-    pub async fn body(&self) -> &str {
-        &self.body
+    pub async fn taxon(&self, context: &Context<'_>) -> Result<Option<Taxon>> {
+        match self.taxon_id {
+            Some(id) => get_taxon(context, id).await,
+            None => Ok(None),
+        }
     }
 
-    pub async fn created_at(&self) -> Option<NaiveDateTimeScalar> {
-        self.created_at.map(NaiveDateTimeScalar::from)
+    /// Common names the entry is also known by
+    pub async fn aliases(&self, context: &Context<'_>) -> Result<Vec<String>> {
+        get_deer_aliases(context, self.id).await
     }
 
-    pub async fn updated_at(&self) -> Option<NaiveDateTimeScalar> {
-        self.updated_at.map(NaiveDateTimeScalar::from)
+    /// Best-scored first; tags voted down to zero or below only on request
+    pub async fn tags(
+        &self,
+        context: &Context<'_>,
+        include_hidden: Option<bool>,
+    ) -> Result<Vec<DeerTag>> {
+        get_deer_tags(context, self.id, include_hidden.unwrap_or(false)).await
     }
-}
 
-#[derive(InputObject, Debug, Serialize, Deserialize)]
-pub struct CreateReviewInput {
-    pub user_id: UuidScalar,
-    pub cervidae_id: UuidScalar,
-    pub danger_level: i32,
-    pub title: String,
-    pub body: String,
-}
+    /// Reviews of the entry, newest first unless another sort is asked for
+    pub async fn review_connection(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+        after: Option<UuidScalar>,
+        sort: Option<ReviewSort>,
+    ) -> Result<ReviewConnection> {
+        let sort = sort.unwrap_or(ReviewSort::New);
+        get_reviews_page(context, self.id, sort, first, after.map(Uuid::from)).await
+    }
 
-#[derive(InputObject, Debug, Serialize, Deserialize)]
-pub struct UpdateReviewInput {
-    pub user_id: UuidScalar,
-    pub cervidae_id: UuidScalar,
-    pub danger_level: Option<i32>,
-    pub title: Option<String>,
-    pub body: Option<String>,
-}
+    /// Dated incidents of the entry, most recent first
+    pub async fn incidents(&self, context: &Context<'_>) -> Result<Vec<Incident>> {
+        get_incidents_by_deer(context, self.id).await
+    }
 
-impl UpdateReviewInput {
-    pub fn is_empty(&self) -> bool {
-        self.danger_level.is_none() && self.title.is_none() && self.body.is_none()
+    /// Reported sightings of the entry, most recently observed first
+    pub async fn sightings(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+        after: Option<UuidScalar>,
+    ) -> Result<SightingConnection> {
+        get_sightings_page(context, self.id, first, after.map(Uuid::from)).await
+    }
+
+    /// Appeals against rejections of the entry, newest first
+    pub async fn appeals(&self, context: &Context<'_>) -> Result<Vec<DeerAppeal>> {
+        get_appeals_by_deer(context, self.id).await
     }
 }
 
-#[derive(Deserialize, FromRow)]
-pub struct Comment {
+#[derive(Enum, sqlx::Type, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[sqlx(type_name = "Taxon_Rank")]
+pub enum TaxonRank {
+    Family,
+    Subfamily,
+    Genus,
+    Species,
+    Subspecies,
+}
+
+#[derive(FromRow)]
+pub struct Taxon {
     pub id: Uuid,
-    pub user_id: Uuid,
-    pub cervidae_id: Uuid,
     pub parent_id: Option<Uuid>,
-    pub content: String,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub rank: TaxonRank,
+    pub scientific_name: String,
+    pub common_name: Option<String>,
+    pub created_at: NaiveDateTime,
 }
 
 #[Object]
-impl Comment {
+impl Taxon {
     pub async fn id(&self) -> UuidScalar {
         UuidScalar::from(self.id)
     }
 
-    pub async fn user(&self, context: &Context<'_>) -> Result<User> {
-        let user = get_user(context, self.user_id).await?;
-        if let Some(user) = user {
-            Ok(user)
-        } else {
-            Err(Error::new("User not found"))
-        }
+    pub async fn rank(&self) -> TaxonRank {
+        self.rank
     }
 
-    pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
-        let deer = get_deer(context, self.cervidae_id).await?;
-        if let Some(deer) = deer {
-            Ok(deer)
-        } else {
-            Err(Error::new("Deer not found"))
-        }
+    pub async fn scientific_name(&self) -> &str {
+        &self.scientific_name
     }
 
-    pub async fn parent(&self, context: &Context<'_>) -> Result<Option<Comment>> {
-        if let Some(parent_id) = self.parent_id {
-            let parent = get_comment(context, parent_id).await?;
-            if let Some(parent) = parent {
-                Ok(Some(parent))
-            } else {
-                Ok(None)
-            }
-        } else {
-            Ok(None)
+    pub async fn common_name(&self) -> Option<&str> {
+        self.common_name.as_deref()
+    }
+
+    pub async fn parent(&self, context: &Context<'_>) -> Result<Option<Taxon>> {
+        match self.parent_id {
+            Some(id) => get_taxon(context, id).await,
+            None => Ok(None),
         }
     }
 
-    pub async fn content(&self) -> &str {
-        &self.content
+    pub async fn children(&self, context: &Context<'_>) -> Result<Vec<Taxon>> {
+        get_taxon_children(context, Some(self.id)).await
     }
 
-    pub async fn created_at(&self) -> Option<NaiveDateTimeScalar> {
-        self.created_at.map(NaiveDateTimeScalar::from)
+    /// Higher taxa from the family down, not including this one
+    pub async fn ancestors(&self, context: &Context<'_>) -> Result<Vec<Taxon>> {
+        get_taxon_ancestors(context, self.id).await
     }
 
-    pub async fn updated_at(&self) -> Option<NaiveDateTimeScalar> {
-        self.updated_at.map(NaiveDateTimeScalar::from)
+    /// Approved entries in this taxon or any taxon below it
+    pub async fn deer(&self, context: &Context<'_>) -> Result<Vec<Deer>> {
+        get_deer_by_taxon(context, self.id).await
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
     }
 }
 
 #[derive(InputObject, Debug, Serialize, Deserialize)]
-pub struct CreateCommentInput {
-    pub user_id: UuidScalar,
-    pub cervidae_id: UuidScalar,
+pub struct CreateTaxonInput {
     pub parent_id: Option<UuidScalar>,
-    pub content: String,
+    pub rank: TaxonRank,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub scientific_name: String,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub common_name: Option<String>,
 }
 
 #[derive(InputObject, Debug, Serialize, Deserialize)]
-pub struct UpdateCommentInput {
+pub struct UpdateTaxonInput {
     pub id: UuidScalar,
-    pub content: Option<String>,
+    pub parent_id: Option<UuidScalar>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub scientific_name: Option<String>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub common_name: Option<String>,
 }
 
-impl UpdateCommentInput {
+impl UpdateTaxonInput {
     pub fn is_empty(&self) -> bool {
-        self.content.is_none()
+        self.parent_id.is_none() && self.scientific_name.is_none() && self.common_name.is_none()
     }
 }
 
-#[derive(Deserialize, FromRow)]
-pub struct Crime {
+/// Approved entries summed over a genus and everything below it
+#[derive(SimpleObject, FromRow)]
+pub struct GenusStats {
+    #[sqlx(flatten)]
+    pub genus: Taxon,
+    pub deer_count: i64,
+    pub total_kill_count: i64,
+    pub average_danger_score: Option<f64>,
+    pub max_danger_score: Option<f64>,
+}
+
+#[derive(FromRow)]
+pub struct DeerModerationEvent {
     pub id: Uuid,
-    pub name: String,
-    pub description: Option<String>,
-    pub created_at: Option<NaiveDateTime>,
-    pub updated_at: Option<NaiveDateTime>,
+    pub cervidae_id: Uuid,
+    pub actor_id: Uuid,
+    pub from_status: DeerEntryStatus,
+    pub to_status: DeerEntryStatus,
+    pub reason: Option<String>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
 }
 
 #[Object]
-impl Crime {
+impl DeerModerationEvent {
     pub async fn id(&self) -> UuidScalar {
         UuidScalar::from(self.id)
     }
 
-    pub async fn name(&self) -> &str {
-        &self.name
+    pub async fn actor(&self, context: &Context<'_>) -> Result<User> {
+        let user = get_user(context, self.actor_id).await?;
+        if let Some(user) = user {
+            Ok(user)
+        } else {
+            Err(Error::new("User not found"))
+        }
     }
 
-    pub async fn description(&self) -> Option<&str> {
-        self.description.as_deref()
+    pub async fn from_status(&self) -> DeerEntryStatus {
+        self.from_status
     }
 
-    pub async fn created_at(&self) -> Option<NaiveDateTimeScalar> {
-        self.created_at.map(NaiveDateTimeScalar::from)
+    pub async fn to_status(&self) -> DeerEntryStatus {
+        self.to_status
     }
 
-    pub async fn updated_at(&self) -> Option<NaiveDateTimeScalar> {
-        self.updated_at.map(NaiveDateTimeScalar::from)
+    pub async fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
     }
-}
 
-#[derive(InputObject, Debug, Serialize, Deserialize)]
-pub struct CreateCrimeInput {
-    pub name: String,
-    pub description: String,
-}
+    /// Private moderator note, only visible to admins
+    pub async fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
 
-#[derive(InputObject, Debug, Serialize, Deserialize)]
-pub struct UpdateCrimeInput {
-    pub id: UuidScalar,
-    pub name: Option<String>,
-    pub description: Option<String>,
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
 }
 
-impl UpdateCrimeInput {
-    pub fn is_empty(&self) -> bool {
-        self.name.is_none() && self.description.is_none()
-    }
+#[derive(Enum, sqlx::Type, Copy, Clone, Eq, PartialEq, Debug)]
+#[sqlx(type_name = "Appeal_Status")]
+pub enum AppealStatus {
+    Open,
+    /// The rejection stands
+    Upheld,
+    /// The rejection was reversed and the deer approved
+    Overturned,
 }
 
-#[derive(Deserialize, FromRow)]
-pub struct CrimeCervidae {
-    pub crime_id: Uuid,
+#[derive(FromRow)]
+pub struct DeerAppeal {
+    pub id: Uuid,
     pub cervidae_id: Uuid,
+    pub appellant_id: Option<Uuid>,
+    pub rejection_event_id: Uuid,
+    pub round: i32,
+    pub justification: String,
+    pub status: AppealStatus,
+    pub handled_by: Option<Uuid>,
+    pub resolution: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub resolved_at: Option<NaiveDateTime>,
 }
 
 #[Object]
-impl CrimeCervidae {
-    pub async fn crime(&self, context: &Context<'_>) -> Result<Crime> {
-        let crime = get_crime(context, self.crime_id).await?;
-        if let Some(crime) = crime {
-            Ok(crime)
-        } else {
-            Err(Error::new("Crime not found"))
-        }
+impl DeerAppeal {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
     }
 
     pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
@@ -568,12 +661,2588 @@ impl CrimeCervidae {
             Err(Error::new("Deer not found"))
         }
     }
+
+    pub async fn appellant(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.appellant_id {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// The rejection being appealed
+    pub async fn rejection(&self, context: &Context<'_>) -> Result<DeerModerationEvent> {
+        let event = get_moderation_event(context, self.rejection_event_id).await?;
+        if let Some(mut event) = event {
+            if super::require_admin(context).await.is_err() {
+                event.note = None;
+            }
+            Ok(event)
+        } else {
+            Err(Error::new("Moderation event not found"))
+        }
+    }
+
+    pub async fn justification(&self) -> &str {
+        &self.justification
+    }
+
+    pub async fn status(&self) -> AppealStatus {
+        self.status
+    }
+
+    pub async fn handled_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.handled_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn resolution(&self) -> Option<&str> {
+        self.resolution.as_deref()
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+
+    pub async fn resolved_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.resolved_at.map(NaiveDateTimeScalar::from)
+    }
 }
 
-#[derive(InputObject, Debug, Serialize, Deserialize)]
-pub struct CrimeCervidaeInput {
-    pub crime_id: UuidScalar,
-    pub cervidae_id: UuidScalar,
+#[derive(Enum, sqlx::Type, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[sqlx(type_name = "Sighting_Status")]
+pub enum SightingStatus {
+    Unverified,
+    Verified,
+    Disputed,
+}
+
+#[derive(FromRow)]
+pub struct Sighting {
+    pub id: Uuid,
+    pub cervidae_id: Uuid,
+    pub reporter_id: Option<Uuid>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub accuracy_meters: Option<f64>,
+    pub observed_at: NaiveDateTime,
+    pub notes: Option<String>,
+    pub photo_url: Option<String>,
+    pub status: SightingStatus,
+    pub verified_by: Option<Uuid>,
+    pub verified_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[Object]
+impl Sighting {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
+        let deer = get_deer(context, self.cervidae_id).await?;
+        if let Some(deer) = deer {
+            Ok(deer)
+        } else {
+            Err(Error::new("Deer not found"))
+        }
+    }
+
+    pub async fn reporter(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.reporter_id {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    pub async fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Radius of the reported position's uncertainty
+    pub async fn accuracy_meters(&self) -> Option<f64> {
+        self.accuracy_meters
+    }
+
+    pub async fn observed_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.observed_at)
+    }
+
+    pub async fn notes(&self) -> Option<&str> {
+        self.notes.as_deref()
+    }
+
+    pub async fn photo_url(&self) -> Option<&str> {
+        self.photo_url.as_deref()
+    }
+
+    pub async fn status(&self) -> SightingStatus {
+        self.status
+    }
+
+    pub async fn verified_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.verified_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn verified_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.verified_at.map(NaiveDateTimeScalar::from)
+    }
+
+    pub async fn disputes(&self, context: &Context<'_>) -> Result<Vec<SightingDispute>> {
+        get_sighting_disputes(context, self.id).await
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+}
+
+#[derive(FromRow)]
+pub struct SightingDispute {
+    pub sighting_id: Uuid,
+    pub user_id: Uuid,
+    pub reason: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[Object]
+impl SightingDispute {
+    pub async fn user(&self, context: &Context<'_>) -> Result<Option<User>> {
+        get_user(context, self.user_id).await
+    }
+
+    pub async fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct SightingEdge {
+    pub node: Sighting,
+    pub cursor: String,
+}
+
+#[derive(SimpleObject)]
+pub struct SightingConnection {
+    pub edges: Vec<SightingEdge>,
+    pub page_info: PageInfo,
+}
+
+/// A sighting found by a radius search, with its distance from the centre
+#[derive(SimpleObject, FromRow)]
+pub struct NearbySighting {
+    #[sqlx(flatten)]
+    pub sighting: Sighting,
+    pub distance_km: f64,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CreateSightingInput {
+    pub cervidae_id: UuidScalar,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub accuracy_meters: Option<f64>,
+    pub observed_at: NaiveDateTimeScalar,
+    #[graphql(validator(chars_max_length = 2000))]
+    pub notes: Option<String>,
+    #[graphql(validator(url, chars_max_length = 2048))]
+    pub photo_url: Option<String>,
+}
+
+/// Narrows a geographic search to one entry or to recent sightings
+#[derive(InputObject, Default)]
+pub struct SightingFilter {
+    pub cervidae_id: Option<UuidScalar>,
+    pub status: Option<SightingStatus>,
+    pub observed_after: Option<NaiveDateTimeScalar>,
+}
+
+/// Edges in degrees; a west edge east of the east edge wraps the antimeridian
+#[derive(InputObject, Clone, Copy)]
+pub struct BoundingBox {
+    pub south: f64,
+    pub west: f64,
+    pub north: f64,
+    pub east: f64,
+}
+
+/// A grid cell holding at least one sighting
+#[derive(SimpleObject, FromRow)]
+pub struct HeatmapCell {
+    pub south: f64,
+    pub west: f64,
+    pub north: f64,
+    pub east: f64,
+    /// Mean position of the sightings in the cell
+    pub latitude: f64,
+    pub longitude: f64,
+    pub count: i64,
+}
+
+#[derive(SimpleObject)]
+pub struct SightingHeatmap {
+    pub cell_size_degrees: f64,
+    pub total_count: i64,
+    pub cells: Vec<HeatmapCell>,
+}
+
+#[derive(FromRow)]
+pub struct Incident {
+    pub id: Uuid,
+    pub cervidae_id: Uuid,
+    pub crime_id: Uuid,
+    pub occurred_at: NaiveDateTime,
+    pub location_name: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub casualties: i32,
+    pub description: Option<String>,
+    pub sources: Vec<String>,
+    pub created_by: Option<Uuid>,
+    pub verified_by: Option<Uuid>,
+    pub verified_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[Object]
+impl Incident {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
+        let deer = get_deer(context, self.cervidae_id).await?;
+        if let Some(deer) = deer {
+            Ok(deer)
+        } else {
+            Err(Error::new("Deer not found"))
+        }
+    }
+
+    pub async fn crime(&self, context: &Context<'_>) -> Result<Crime> {
+        let crime = get_crime(context, self.crime_id).await?;
+        if let Some(crime) = crime {
+            Ok(crime)
+        } else {
+            Err(Error::new("Crime not found"))
+        }
+    }
+
+    pub async fn occurred_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.occurred_at)
+    }
+
+    pub async fn location_name(&self) -> Option<&str> {
+        self.location_name.as_deref()
+    }
+
+    pub async fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+
+    pub async fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+
+    pub async fn casualties(&self) -> i32 {
+        self.casualties
+    }
+
+    pub async fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub async fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    pub async fn created_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.created_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn verified(&self) -> bool {
+        self.verified_at.is_some()
+    }
+
+    pub async fn verified_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.verified_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn verified_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.verified_at.map(NaiveDateTimeScalar::from)
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+
+    pub async fn updated_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.updated_at)
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CreateIncidentInput {
+    pub cervidae_id: UuidScalar,
+    pub crime_id: UuidScalar,
+    pub occurred_at: NaiveDateTimeScalar,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub location_name: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    #[graphql(validator(minimum = 0))]
+    pub casualties: Option<i32>,
+    #[graphql(validator(chars_max_length = 5000))]
+    pub description: Option<String>,
+    #[graphql(validator(max_items = 20, list, url, chars_max_length = 2048))]
+    pub sources: Option<Vec<String>>,
+}
+
+/// Coordinates are replaced together; editing a verified incident unverifies it unless done by an admin
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateIncidentInput {
+    pub id: UuidScalar,
+    pub occurred_at: Option<NaiveDateTimeScalar>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub location_name: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    #[graphql(validator(minimum = 0))]
+    pub casualties: Option<i32>,
+    #[graphql(validator(chars_max_length = 5000))]
+    pub description: Option<String>,
+    #[graphql(validator(max_items = 20, list, url, chars_max_length = 2048))]
+    pub sources: Option<Vec<String>>,
+}
+
+impl UpdateIncidentInput {
+    pub fn is_empty(&self) -> bool {
+        self.occurred_at.is_none()
+            && self.location_name.is_none()
+            && self.latitude.is_none()
+            && self.longitude.is_none()
+            && self.casualties.is_none()
+            && self.description.is_none()
+            && self.sources.is_none()
+    }
+}
+
+#[derive(FromRow)]
+pub struct ModerationConfig {
+    pub id: bool,
+    pub approve_quorum: i32,
+    pub voting_window_hours: i32,
+    pub min_window_votes: i32,
+    pub updated_at: NaiveDateTime,
+    pub updated_by: Option<Uuid>,
+    pub report_hide_threshold: i32,
+}
+
+#[Object]
+impl ModerationConfig {
+    /// Approve votes that approve a deer outright, provided nobody voted to reject
+    pub async fn approve_quorum(&self) -> i32 {
+        self.approve_quorum
+    }
+
+    /// Hours after which a strict majority decides instead
+    pub async fn voting_window_hours(&self) -> i32 {
+        self.voting_window_hours
+    }
+
+    /// Votes needed before a majority decision counts
+    pub async fn min_window_votes(&self) -> i32 {
+        self.min_window_votes
+    }
+
+    /// Distinct reporters after which reported content is hidden until a moderator looks at it
+    pub async fn report_hide_threshold(&self) -> i32 {
+        self.report_hide_threshold
+    }
+
+    pub async fn updated_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.updated_at)
+    }
+
+    pub async fn updated_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.updated_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateModerationConfigInput {
+    #[graphql(validator(minimum = 1))]
+    pub approve_quorum: Option<i32>,
+    #[graphql(validator(minimum = 1))]
+    pub voting_window_hours: Option<i32>,
+    #[graphql(validator(minimum = 1))]
+    pub min_window_votes: Option<i32>,
+    #[graphql(validator(minimum = 1))]
+    pub report_hide_threshold: Option<i32>,
+}
+
+#[derive(Enum, sqlx::Type, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[sqlx(type_name = "Report_Target")]
+pub enum ReportTarget {
+    Deer,
+    Review,
+    Comment,
+    User,
+}
+
+#[derive(Enum, sqlx::Type, Copy, Clone, Eq, PartialEq, Debug)]
+#[sqlx(type_name = "Report_Reason")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    Offensive,
+    Misinformation,
+    Other,
+}
+
+#[derive(Enum, sqlx::Type, Copy, Clone, Eq, PartialEq, Debug)]
+#[sqlx(type_name = "Report_Status")]
+pub enum ReportStatus {
+    Open,
+    /// Handled without removing anything
+    Resolved,
+    /// Not a problem; content hidden by the reports is shown again
+    Dismissed,
+    /// The content was removed
+    ActionTaken,
+}
+
+#[derive(FromRow)]
+pub struct ContentReport {
+    pub id: Uuid,
+    pub target_type: ReportTarget,
+    pub target_id: Uuid,
+    pub reporter_id: Option<Uuid>,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub status: ReportStatus,
+    pub handled_by: Option<Uuid>,
+    pub resolution: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub resolved_at: Option<NaiveDateTime>,
+}
+
+#[Object]
+impl ContentReport {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn target_type(&self) -> ReportTarget {
+        self.target_type
+    }
+
+    pub async fn target_id(&self) -> UuidScalar {
+        UuidScalar::from(self.target_id)
+    }
+
+    /// The reported text as it stands now, including hidden content; only visible to admins
+    pub async fn excerpt(&self, context: &Context<'_>) -> Result<Option<String>> {
+        super::require_admin(context).await?;
+        get_report_excerpt(context, self.target_type, self.target_id).await
+    }
+
+    pub async fn reporter(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.reporter_id {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn reason(&self) -> ReportReason {
+        self.reason
+    }
+
+    pub async fn details(&self) -> Option<&str> {
+        self.details.as_deref()
+    }
+
+    pub async fn status(&self) -> ReportStatus {
+        self.status
+    }
+
+    /// Open reports against the same content, this one included
+    pub async fn open_report_count(&self, context: &Context<'_>) -> Result<i64> {
+        get_open_report_count(context, self.target_type, self.target_id).await
+    }
+
+    pub async fn handled_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.handled_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn resolution(&self) -> Option<&str> {
+        self.resolution.as_deref()
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+
+    pub async fn resolved_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.resolved_at.map(NaiveDateTimeScalar::from)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ContentReportEdge {
+    pub node: ContentReport,
+    pub cursor: String,
+}
+
+#[derive(SimpleObject)]
+pub struct ContentReportConnection {
+    pub edges: Vec<ContentReportEdge>,
+    pub page_info: PageInfo,
+}
+
+#[derive(Enum, sqlx::Type, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[sqlx(type_name = "Automod_Rule_Kind")]
+pub enum AutomodRuleKind {
+    /// Any of the listed words, case-insensitively
+    WordList,
+    /// Any of the listed regular expressions
+    Regex,
+    /// More links than the threshold
+    LinkLimit,
+    /// An author whose account is younger than the threshold in hours
+    NewAccount,
+    /// The same text posted by the same author within the window
+    Duplicate,
+    /// At least threshold posts by the same author within the window
+    RateLimit,
+}
+
+#[derive(Enum, sqlx::Type, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[sqlx(type_name = "Automod_Action")]
+pub enum AutomodAction {
+    /// Let the write through and skip lower priority rules
+    Allow,
+    /// Hide the content until a moderator reviews it, telling the author
+    Hold,
+    /// Refuse the write
+    Reject,
+    /// Hide the content without telling the author
+    ShadowHide,
+}
+
+#[derive(FromRow)]
+pub struct AutomodRule {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: AutomodRuleKind,
+    pub action: AutomodAction,
+    pub patterns: Vec<String>,
+    pub threshold: Option<i32>,
+    pub window_minutes: Option<i32>,
+    pub applies_to_reviews: bool,
+    pub applies_to_comments: bool,
+    pub enabled: bool,
+    pub priority: i32,
+    pub created_by: Option<Uuid>,
+    pub updated_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[Object]
+impl AutomodRule {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn kind(&self) -> AutomodRuleKind {
+        self.kind
+    }
+
+    pub async fn action(&self) -> AutomodAction {
+        self.action
+    }
+
+    pub async fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    pub async fn threshold(&self) -> Option<i32> {
+        self.threshold
+    }
+
+    pub async fn window_minutes(&self) -> Option<i32> {
+        self.window_minutes
+    }
+
+    pub async fn applies_to_reviews(&self) -> bool {
+        self.applies_to_reviews
+    }
+
+    pub async fn applies_to_comments(&self) -> bool {
+        self.applies_to_comments
+    }
+
+    pub async fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Rules run from the highest priority down
+    pub async fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    pub async fn created_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.created_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn updated_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.updated_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+
+    pub async fn updated_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.updated_at)
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CreateAutomodRuleInput {
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub name: String,
+    pub kind: AutomodRuleKind,
+    pub action: AutomodAction,
+    #[graphql(validator(max_items = 500))]
+    pub patterns: Option<Vec<String>>,
+    #[graphql(validator(minimum = 0))]
+    pub threshold: Option<i32>,
+    #[graphql(validator(minimum = 1))]
+    pub window_minutes: Option<i32>,
+    pub applies_to_reviews: Option<bool>,
+    pub applies_to_comments: Option<bool>,
+    pub enabled: Option<bool>,
+    pub priority: Option<i32>,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateAutomodRuleInput {
+    pub id: UuidScalar,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub name: Option<String>,
+    pub action: Option<AutomodAction>,
+    #[graphql(validator(max_items = 500))]
+    pub patterns: Option<Vec<String>>,
+    #[graphql(validator(minimum = 0))]
+    pub threshold: Option<i32>,
+    #[graphql(validator(minimum = 1))]
+    pub window_minutes: Option<i32>,
+    pub applies_to_reviews: Option<bool>,
+    pub applies_to_comments: Option<bool>,
+    pub enabled: Option<bool>,
+    pub priority: Option<i32>,
+}
+
+#[derive(FromRow)]
+pub struct AutomodHit {
+    pub id: Uuid,
+    pub rule_id: Option<Uuid>,
+    pub rule_name: String,
+    pub action: AutomodAction,
+    pub target_type: ReportTarget,
+    pub target_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub matched: String,
+    pub excerpt: String,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[Object]
+impl AutomodHit {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    /// Gone once the rule is deleted; rule_name keeps what it was called
+    pub async fn rule(&self, context: &Context<'_>) -> Result<Option<AutomodRule>> {
+        match self.rule_id {
+            Some(id) => get_automod_rule(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn rule_name(&self) -> &str {
+        &self.rule_name
+    }
+
+    pub async fn action(&self) -> AutomodAction {
+        self.action
+    }
+
+    pub async fn target_type(&self) -> ReportTarget {
+        self.target_type
+    }
+
+    /// Empty when the write was rejected
+    pub async fn target_id(&self) -> Option<UuidScalar> {
+        self.target_id.map(UuidScalar::from)
+    }
+
+    pub async fn user(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.user_id {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// What the rule matched on
+    pub async fn matched(&self) -> &str {
+        &self.matched
+    }
+
+    pub async fn excerpt(&self) -> &str {
+        &self.excerpt
+    }
+
+    pub async fn reviewed_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.reviewed_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn reviewed_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.reviewed_at.map(NaiveDateTimeScalar::from)
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+}
+
+#[derive(FromRow)]
+pub struct ModerationTally {
+    #[sqlx(skip)]
+    pub cervidae_id: Uuid,
+    pub round: i32,
+    pub opened_at: NaiveDateTime,
+    pub approvals: i64,
+    pub rejections: i64,
+    pub approve_quorum: i32,
+    pub voting_window_hours: i32,
+}
+
+#[Object]
+impl ModerationTally {
+    pub async fn round(&self) -> i32 {
+        self.round
+    }
+
+    pub async fn approvals(&self) -> i64 {
+        self.approvals
+    }
+
+    pub async fn rejections(&self) -> i64 {
+        self.rejections
+    }
+
+    pub async fn approve_quorum(&self) -> i32 {
+        self.approve_quorum
+    }
+
+    /// When a majority starts to decide the round
+    pub async fn window_closes_at(&self) -> NaiveDateTimeScalar {
+        let window = chrono::Duration::hours(self.voting_window_hours as i64);
+        NaiveDateTimeScalar::from(self.opened_at + window)
+    }
+
+    /// Individual votes, only visible to admins
+    pub async fn votes(&self, context: &Context<'_>) -> Result<Vec<DeerModerationVote>> {
+        super::require_admin(context).await?;
+        get_round_votes(context, self.cervidae_id, self.round).await
+    }
+}
+
+#[derive(FromRow)]
+pub struct DeerModerationVote {
+    pub id: Uuid,
+    pub cervidae_id: Uuid,
+    pub voter_id: Option<Uuid>,
+    pub round: i32,
+    pub decision: ModerationDecision,
+    pub reason: Option<String>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[Object]
+impl DeerModerationVote {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn voter(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.voter_id {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn decision(&self) -> ModerationDecision {
+        self.decision
+    }
+
+    pub async fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub async fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+}
+
+#[derive(FromRow)]
+pub struct DeerRevision {
+    pub id: Uuid,
+    pub cervidae_id: Uuid,
+    pub revision: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub kill_count: Option<i64>,
+    pub edited_by: Uuid,
+    pub reverted_from: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+}
+
+impl DeerRevision {
+    // Field values in a comparable form, keyed by their GraphQL names
+    pub fn fields(&self) -> [(&'static str, Option<String>); 4] {
+        [
+            ("name", Some(self.name.clone())),
+            ("description", self.description.clone()),
+            ("imageUrl", self.image_url.clone()),
+            ("killCount", self.kill_count.map(|count| count.to_string())),
+        ]
+    }
+}
+
+#[Object]
+impl DeerRevision {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn revision(&self) -> i32 {
+        self.revision
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub async fn image_url(&self) -> Option<&str> {
+        self.image_url.as_deref()
+    }
+
+    pub async fn kill_count(&self) -> Option<i64> {
+        self.kill_count
+    }
+
+    pub async fn edited_by(&self, context: &Context<'_>) -> Result<User> {
+        let user = get_user(context, self.edited_by).await?;
+        if let Some(user) = user {
+            Ok(user)
+        } else {
+            Err(Error::new("User not found"))
+        }
+    }
+
+    /// The revision whose content this one restored, when it came from a revert
+    pub async fn reverted_from(&self) -> Option<UuidScalar> {
+        self.reverted_from.map(UuidScalar::from)
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct DeerRevisionEdge {
+    pub node: DeerRevision,
+    pub cursor: String,
+}
+
+#[derive(SimpleObject)]
+pub struct DeerRevisionConnection {
+    pub edges: Vec<DeerRevisionEdge>,
+    pub page_info: PageInfo,
+}
+
+#[derive(SimpleObject)]
+pub struct RevisionFieldDiff {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(FromRow)]
+pub struct ReviewStats {
+    pub cervidae_id: Uuid,
+    pub review_count: i64,
+    pub average_danger: Option<f64>,
+    pub median_danger: Option<f64>,
+    pub histogram: Vec<i32>,
+    pub last_reviewed_at: Option<NaiveDateTime>,
+}
+
+impl ReviewStats {
+    pub fn empty(cervidae_id: Uuid) -> Self {
+        ReviewStats {
+            cervidae_id,
+            review_count: 0,
+            average_danger: None,
+            median_danger: None,
+            histogram: vec![0; 10],
+            last_reviewed_at: None,
+        }
+    }
+}
+
+#[Object]
+impl ReviewStats {
+    pub async fn count(&self) -> i64 {
+        self.review_count
+    }
+
+    pub async fn average_danger(&self) -> Option<f64> {
+        self.average_danger
+    }
+
+    pub async fn median(&self) -> Option<f64> {
+        self.median_danger
+    }
+
+    /// Number of reviews per danger level, index 0 holding level 1 through index 9 holding level 10
+    pub async fn histogram(&self) -> &[i32] {
+        &self.histogram
+    }
+
+    pub async fn last_reviewed_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.last_reviewed_at.map(NaiveDateTimeScalar::from)
+    }
+}
+
+#[derive(FromRow)]
+pub struct DangerScore {
+    pub cervidae_id: Uuid,
+    pub score: f64,
+    pub weighted_score: f64,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum DeerSort {
+    Id,
+    ReviewCount,
+    AverageDanger,
+    MedianDanger,
+    DangerScore,
+    WeightedDangerScore,
+    LastReviewed,
+}
+
+#[derive(InputObject, Default)]
+pub struct DeerFilter {
+    pub min_review_count: Option<i64>,
+    pub max_review_count: Option<i64>,
+    pub min_average_danger: Option<f64>,
+    pub max_average_danger: Option<f64>,
+    /// Entries in this taxon or any taxon below it
+    pub taxon_id: Option<UuidScalar>,
+    /// Matches the name or any alias, case-insensitively
+    #[graphql(validator(chars_max_length = 200))]
+    pub name: Option<String>,
+    /// Entries linked to a crime in this category or any subcategory
+    pub crime_category_id: Option<UuidScalar>,
+    /// Entries currently described by every one of these tags; synonyms count
+    #[graphql(validator(max_items = 10, list, chars_max_length = 100))]
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CreateDeerInput {
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub name: String,
+    #[graphql(validator(chars_max_length = 10000))]
+    pub description: String,
+    #[graphql(validator(url, chars_max_length = 2048))]
+    pub image_url: Option<String>,
+    #[graphql(validator(minimum = 0))]
+    pub kill_count: Option<i64>,
+    pub taxon_id: Option<UuidScalar>,
+    /// Keep the entry as a private draft instead of submitting it for review
+    pub draft: Option<bool>,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateDeerInput {
+    pub id: UuidScalar,
+    pub expected_version: i32,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub name: Option<String>,
+    #[graphql(validator(chars_max_length = 10000))]
+    pub description: Option<String>,
+    #[graphql(validator(url, chars_max_length = 2048))]
+    pub image_url: Option<String>,
+    #[graphql(validator(minimum = 0))]
+    pub kill_count: Option<i64>,
+    pub taxon_id: Option<UuidScalar>,
+    /// Sum the kill count from verified incidents instead of taking hand edits
+    pub kill_count_derived: Option<bool>,
+}
+
+impl UpdateDeerInput {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.image_url.is_none()
+            && self.kill_count.is_none()
+            && self.taxon_id.is_none()
+            && self.kill_count_derived.is_none()
+    }
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Review {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub cervidae_id: Uuid,
+    pub danger_level: i32,
+    pub title: String,
+    pub body: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    pub version: i32,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub deleted_by: Option<Uuid>,
+    pub helpful_votes: i32,
+    pub unhelpful_votes: i32,
+    pub score: i32,
+    pub controversy: f64,
+    pub shadow_hidden: bool,
+}
+
+#[Object]
+impl Review {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn user(&self, context: &Context<'_>) -> Result<User> {
+        let user = get_user(context, self.user_id).await?;
+        if let Some(user) = user {
+            Ok(user)
+        } else {
+            Err(Error::new("User not found"))
+        }
+    }
+
+    pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
+        let deer = get_deer(context, self.cervidae_id).await?;
+        if let Some(deer) = deer {
+            Ok(deer)
+        } else {
+            Err(Error::new("Deer not found"))
+        }
+    }
+
+    pub async fn danger_level(&self) -> i32 {
+        self.danger_level
+    }
+
+    pub async fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub async fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub async fn helpful_votes(&self) -> i32 {
+        self.helpful_votes
+    }
+
+    pub async fn unhelpful_votes(&self) -> i32 {
+        self.unhelpful_votes
+    }
+
+    /// Helpful minus unhelpful votes
+    pub async fn score(&self) -> i32 {
+        self.score
+    }
+
+    /// How the signed-in user voted, if at all
+    pub async fn viewer_vote(&self, context: &Context<'_>) -> Result<Option<ReviewVote>> {
+        let Ok(user_id) = super::current_user_id(context).await else {
+            return Ok(None);
+        };
+        let value = get_review_vote(context, self.id, user_id).await?;
+        Ok(value.map(ReviewVote::from_value))
+    }
+
+    pub async fn version(&self) -> i32 {
+        self.version
+    }
+
+    pub async fn created_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.created_at.map(NaiveDateTimeScalar::from)
+    }
+
+    pub async fn updated_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.updated_at.map(NaiveDateTimeScalar::from)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ReviewEdge {
+    pub node: Review,
+    pub cursor: String,
+}
+
+#[derive(SimpleObject)]
+pub struct ReviewConnection {
+    pub edges: Vec<ReviewEdge>,
+    pub page_info: PageInfo,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ReviewSort {
+    New,
+    Old,
+    /// Highest score first
+    Top,
+    /// Most evenly split votes first
+    Controversial,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ReviewVote {
+    Helpful,
+    Unhelpful,
+}
+
+impl ReviewVote {
+    pub fn value(self) -> i16 {
+        match self {
+            ReviewVote::Helpful => 1,
+            ReviewVote::Unhelpful => -1,
+        }
+    }
+
+    pub fn from_value(value: i16) -> Self {
+        if value > 0 {
+            ReviewVote::Helpful
+        } else {
+            ReviewVote::Unhelpful
+        }
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CreateReviewInput {
+    pub cervidae_id: UuidScalar,
+    #[graphql(validator(minimum = 1, maximum = 10))]
+    pub danger_level: i32,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub title: String,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 10000))]
+    pub body: String,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateReviewInput {
+    pub user_id: UuidScalar,
+    pub cervidae_id: UuidScalar,
+    pub expected_version: i32,
+    #[graphql(validator(minimum = 1, maximum = 10))]
+    pub danger_level: Option<i32>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub title: Option<String>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 10000))]
+    pub body: Option<String>,
+}
+
+impl UpdateReviewInput {
+    pub fn is_empty(&self) -> bool {
+        self.danger_level.is_none() && self.title.is_none() && self.body.is_none()
+    }
+}
+
+// Shown in place of the content of a deleted comment that still has live replies
+pub const DELETED_COMMENT_PLACEHOLDER: &str = "[deleted]";
+
+#[derive(Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub cervidae_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub content: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    pub version: i32,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub deleted_by: Option<Uuid>,
+    pub upvotes: i32,
+    pub downvotes: i32,
+    pub score: i32,
+    pub controversy: f64,
+    pub shadow_hidden: bool,
+}
+
+#[Object]
+impl Comment {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    /// The author, withheld once the comment is deleted
+    pub async fn user(&self, context: &Context<'_>) -> Result<Option<User>> {
+        if self.deleted_at.is_some() {
+            return Ok(None);
+        }
+        let user = get_user(context, self.user_id).await?;
+        if let Some(user) = user {
+            Ok(Some(user))
+        } else {
+            Err(Error::new("User not found"))
+        }
+    }
+
+    pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
+        let deer = get_deer(context, self.cervidae_id).await?;
+        if let Some(deer) = deer {
+            Ok(deer)
+        } else {
+            Err(Error::new("Deer not found"))
+        }
+    }
+
+    pub async fn parent(&self, context: &Context<'_>) -> Result<Option<Comment>> {
+        if let Some(parent_id) = self.parent_id {
+            let parent = get_comment(context, parent_id).await?;
+            if let Some(parent) = parent {
+                Ok(Some(parent))
+            } else {
+                Ok(None)
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Oldest first unless another sort is asked for
+    pub async fn replies(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+        after: Option<UuidScalar>,
+        sort: Option<CommentSort>,
+    ) -> Result<CommentConnection> {
+        let sort = sort.unwrap_or(CommentSort::Old);
+        get_replies_page(context, self.id, sort, first, after.map(Uuid::from)).await
+    }
+
+    pub async fn reply_count(&self, context: &Context<'_>) -> Result<i64> {
+        get_reply_count(context, self.id).await
+    }
+
+    pub async fn content(&self) -> &str {
+        if self.deleted_at.is_some() {
+            DELETED_COMMENT_PLACEHOLDER
+        } else {
+            &self.content
+        }
+    }
+
+    pub async fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub async fn upvotes(&self) -> i32 {
+        self.upvotes
+    }
+
+    pub async fn downvotes(&self) -> i32 {
+        self.downvotes
+    }
+
+    /// Upvotes minus downvotes
+    pub async fn score(&self) -> i32 {
+        self.score
+    }
+
+    /// How the signed-in user voted, if at all
+    pub async fn viewer_vote(&self, context: &Context<'_>) -> Result<Option<CommentVote>> {
+        let Ok(user_id) = super::current_user_id(context).await else {
+            return Ok(None);
+        };
+        let value = get_comment_vote(context, self.id, user_id).await?;
+        Ok(value.map(CommentVote::from_value))
+    }
+
+    pub async fn version(&self) -> i32 {
+        self.version
+    }
+
+    pub async fn created_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.created_at.map(NaiveDateTimeScalar::from)
+    }
+
+    pub async fn updated_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.updated_at.map(NaiveDateTimeScalar::from)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct CommentEdge {
+    pub node: Comment,
+    pub cursor: String,
+}
+
+#[derive(SimpleObject)]
+pub struct CommentConnection {
+    pub edges: Vec<CommentEdge>,
+    pub page_info: PageInfo,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum CommentSort {
+    New,
+    Old,
+    /// Highest score first
+    Top,
+    /// Most evenly split votes first
+    Controversial,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum CommentVote {
+    Up,
+    Down,
+}
+
+impl CommentVote {
+    pub fn value(self) -> i16 {
+        match self {
+            CommentVote::Up => 1,
+            CommentVote::Down => -1,
+        }
+    }
+
+    pub fn from_value(value: i16) -> Self {
+        if value > 0 {
+            CommentVote::Up
+        } else {
+            CommentVote::Down
+        }
+    }
+}
+
+// Row shape returned by the recursive thread query
+#[derive(FromRow)]
+pub struct CommentThreadRow {
+    #[sqlx(flatten)]
+    pub comment: Comment,
+    pub depth: i32,
+}
+
+#[derive(SimpleObject)]
+pub struct CommentThreadNode {
+    pub comment: Comment,
+    pub depth: i32,
+    pub descendant_count: i64,
+    pub replies: Vec<CommentThreadNode>,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CreateCommentInput {
+    pub cervidae_id: UuidScalar,
+    pub parent_id: Option<UuidScalar>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 5000))]
+    pub content: String,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateCommentInput {
+    pub id: UuidScalar,
+    pub expected_version: i32,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 5000))]
+    pub content: Option<String>,
+}
+
+impl UpdateCommentInput {
+    pub fn is_empty(&self) -> bool {
+        self.content.is_none()
+    }
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Crime {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    pub version: i32,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub deleted_by: Option<Uuid>,
+    pub severity: i32,
+    pub category_id: Uuid,
+}
+
+#[Object]
+impl Crime {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// From 1 for petty offences to 10 for the gravest
+    pub async fn severity(&self) -> i32 {
+        self.severity
+    }
+
+    /// The broad kind of crime, read from the top-level category it is filed under
+    pub async fn category(&self, context: &Context<'_>) -> Result<CrimeCategory> {
+        let root = get_crime_category_root(context, self.category_id).await?;
+        if let Some(root) = root {
+            Ok(CrimeCategory::from_slug(&root.slug))
+        } else {
+            Err(Error::new("Crime category not found"))
+        }
+    }
+
+    /// The category the crime is filed under, at any depth of the tree
+    pub async fn category_node(&self, context: &Context<'_>) -> Result<CrimeCategoryNode> {
+        let category = get_crime_category(context, self.category_id).await?;
+        if let Some(category) = category {
+            Ok(category)
+        } else {
+            Err(Error::new("Crime category not found"))
+        }
+    }
+
+    /// How the crime is written into law, optionally in one jurisdiction only
+    pub async fn statutes(
+        &self,
+        context: &Context<'_>,
+        jurisdiction: Option<String>,
+    ) -> Result<Vec<Statute>> {
+        get_statutes(context, Some(self.id), jurisdiction.as_deref()).await
+    }
+
+    pub async fn version(&self) -> i32 {
+        self.version
+    }
+
+    pub async fn created_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.created_at.map(NaiveDateTimeScalar::from)
+    }
+
+    pub async fn updated_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.updated_at.map(NaiveDateTimeScalar::from)
+    }
+
+    /// Dated incidents of the crime, most recent first
+    pub async fn incidents(&self, context: &Context<'_>) -> Result<Vec<Incident>> {
+        get_incidents_by_crime(context, self.id).await
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CreateCrimeInput {
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub name: String,
+    #[graphql(validator(chars_max_length = 5000))]
+    pub description: String,
+    #[graphql(validator(minimum = 1, maximum = 10))]
+    pub severity: Option<i32>,
+    pub category_id: Option<UuidScalar>,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateCrimeInput {
+    pub id: UuidScalar,
+    pub expected_version: i32,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub name: Option<String>,
+    #[graphql(validator(chars_max_length = 5000))]
+    pub description: Option<String>,
+    #[graphql(validator(minimum = 1, maximum = 10))]
+    pub severity: Option<i32>,
+    pub category_id: Option<UuidScalar>,
+}
+
+impl UpdateCrimeInput {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.severity.is_none()
+            && self.category_id.is_none()
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum CrimeCategory {
+    Violent,
+    Property,
+    State,
+    Other,
+}
+
+impl CrimeCategory {
+    // Top-level categories added since the enum was fixed fall under Other
+    pub fn from_slug(slug: &str) -> Self {
+        match slug {
+            "violent" => CrimeCategory::Violent,
+            "property" => CrimeCategory::Property,
+            "state" => CrimeCategory::State,
+            _ => CrimeCategory::Other,
+        }
+    }
+}
+
+#[derive(FromRow)]
+pub struct CrimeCategoryNode {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[Object]
+impl CrimeCategoryNode {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub async fn parent(&self, context: &Context<'_>) -> Result<Option<CrimeCategoryNode>> {
+        match self.parent_id {
+            Some(id) => get_crime_category(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn children(&self, context: &Context<'_>) -> Result<Vec<CrimeCategoryNode>> {
+        get_crime_category_children(context, Some(self.id)).await
+    }
+
+    /// Crimes filed under this category, and by default under its subcategories too
+    pub async fn crimes(
+        &self,
+        context: &Context<'_>,
+        include_subcategories: Option<bool>,
+    ) -> Result<Vec<Crime>> {
+        get_crimes_by_category(context, self.id, include_subcategories.unwrap_or(true)).await
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CreateCrimeCategoryInput {
+    pub parent_id: Option<UuidScalar>,
+    #[graphql(validator(regex = "^[a-z0-9]+(-[a-z0-9]+)*$", chars_max_length = 100))]
+    pub slug: String,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub name: String,
+    #[graphql(validator(chars_max_length = 5000))]
+    pub description: Option<String>,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateCrimeCategoryInput {
+    pub id: UuidScalar,
+    pub parent_id: Option<UuidScalar>,
+    #[graphql(validator(regex = "^[a-z0-9]+(-[a-z0-9]+)*$", chars_max_length = 100))]
+    pub slug: Option<String>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub name: Option<String>,
+    #[graphql(validator(chars_max_length = 5000))]
+    pub description: Option<String>,
+}
+
+impl UpdateCrimeCategoryInput {
+    pub fn is_empty(&self) -> bool {
+        self.parent_id.is_none()
+            && self.slug.is_none()
+            && self.name.is_none()
+            && self.description.is_none()
+    }
+}
+
+#[derive(FromRow)]
+pub struct Statute {
+    pub id: Uuid,
+    pub crime_id: Uuid,
+    pub jurisdiction: String,
+    pub code: String,
+    pub title: Option<String>,
+    pub text: String,
+    pub source_url: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[Object]
+impl Statute {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    /// The base crime the statute defines
+    pub async fn crime(&self, context: &Context<'_>) -> Result<Crime> {
+        let crime = get_crime(context, self.crime_id).await?;
+        if let Some(crime) = crime {
+            Ok(crime)
+        } else {
+            Err(Error::new("Crime not found"))
+        }
+    }
+
+    pub async fn jurisdiction(&self) -> &str {
+        &self.jurisdiction
+    }
+
+    /// Section reference within the jurisdiction's code, such as "s. 1"
+    pub async fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub async fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub async fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub async fn source_url(&self) -> Option<&str> {
+        self.source_url.as_deref()
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+
+    pub async fn updated_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.updated_at)
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CreateStatuteInput {
+    pub crime_id: UuidScalar,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub jurisdiction: String,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub code: String,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub title: Option<String>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 20000))]
+    pub text: String,
+    #[graphql(validator(url, chars_max_length = 2048))]
+    pub source_url: Option<String>,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateStatuteInput {
+    pub id: UuidScalar,
+    pub crime_id: Option<UuidScalar>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub jurisdiction: Option<String>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub code: Option<String>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub title: Option<String>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 20000))]
+    pub text: Option<String>,
+    #[graphql(validator(url, chars_max_length = 2048))]
+    pub source_url: Option<String>,
+}
+
+impl UpdateStatuteInput {
+    pub fn is_empty(&self) -> bool {
+        self.crime_id.is_none()
+            && self.jurisdiction.is_none()
+            && self.code.is_none()
+            && self.title.is_none()
+            && self.text.is_none()
+            && self.source_url.is_none()
+    }
+}
+
+#[derive(Enum, sqlx::Type, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[sqlx(type_name = "Verdict")]
+pub enum Verdict {
+    Pending,
+    Guilty,
+    Acquitted,
+    Dismissed,
+}
+
+#[derive(FromRow)]
+pub struct Case {
+    pub id: Uuid,
+    pub cervidae_id: Uuid,
+    pub jurisdiction: String,
+    pub docket_number: Option<String>,
+    pub summary: Option<String>,
+    pub opened_by: Option<Uuid>,
+    pub opened_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[Object]
+impl Case {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
+        let deer = get_deer(context, self.cervidae_id).await?;
+        if let Some(deer) = deer {
+            Ok(deer)
+        } else {
+            Err(Error::new("Deer not found"))
+        }
+    }
+
+    pub async fn jurisdiction(&self) -> &str {
+        &self.jurisdiction
+    }
+
+    pub async fn docket_number(&self) -> Option<&str> {
+        self.docket_number.as_deref()
+    }
+
+    pub async fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    pub async fn charges(&self, context: &Context<'_>) -> Result<Vec<Charge>> {
+        get_charges_by_case(context, self.id).await
+    }
+
+    /// Earliest first, past hearings included
+    pub async fn hearings(&self, context: &Context<'_>) -> Result<Vec<Hearing>> {
+        get_hearings_by_case(context, self.id).await
+    }
+
+    /// Whether the case has no charges yet or any charge still awaits a verdict
+    pub async fn open(&self, context: &Context<'_>) -> Result<bool> {
+        let charges = get_charges_by_case(context, self.id).await?;
+        Ok(charges.is_empty()
+            || charges
+                .iter()
+                .any(|charge| charge.verdict == Verdict::Pending))
+    }
+
+    pub async fn opened_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.opened_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn opened_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.opened_at)
+    }
+
+    pub async fn updated_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.updated_at)
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct OpenCaseInput {
+    pub cervidae_id: UuidScalar,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub jurisdiction: String,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub docket_number: Option<String>,
+    #[graphql(validator(chars_max_length = 5000))]
+    pub summary: Option<String>,
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateCaseInput {
+    pub id: UuidScalar,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub jurisdiction: Option<String>,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub docket_number: Option<String>,
+    #[graphql(validator(chars_max_length = 5000))]
+    pub summary: Option<String>,
+}
+
+impl UpdateCaseInput {
+    pub fn is_empty(&self) -> bool {
+        self.jurisdiction.is_none() && self.docket_number.is_none() && self.summary.is_none()
+    }
+}
+
+#[derive(FromRow)]
+pub struct Charge {
+    pub id: Uuid,
+    pub case_id: Uuid,
+    pub crime_id: Uuid,
+    pub incident_id: Option<Uuid>,
+    pub statute_id: Option<Uuid>,
+    pub verdict: Verdict,
+    pub decided_at: Option<NaiveDateTime>,
+    pub sentence: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[Object]
+impl Charge {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn case(&self, context: &Context<'_>) -> Result<Case> {
+        let case = get_case(context, self.case_id).await?;
+        if let Some(case) = case {
+            Ok(case)
+        } else {
+            Err(Error::new("Case not found"))
+        }
+    }
+
+    pub async fn crime(&self, context: &Context<'_>) -> Result<Crime> {
+        let crime = get_crime(context, self.crime_id).await?;
+        if let Some(crime) = crime {
+            Ok(crime)
+        } else {
+            Err(Error::new("Crime not found"))
+        }
+    }
+
+    /// The occurrence the charge is brought over, when known
+    pub async fn incident(&self, context: &Context<'_>) -> Result<Option<Incident>> {
+        match self.incident_id {
+            Some(id) => get_incident(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// The section of the case's jurisdiction the charge is brought under
+    pub async fn statute(&self, context: &Context<'_>) -> Result<Option<Statute>> {
+        match self.statute_id {
+            Some(id) => get_statute(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn verdict(&self) -> Verdict {
+        self.verdict
+    }
+
+    pub async fn decided_at(&self) -> Option<NaiveDateTimeScalar> {
+        self.decided_at.map(NaiveDateTimeScalar::from)
+    }
+
+    pub async fn sentence(&self) -> Option<&str> {
+        self.sentence.as_deref()
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+
+    pub async fn updated_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.updated_at)
+    }
+}
+
+/// The incident must be the deer's, for the same crime, and the statute from the case's jurisdiction
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct FileChargeInput {
+    pub case_id: UuidScalar,
+    pub crime_id: UuidScalar,
+    pub incident_id: Option<UuidScalar>,
+    pub statute_id: Option<UuidScalar>,
+}
+
+/// A sentence goes with a guilty verdict only; going back to pending reopens the charge
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct RecordVerdictInput {
+    pub charge_id: UuidScalar,
+    pub verdict: Verdict,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 2000))]
+    pub sentence: Option<String>,
+}
+
+#[derive(FromRow)]
+pub struct Hearing {
+    pub id: Uuid,
+    pub case_id: Uuid,
+    pub scheduled_at: NaiveDateTime,
+    pub court: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[Object]
+impl Hearing {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn case(&self, context: &Context<'_>) -> Result<Case> {
+        let case = get_case(context, self.case_id).await?;
+        if let Some(case) = case {
+            Ok(case)
+        } else {
+            Err(Error::new("Case not found"))
+        }
+    }
+
+    pub async fn scheduled_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.scheduled_at)
+    }
+
+    pub async fn court(&self) -> Option<&str> {
+        self.court.as_deref()
+    }
+
+    pub async fn notes(&self) -> Option<&str> {
+        self.notes.as_deref()
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct ScheduleHearingInput {
+    pub case_id: UuidScalar,
+    pub scheduled_at: NaiveDateTimeScalar,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 200))]
+    pub court: Option<String>,
+    #[graphql(validator(chars_max_length = 2000))]
+    pub notes: Option<String>,
+}
+
+/// What a deer has been found guilty of, as opposed to the crimes it is accused of
+pub struct CriminalRecord {
+    pub cervidae_id: Uuid,
+}
+
+#[Object]
+impl CriminalRecord {
+    /// Guilty charges, most recently decided first
+    pub async fn convictions(&self, context: &Context<'_>) -> Result<Vec<Charge>> {
+        get_charges_by_deer(context, self.cervidae_id, Verdict::Guilty).await
+    }
+
+    /// Each crime the deer stands convicted of, once
+    pub async fn convicted_of(&self, context: &Context<'_>) -> Result<Vec<Crime>> {
+        get_convicted_crimes(context, self.cervidae_id).await
+    }
+
+    /// Charges still awaiting a verdict, which count for nothing yet
+    pub async fn pending_charges(&self, context: &Context<'_>) -> Result<Vec<Charge>> {
+        get_charges_by_deer(context, self.cervidae_id, Verdict::Pending).await
+    }
+}
+
+#[derive(FromRow)]
+pub struct Tag {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub banned: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+}
+
+#[Object]
+impl Tag {
+    pub async fn id(&self) -> UuidScalar {
+        UuidScalar::from(self.id)
+    }
+
+    pub async fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Banned tags cannot be applied and no longer describe any deer
+    pub async fn banned(&self) -> bool {
+        self.banned
+    }
+
+    /// Other slugs that resolve to this tag
+    pub async fn synonyms(&self, context: &Context<'_>) -> Result<Vec<String>> {
+        get_tag_synonyms(context, self.id).await
+    }
+
+    pub async fn created_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.created_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateTagInput {
+    pub id: UuidScalar,
+    #[graphql(validator(chars_min_length = 1, chars_max_length = 100))]
+    pub name: Option<String>,
+    #[graphql(validator(chars_max_length = 2000))]
+    pub description: Option<String>,
+    pub banned: Option<bool>,
+}
+
+impl UpdateTagInput {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.description.is_none() && self.banned.is_none()
+    }
+}
+
+/// A tag on one deer with the votes on whether it fits
+#[derive(FromRow)]
+pub struct DeerTag {
+    pub cervidae_id: Uuid,
+    pub tag_id: Uuid,
+    pub tagged_by: Option<Uuid>,
+    pub upvotes: i32,
+    pub downvotes: i32,
+    pub score: i32,
+    pub created_at: NaiveDateTime,
+}
+
+#[Object]
+impl DeerTag {
+    pub async fn tag(&self, context: &Context<'_>) -> Result<Tag> {
+        let tag = get_tag(context, self.tag_id).await?;
+        if let Some(tag) = tag {
+            Ok(tag)
+        } else {
+            Err(Error::new("Tag not found"))
+        }
+    }
+
+    pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
+        let deer = get_deer(context, self.cervidae_id).await?;
+        if let Some(deer) = deer {
+            Ok(deer)
+        } else {
+            Err(Error::new("Deer not found"))
+        }
+    }
+
+    pub async fn tagged_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.tagged_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn upvotes(&self) -> i32 {
+        self.upvotes
+    }
+
+    pub async fn downvotes(&self) -> i32 {
+        self.downvotes
+    }
+
+    /// The tag only describes the deer while this is positive
+    pub async fn score(&self) -> i32 {
+        self.score
+    }
+
+    pub async fn viewer_vote(&self, context: &Context<'_>) -> Result<Option<TagVote>> {
+        let Ok(user_id) = super::current_user_id(context).await else {
+            return Ok(None);
+        };
+        let value = get_deer_tag_vote(context, self.cervidae_id, self.tag_id, user_id).await?;
+        Ok(value.map(TagVote::from_value))
+    }
+
+    pub async fn created_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.created_at)
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum TagVote {
+    Up,
+    Down,
+}
+
+impl TagVote {
+    pub fn value(self) -> i16 {
+        match self {
+            TagVote::Up => 1,
+            TagVote::Down => -1,
+        }
+    }
+
+    pub fn from_value(value: i16) -> Self {
+        if value > 0 {
+            TagVote::Up
+        } else {
+            TagVote::Down
+        }
+    }
+}
+
+/// A tag with the number of approved entries it currently describes
+#[derive(SimpleObject, FromRow)]
+pub struct PopularTag {
+    #[sqlx(flatten)]
+    pub tag: Tag,
+    pub deer_count: i64,
+}
+
+#[derive(FromRow)]
+pub struct ThreatConfig {
+    pub id: bool,
+    pub severity_weight: f64,
+    pub frequency_weight: f64,
+    pub kill_weight: f64,
+    pub danger_weight: f64,
+    pub frequency_window_days: i32,
+    pub frequency_half_count: i32,
+    pub kill_count_cap: i64,
+    pub updated_at: NaiveDateTime,
+    pub updated_by: Option<Uuid>,
+}
+
+#[Object]
+impl ThreatConfig {
+    pub async fn severity_weight(&self) -> f64 {
+        self.severity_weight
+    }
+
+    pub async fn frequency_weight(&self) -> f64 {
+        self.frequency_weight
+    }
+
+    pub async fn kill_weight(&self) -> f64 {
+        self.kill_weight
+    }
+
+    pub async fn danger_weight(&self) -> f64 {
+        self.danger_weight
+    }
+
+    /// Days of verified incidents that count towards the frequency factor
+    pub async fn frequency_window_days(&self) -> i32 {
+        self.frequency_window_days
+    }
+
+    /// Incidents in the window at which the frequency factor reaches one half
+    pub async fn frequency_half_count(&self) -> i32 {
+        self.frequency_half_count
+    }
+
+    /// Kill count at which the kill factor saturates, on a log scale
+    pub async fn kill_count_cap(&self) -> i64 {
+        self.kill_count_cap
+    }
+
+    pub async fn updated_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.updated_at)
+    }
+
+    pub async fn updated_by(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.updated_by {
+            Some(id) => get_user(context, id).await,
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct UpdateThreatConfigInput {
+    pub severity_weight: Option<f64>,
+    pub frequency_weight: Option<f64>,
+    pub kill_weight: Option<f64>,
+    pub danger_weight: Option<f64>,
+    #[graphql(validator(minimum = 1))]
+    pub frequency_window_days: Option<i32>,
+    #[graphql(validator(minimum = 1))]
+    pub frequency_half_count: Option<i32>,
+    #[graphql(validator(minimum = 1))]
+    pub kill_count_cap: Option<i64>,
+}
+
+/// The raw figures a threat score is computed from
+#[derive(FromRow)]
+pub struct ThreatInputs {
+    pub max_severity: Option<i32>,
+    pub recent_incidents: i64,
+    pub danger_score: Option<f64>,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ThreatFactor {
+    /// Severity of the gravest linked crime
+    CrimeSeverity,
+    /// Verified incidents within the configured window
+    IncidentFrequency,
+    KillCount,
+    /// Credibility-weighted danger score from reviews
+    ReviewDanger,
+}
+
+#[derive(SimpleObject)]
+pub struct ThreatComponent {
+    pub factor: ThreatFactor,
+    /// The figure before scaling, or none when the deer has no data for it
+    pub raw_value: Option<f64>,
+    /// The figure scaled to 0..1
+    pub normalized: f64,
+    pub weight: f64,
+    /// Points this factor adds to the score
+    pub contribution: f64,
+}
+
+#[derive(SimpleObject)]
+pub struct ThreatScore {
+    /// Weighted mean of the factors, from 0 to 100
+    pub score: f64,
+    pub components: Vec<ThreatComponent>,
+}
+
+#[derive(Deserialize, FromRow)]
+pub struct CrimeCervidae {
+    pub crime_id: Uuid,
+    pub cervidae_id: Uuid,
+    pub created_at: NaiveDateTime,
+}
+
+#[Object]
+impl CrimeCervidae {
+    pub async fn crime(&self, context: &Context<'_>) -> Result<Crime> {
+        let crime = get_crime(context, self.crime_id).await?;
+        if let Some(crime) = crime {
+            Ok(crime)
+        } else {
+            Err(Error::new("Crime not found"))
+        }
+    }
+
+    pub async fn deer(&self, context: &Context<'_>) -> Result<Deer> {
+        let deer = get_deer(context, self.cervidae_id).await?;
+        if let Some(deer) = deer {
+            Ok(deer)
+        } else {
+            Err(Error::new("Deer not found"))
+        }
+    }
+}
+
+#[derive(InputObject, Debug, Serialize, Deserialize)]
+pub struct CrimeCervidaeInput {
+    pub crime_id: UuidScalar,
+    pub cervidae_id: UuidScalar,
+}
+
+#[derive(Enum, sqlx::Type, Copy, Clone, Eq, PartialEq)]
+#[sqlx(type_name = "Leaderboard_Kind")]
+pub enum LeaderboardKind {
+    DeerKillCount,
+    DeerDangerScore,
+    DeerCrimes,
+    UserSubmissions,
+    UserReviews,
+    UserHelpfulVotes,
+}
+
+#[derive(Enum, sqlx::Type, Copy, Clone, Eq, PartialEq)]
+#[sqlx(type_name = "Leaderboard_Period")]
+pub enum LeaderboardPeriod {
+    AllTime,
+    #[graphql(name = "LAST_30_DAYS")]
+    Last30Days,
+    #[graphql(name = "LAST_7_DAYS")]
+    Last7Days,
+}
+
+#[derive(FromRow)]
+pub struct LeaderboardEntry {
+    pub board: LeaderboardKind,
+    pub period: LeaderboardPeriod,
+    pub rank: i32,
+    pub subject_id: Uuid,
+    pub value: f64,
+    pub refreshed_at: NaiveDateTime,
+}
+
+#[Object]
+impl LeaderboardEntry {
+    pub async fn rank(&self) -> i32 {
+        self.rank
+    }
+
+    pub async fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub async fn refreshed_at(&self) -> NaiveDateTimeScalar {
+        NaiveDateTimeScalar::from(self.refreshed_at)
+    }
+
+    pub async fn deer(&self, context: &Context<'_>) -> Result<Option<Deer>> {
+        match self.board {
+            LeaderboardKind::DeerKillCount
+            | LeaderboardKind::DeerDangerScore
+            | LeaderboardKind::DeerCrimes => get_deer(context, self.subject_id).await,
+            _ => Ok(None),
+        }
+    }
+
+    pub async fn user(&self, context: &Context<'_>) -> Result<Option<User>> {
+        match self.board {
+            LeaderboardKind::UserSubmissions
+            | LeaderboardKind::UserReviews
+            | LeaderboardKind::UserHelpfulVotes => get_user(context, self.subject_id).await,
+            _ => Ok(None),
+        }
+    }
+}
+
+pub struct Leaderboards {
+    pub period: LeaderboardPeriod,
+}
+
+#[Object]
+impl Leaderboards {
+    pub async fn period(&self) -> LeaderboardPeriod {
+        self.period
+    }
+
+    pub async fn top_deer_by_kill_count(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        get_leaderboard(context, LeaderboardKind::DeerKillCount, self.period, first).await
+    }
+
+    pub async fn top_deer_by_danger_score(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        get_leaderboard(
+            context,
+            LeaderboardKind::DeerDangerScore,
+            self.period,
+            first,
+        )
+        .await
+    }
+
+    pub async fn top_deer_by_crimes(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        get_leaderboard(context, LeaderboardKind::DeerCrimes, self.period, first).await
+    }
+
+    pub async fn top_users_by_submissions(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        get_leaderboard(
+            context,
+            LeaderboardKind::UserSubmissions,
+            self.period,
+            first,
+        )
+        .await
+    }
+
+    pub async fn top_users_by_reviews(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        get_leaderboard(context, LeaderboardKind::UserReviews, self.period, first).await
+    }
+
+    pub async fn top_users_by_helpful_votes(
+        &self,
+        context: &Context<'_>,
+        first: Option<i64>,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        get_leaderboard(
+            context,
+            LeaderboardKind::UserHelpfulVotes,
+            self.period,
+            first,
+        )
+        .await
+    }
+}
+
+#[derive(Enum, sqlx::Type, Copy, Clone, Eq, PartialEq)]
+#[sqlx(type_name = "Moderation_Decision")]
+pub enum ModerationDecision {
+    Approve,
+    Reject,
+}
+
+/// What happens to the reviews and comments of a deleted account
+#[derive(Enum, sqlx::Type, Copy, Clone, Eq, PartialEq)]
+#[sqlx(type_name = "Account_Content_Policy")]
+pub enum AccountContentPolicy {
+    /// Keep the content, attributed to the ghost user
+    Keep,
+    /// Remove reviews and blank out comments, leaving placeholders where threads need them
+    Erase,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum BulkErrorCode {
+    NotFound,
+    InvalidStatus,
+    AlreadyAssigned,
+    Duplicate,
+    RolledBack,
+    DatabaseError,
+    OwnSubmission,
+}
+
+#[derive(SimpleObject)]
+pub struct BulkDeerResult {
+    pub deer_id: UuidScalar,
+    pub ok: bool,
+    pub error_code: Option<BulkErrorCode>,
+    pub message: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct BulkModerationOutcome {
+    /// False when the batch was atomic and at least one item failed, in which case nothing was written
+    pub committed: bool,
+    pub results: Vec<BulkDeerResult>,
+}
+
+#[derive(SimpleObject)]
+pub struct BulkCrimeAssignmentResult {
+    pub deer_id: UuidScalar,
+    pub crime_id: UuidScalar,
+    pub ok: bool,
+    pub error_code: Option<BulkErrorCode>,
+    pub message: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct BulkAssignmentOutcome {
+    /// False when the batch was atomic and at least one item failed, in which case nothing was written
+    pub committed: bool,
+    pub results: Vec<BulkCrimeAssignmentResult>,
 }
 
 #[derive(Debug, Serialize, Deserialize, SimpleObject)]
@@ -583,6 +3252,9 @@ pub struct Claims {
     pub exp: usize,
     pub iat: usize,
     pub iss: String,
+    // Id of the User_Session row backing this token
+    #[graphql(skip)]
+    pub jti: String,
 }
 
 #[derive(Serialize, SimpleObject)]
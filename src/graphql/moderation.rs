@@ -0,0 +1,28 @@
+use crate::graphql::models::DeerEntryStatus;
+use sqlx::{query_scalar, PgConnection, PgPool};
+use uuid::Uuid;
+
+// Moves a pending deer once its votes meet the quorum rules, returning the new status
+pub async fn settle_moderation_round(
+    conn: &mut PgConnection,
+    deer: Uuid,
+) -> Result<Option<DeerEntryStatus>, sqlx::Error> {
+    query_scalar!(
+        r#"SELECT settle_moderation_round($1) AS "status: DeerEntryStatus""#,
+        deer
+    )
+    .fetch_one(conn)
+    .await
+}
+
+// Settles every pending deer whose voting window has closed with a majority
+pub async fn settle_expired_rounds(pool: &PgPool) -> Result<i64, sqlx::Error> {
+    let settled = query_scalar!(
+        r#"
+        SELECT COUNT(settle_moderation_round(id)) FROM Cervidae
+         WHERE status = 'Pending' AND deleted_at IS NULL"#
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(settled.unwrap_or(0))
+}
@@ -0,0 +1,187 @@
+use crate::graphql::models::{
+    ThreatComponent, ThreatConfig, ThreatFactor, ThreatInputs, ThreatScore,
+};
+use sqlx::{query, PgPool};
+use uuid::Uuid;
+
+// How many phantom reviews at the global mean every deer starts with
+pub const DANGER_PRIOR_WEIGHT: f64 = 5.0;
+
+// Entries kept per leaderboard and period
+pub const LEADERBOARD_SIZE: i32 = 25;
+
+// Recomputes the Bayesian danger score of one deer, or of every deer when `deer` is None.
+// The prior mean and reviewer credibility are global, so a write that only refreshes its
+// own deer leaves every other deer slightly stale until the next `refresh_leaderboards` run
+pub async fn refresh_danger_scores(pool: &PgPool, deer: Option<Uuid>) -> Result<(), sqlx::Error> {
+    query!(
+        "SELECT refresh_danger_scores($1, $2)",
+        deer,
+        DANGER_PRIOR_WEIGHT
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// Recomputes every danger score, then rebuilds the leaderboard tables from scratch
+pub async fn refresh_leaderboards(pool: &PgPool) -> Result<(), sqlx::Error> {
+    refresh_danger_scores(pool, None).await?;
+    query!(
+        "SELECT refresh_leaderboards($1, $2)",
+        LEADERBOARD_SIZE,
+        DANGER_PRIOR_WEIGHT
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// Scales each factor to 0..1 and takes their weighted mean, out of 100.
+// With every weight at zero there is nothing to average and the score is 0
+pub fn threat_score(
+    config: &ThreatConfig,
+    inputs: &ThreatInputs,
+    kill_count: Option<i64>,
+) -> ThreatScore {
+    let recent_incidents = inputs.recent_incidents as f64;
+    let factors = [
+        (
+            ThreatFactor::CrimeSeverity,
+            inputs.max_severity.map(f64::from),
+            inputs
+                .max_severity
+                .map_or(0.0, |severity| f64::from(severity) / 10.0),
+            config.severity_weight,
+        ),
+        (
+            ThreatFactor::IncidentFrequency,
+            Some(recent_incidents),
+            recent_incidents / (recent_incidents + f64::from(config.frequency_half_count)),
+            config.frequency_weight,
+        ),
+        (
+            ThreatFactor::KillCount,
+            kill_count.map(|count| count as f64),
+            kill_count.map_or(0.0, |count| {
+                let cap = config.kill_count_cap as f64;
+                ((count.max(0) as f64).ln_1p() / cap.ln_1p()).min(1.0)
+            }),
+            config.kill_weight,
+        ),
+        (
+            ThreatFactor::ReviewDanger,
+            inputs.danger_score,
+            inputs
+                .danger_score
+                .map_or(0.0, |score| ((score - 1.0) / 9.0).clamp(0.0, 1.0)),
+            config.danger_weight,
+        ),
+    ];
+    let total_weight: f64 = factors.iter().map(|(_, _, _, weight)| weight).sum();
+    let components: Vec<ThreatComponent> = factors
+        .into_iter()
+        .map(|(factor, raw_value, normalized, weight)| ThreatComponent {
+            factor,
+            raw_value,
+            normalized,
+            weight,
+            contribution: if total_weight > 0.0 {
+                100.0 * weight * normalized / total_weight
+            } else {
+                0.0
+            },
+        })
+        .collect();
+    ThreatScore {
+        score: components
+            .iter()
+            .map(|component| component.contribution)
+            .sum(),
+        components,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn config() -> ThreatConfig {
+        ThreatConfig {
+            id: true,
+            severity_weight: 0.35,
+            frequency_weight: 0.2,
+            kill_weight: 0.25,
+            danger_weight: 0.2,
+            frequency_window_days: 365,
+            frequency_half_count: 3,
+            kill_count_cap: 10_000_000,
+            updated_at: NaiveDateTime::default(),
+            updated_by: None,
+        }
+    }
+
+    fn inputs(max_severity: Option<i32>, recent_incidents: i64) -> ThreatInputs {
+        ThreatInputs {
+            max_severity,
+            recent_incidents,
+            danger_score: None,
+        }
+    }
+
+    #[test]
+    fn threat_score_is_zero_with_zero_total_weight() {
+        let config = ThreatConfig {
+            severity_weight: 0.0,
+            frequency_weight: 0.0,
+            kill_weight: 0.0,
+            danger_weight: 0.0,
+            ..config()
+        };
+        let score = threat_score(&config, &inputs(Some(10), 5), Some(100));
+        assert_eq!(score.score, 0.0);
+        assert!(score
+            .components
+            .iter()
+            .all(|component| component.contribution == 0.0));
+    }
+
+    #[test]
+    fn threat_score_caps_kill_count_at_one() {
+        let config = ThreatConfig {
+            kill_count_cap: 1,
+            ..config()
+        };
+        let score = threat_score(&config, &inputs(None, 0), Some(1_000));
+        let kills = &score.components[2];
+        assert!(matches!(kills.factor, ThreatFactor::KillCount));
+        assert_eq!(kills.normalized, 1.0);
+        assert!((score.score - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn threat_score_treats_missing_inputs_as_zero() {
+        let score = threat_score(&config(), &inputs(None, 0), None);
+        assert_eq!(score.score, 0.0);
+        assert!(score.components[0].raw_value.is_none());
+        assert_eq!(score.components[1].raw_value, Some(0.0));
+        assert!(score.components[2].raw_value.is_none());
+        assert!(score.components[3].raw_value.is_none());
+    }
+
+    #[test]
+    fn threat_score_reaches_100_when_every_factor_is_maxed() {
+        let config = ThreatConfig {
+            frequency_weight: 0.0,
+            kill_count_cap: 1,
+            ..config()
+        };
+        let inputs = ThreatInputs {
+            danger_score: Some(10.0),
+            ..inputs(Some(10), 0)
+        };
+        let score = threat_score(&config, &inputs, Some(1));
+        assert!((score.score - 100.0).abs() < 1e-9);
+    }
+}
@@ -1,8 +1,80 @@
 use crate::graphql::models::*;
-use async_graphql::{Context, Result};
-use sqlx::{self, query_as, PgPool};
+use crate::graphql::scoring::LEADERBOARD_SIZE;
+use async_graphql::{Context, Error, Result};
+use chrono::NaiveDateTime;
+use sqlx::{self, query_as, query_scalar, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+
+// Columns a voted page is ordered by; the id always comes last so the order is total
+const NEWEST_KEY: &[&str] = &["created_at", "id"];
+const TOP_KEY: &[&str] = &["score", "created_at", "id"];
+const CONTROVERSIAL_KEY: &[&str] = &["controversy", "score", "id"];
+
+// Sort key and whether the page runs from high to low
+fn review_sort_key(sort: ReviewSort) -> (&'static [&'static str], bool) {
+    match sort {
+        ReviewSort::New => (NEWEST_KEY, true),
+        ReviewSort::Old => (NEWEST_KEY, false),
+        ReviewSort::Top => (TOP_KEY, true),
+        ReviewSort::Controversial => (CONTROVERSIAL_KEY, true),
+    }
+}
+
+fn comment_sort_key(sort: CommentSort) -> (&'static [&'static str], bool) {
+    match sort {
+        CommentSort::New => (NEWEST_KEY, true),
+        CommentSort::Old => (NEWEST_KEY, false),
+        CommentSort::Top => (TOP_KEY, true),
+        CommentSort::Controversial => (CONTROVERSIAL_KEY, true),
+    }
+}
+
+// Restricts to the rows after the cursor row in that order, or with before set,
+// to the cursor row and the rows ahead of it
+fn push_voted_cursor(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    table: &str,
+    (key, descending): (&[&str], bool),
+    cursor: Uuid,
+    before: bool,
+) {
+    let columns = key.join(", ");
+    query_builder.push(format!(" AND ({}) ", columns));
+    query_builder.push(match (descending, before) {
+        (true, false) => "<",
+        (false, false) => ">",
+        (true, true) => ">=",
+        (false, true) => "<=",
+    });
+    query_builder.push(format!(" (SELECT {} FROM {} WHERE id = ", columns, table));
+    query_builder.push_bind(cursor);
+    query_builder.push(")");
+}
+
+// Adds the order and, past a cursor, the rows after the cursor row in that order
+fn push_voted_page(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    table: &str,
+    (key, descending): (&[&str], bool),
+    after: Option<Uuid>,
+    limit: i64,
+) {
+    if let Some(after) = after {
+        push_voted_cursor(query_builder, table, (key, descending), after, false);
+    }
+    let direction = if descending { " DESC" } else { " ASC" };
+    let order: Vec<String> = key
+        .iter()
+        .map(|column| format!("{}{}", column, direction))
+        .collect();
+    query_builder.push(" ORDER BY ");
+    query_builder.push(order.join(", "));
+    query_builder.push(" LIMIT ");
+    query_builder.push_bind(limit);
+}
+
 pub async fn get_user(context: &Context<'_>, id: Uuid) -> Result<Option<User>> {
     let user = query_as!(User, "SELECT * FROM Users WHERE id = $1", id)
         .fetch_optional(context.data_unchecked::<PgPool>())
@@ -13,15 +85,476 @@ pub async fn get_user(context: &Context<'_>, id: Uuid) -> Result<Option<User>> {
 }
 
 pub async fn get_deer(context: &Context<'_>, id: Uuid) -> Result<Option<Deer>> {
-    let deer: Option<Deer> = query_as("SELECT * FROM Cervidae WHERE id = $1")
-        .bind(id)
+    let deer: Option<Deer> =
+        query_as("SELECT * FROM Cervidae WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(|e| e.to_string())?;
+
+    match deer {
+        // Drafts stay private to their submitter and admins
+        Some(deer) if deer.status == DeerEntryStatus::Draft => {
+            let visible = super::require_owner_or_admin(context, deer.created_by).await;
+            Ok(visible.ok().map(|_| deer))
+        }
+        deer => Ok(deer),
+    }
+}
+
+// Sightings are only shown while their deer is, as in push_sighting_filter
+pub async fn get_sighting(context: &Context<'_>, id: Uuid) -> Result<Option<Sighting>> {
+    let sighting = query_as(
+        r#"
+        SELECT * FROM Sighting WHERE id = $1
+         AND EXISTS (SELECT 1 FROM Cervidae WHERE Cervidae.id = Sighting.cervidae_id
+            AND Cervidae.status = 'Approved' AND Cervidae.deleted_at IS NULL)"#,
+    )
+    .bind(id)
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(sighting)
+}
+
+pub async fn get_sighting_disputes(
+    context: &Context<'_>,
+    id: Uuid,
+) -> Result<Vec<SightingDispute>> {
+    let disputes = query_as!(
+        SightingDispute,
+        "SELECT * FROM Sighting_Dispute WHERE sighting_id = $1 ORDER BY created_at",
+        id
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(disputes)
+}
+
+pub async fn get_sightings_page(
+    context: &Context<'_>,
+    id: Uuid,
+    first: Option<i64>,
+    after: Option<Uuid>,
+) -> Result<SightingConnection> {
+    let first = first.unwrap_or(DEFAULT_PAGE_SIZE);
+    if first <= 0 {
+        return Err(Error::new("Invalid arguments: first must be positive"));
+    }
+    // The cursor is the id of the last sighting seen
+    let mut sightings: Vec<Sighting> = query_as(
+        r#"
+        SELECT * FROM Sighting WHERE cervidae_id = $1
+         AND EXISTS (SELECT 1 FROM Cervidae WHERE Cervidae.id = Sighting.cervidae_id
+            AND Cervidae.status = 'Approved' AND Cervidae.deleted_at IS NULL)
+         AND ($2::uuid IS NULL OR (observed_at, id) < (SELECT observed_at, id FROM Sighting WHERE id = $2))
+         ORDER BY observed_at DESC, id DESC LIMIT $3"#,
+    )
+    .bind(id)
+    .bind(after)
+    .bind(first + 1)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let has_next_page = sightings.len() as i64 > first;
+    sightings.truncate(first as usize);
+    let total_count = query_scalar!(
+        r#"
+        SELECT COUNT(*) FROM Sighting WHERE cervidae_id = $1
+         AND EXISTS (SELECT 1 FROM Cervidae WHERE Cervidae.id = Sighting.cervidae_id
+            AND Cervidae.status = 'Approved' AND Cervidae.deleted_at IS NULL)"#,
+        id
+    )
+    .fetch_one(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(SightingConnection {
+        page_info: PageInfo {
+            has_next_page: Some(has_next_page),
+            has_previous_page: Some(after.is_some()),
+            start_cursor: sightings.first().map(|sighting| sighting.id),
+            end_cursor: sightings.last().map(|sighting| sighting.id),
+            total_count,
+        },
+        edges: sightings
+            .into_iter()
+            .map(|sighting| SightingEdge {
+                cursor: sighting.id.to_string(),
+                node: sighting,
+            })
+            .collect(),
+    })
+}
+
+// Only sightings of live, approved entries turn up in geographic searches
+pub fn push_sighting_filter(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    filter: &SightingFilter,
+) {
+    query_builder.push(
+        " AND EXISTS (SELECT 1 FROM Cervidae WHERE Cervidae.id = Sighting.cervidae_id AND Cervidae.status = 'Approved' AND Cervidae.deleted_at IS NULL)",
+    );
+    if let Some(cervidae_id) = filter.cervidae_id {
+        query_builder.push(" AND Sighting.cervidae_id = ");
+        query_builder.push_bind(Uuid::from(cervidae_id));
+    }
+    if let Some(status) = filter.status {
+        query_builder.push(" AND Sighting.status = ");
+        query_builder.push_bind(status);
+    }
+    if let Some(observed_after) = filter.observed_after {
+        query_builder.push(" AND Sighting.observed_at >= ");
+        query_builder.push_bind(NaiveDateTime::from(observed_after));
+    }
+}
+
+// A box whose west edge lies east of its east edge wraps across the antimeridian
+pub fn push_bbox_condition(query_builder: &mut QueryBuilder<'_, Postgres>, bbox: BoundingBox) {
+    query_builder.push("Sighting.latitude BETWEEN ");
+    query_builder.push_bind(bbox.south);
+    query_builder.push(" AND ");
+    query_builder.push_bind(bbox.north);
+    if bbox.west <= bbox.east {
+        query_builder.push(" AND Sighting.longitude BETWEEN ");
+        query_builder.push_bind(bbox.west);
+        query_builder.push(" AND ");
+        query_builder.push_bind(bbox.east);
+    } else {
+        query_builder.push(" AND (Sighting.longitude >= ");
+        query_builder.push_bind(bbox.west);
+        query_builder.push(" OR Sighting.longitude <= ");
+        query_builder.push_bind(bbox.east);
+        query_builder.push(")");
+    }
+}
+
+pub async fn get_sightings_in_box(
+    context: &Context<'_>,
+    bbox: BoundingBox,
+    filter: &SightingFilter,
+    limit: i64,
+) -> Result<Vec<Sighting>> {
+    let mut query_builder = QueryBuilder::new("SELECT * FROM Sighting WHERE ");
+    push_bbox_condition(&mut query_builder, bbox);
+    push_sighting_filter(&mut query_builder, filter);
+    query_builder.push(" ORDER BY observed_at DESC, id DESC LIMIT ");
+    query_builder.push_bind(limit);
+    let sightings = query_builder
+        .build_query_as()
+        .fetch_all(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(sightings)
+}
+
+// Cells are aligned to multiples of the cell size from the equator and the prime
+// meridian, clipped to the globe; points on the north pole or antimeridian fold into
+// the last row or column
+pub async fn get_sighting_heatmap(
+    context: &Context<'_>,
+    bbox: BoundingBox,
+    cell_size: f64,
+    filter: &SightingFilter,
+    deer_ids: &[Uuid],
+) -> Result<Vec<HeatmapCell>> {
+    let mut query_builder = QueryBuilder::new(
+        r#"
+        SELECT GREATEST(row * size, -90) AS south, GREATEST(col * size, -180) AS west,
+            LEAST((row + 1) * size, 90) AS north, LEAST((col + 1) * size, 180) AS east,
+            latitude, longitude, count
+        FROM (
+            SELECT size,
+                LEAST(floor(Sighting.latitude / size), ceil(90 / size) - 1) AS row,
+                LEAST(floor(Sighting.longitude / size), ceil(180 / size) - 1) AS col,
+                AVG(Sighting.latitude) AS latitude, AVG(Sighting.longitude) AS longitude,
+                COUNT(*) AS count
+            FROM Sighting CROSS JOIN (SELECT "#,
+    );
+    query_builder.push_bind(cell_size);
+    query_builder.push("::DOUBLE PRECISION AS size) AS grid WHERE ");
+    push_bbox_condition(&mut query_builder, bbox);
+    push_sighting_filter(&mut query_builder, filter);
+    if !deer_ids.is_empty() {
+        query_builder.push(" AND Sighting.cervidae_id = ANY(");
+        query_builder.push_bind(deer_ids.to_vec());
+        query_builder.push(")");
+    }
+    query_builder.push(" GROUP BY size, row, col) AS cells ORDER BY south, west");
+    let cells = query_builder
+        .build_query_as()
+        .fetch_all(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(cells)
+}
+
+// The latitude band (and longitude band, away from the poles and antimeridian)
+// lets the location index narrow the rows before the exact distance is computed
+pub async fn get_sightings_near(
+    context: &Context<'_>,
+    (latitude, longitude): (f64, f64),
+    radius_km: f64,
+    filter: &SightingFilter,
+    limit: i64,
+) -> Result<Vec<NearbySighting>> {
+    const KM_PER_DEGREE: f64 = 111.19;
+    let lat_delta = radius_km / KM_PER_DEGREE;
+    let mut query_builder = QueryBuilder::new("SELECT * FROM (SELECT Sighting.*, haversine_km(");
+    query_builder.push_bind(latitude);
+    query_builder.push(", ");
+    query_builder.push_bind(longitude);
+    query_builder
+        .push(", latitude, longitude) AS distance_km FROM Sighting WHERE latitude BETWEEN ");
+    query_builder.push_bind(latitude - lat_delta);
+    query_builder.push(" AND ");
+    query_builder.push_bind(latitude + lat_delta);
+    if latitude.abs() + lat_delta < 90.0 {
+        let lon_delta = lat_delta / (latitude.abs() + lat_delta).to_radians().cos();
+        if longitude.abs() + lon_delta <= 180.0 {
+            query_builder.push(" AND longitude BETWEEN ");
+            query_builder.push_bind(longitude - lon_delta);
+            query_builder.push(" AND ");
+            query_builder.push_bind(longitude + lon_delta);
+        }
+    }
+    push_sighting_filter(&mut query_builder, filter);
+    query_builder.push(") AS Sighting WHERE distance_km <= ");
+    query_builder.push_bind(radius_km);
+    query_builder.push(" ORDER BY distance_km, observed_at DESC LIMIT ");
+    query_builder.push_bind(limit);
+    let sightings = query_builder
+        .build_query_as()
+        .fetch_all(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(sightings)
+}
+
+pub async fn get_deer_aliases(context: &Context<'_>, id: Uuid) -> Result<Vec<String>> {
+    let aliases = query_scalar!(
+        "SELECT alias FROM Deer_Alias WHERE cervidae_id = $1 ORDER BY lower(alias)",
+        id
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(aliases)
+}
+
+pub async fn get_tag(context: &Context<'_>, id: Uuid) -> Result<Option<Tag>> {
+    let tag = query_as!(Tag, "SELECT * FROM Tag WHERE id = $1", id)
         .fetch_optional(context.data_unchecked::<PgPool>())
         .await
         .map_err(|e| e.to_string())?;
 
+    Ok(tag)
+}
+
+// Synonym slugs resolve to the tag they stand for
+pub async fn get_tag_by_slug(context: &Context<'_>, slug: &str) -> Result<Option<Tag>> {
+    let tag = query_as!(Tag, "SELECT * FROM Tag WHERE id = resolve_tag($1)", slug)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(tag)
+}
+
+pub async fn get_tag_synonyms(context: &Context<'_>, id: Uuid) -> Result<Vec<String>> {
+    let synonyms = query_scalar!(
+        "SELECT slug FROM Tag_Synonym WHERE tag_id = $1 ORDER BY slug",
+        id
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(synonyms)
+}
+
+// Banned tags are left out even when hidden tags are asked for
+pub async fn get_deer_tags(
+    context: &Context<'_>,
+    id: Uuid,
+    include_hidden: bool,
+) -> Result<Vec<DeerTag>> {
+    let tags = query_as!(
+        DeerTag,
+        r#"
+        SELECT Deer_Tag.* FROM Deer_Tag
+        JOIN Tag ON Tag.id = Deer_Tag.tag_id AND NOT Tag.banned
+        WHERE Deer_Tag.cervidae_id = $1 AND ($2 OR Deer_Tag.score > 0)
+        ORDER BY Deer_Tag.score DESC, Tag.slug"#,
+        id,
+        include_hidden,
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(tags)
+}
+
+pub async fn get_deer_tag(
+    context: &Context<'_>,
+    cervidae_id: Uuid,
+    tag_id: Uuid,
+) -> Result<Option<DeerTag>> {
+    let tag = query_as!(
+        DeerTag,
+        "SELECT * FROM Deer_Tag WHERE cervidae_id = $1 AND tag_id = $2",
+        cervidae_id,
+        tag_id,
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(tag)
+}
+
+pub async fn get_deer_tag_vote(
+    context: &Context<'_>,
+    cervidae_id: Uuid,
+    tag_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<i16>> {
+    let value = query_scalar!(
+        "SELECT value FROM Deer_Tag_Vote WHERE cervidae_id = $1 AND tag_id = $2 AND user_id = $3",
+        cervidae_id,
+        tag_id,
+        user_id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(value)
+}
+
+pub async fn get_popular_tags(
+    context: &Context<'_>,
+    first: Option<i64>,
+) -> Result<Vec<PopularTag>> {
+    let first = first.unwrap_or(DEFAULT_PAGE_SIZE);
+    if first <= 0 {
+        return Err(Error::new("Invalid arguments: first must be positive"));
+    }
+    let tags = query_as(
+        r#"
+        SELECT Tag.*, COUNT(*) AS deer_count FROM Tag
+        JOIN Deer_Tag ON Deer_Tag.tag_id = Tag.id AND Deer_Tag.score > 0
+        JOIN Cervidae ON Cervidae.id = Deer_Tag.cervidae_id
+            AND Cervidae.status = 'Approved' AND Cervidae.deleted_at IS NULL
+        WHERE NOT Tag.banned
+        GROUP BY Tag.id
+        ORDER BY deer_count DESC, Tag.slug
+        LIMIT $1"#,
+    )
+    .bind(first)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(tags)
+}
+
+pub async fn get_deer_by_taxon(context: &Context<'_>, id: Uuid) -> Result<Vec<Deer>> {
+    let deer = query_as(
+        r#"
+        SELECT * FROM Cervidae WHERE taxon_id IN (SELECT taxon_subtree($1))
+         AND status = 'Approved' AND deleted_at IS NULL ORDER BY name"#,
+    )
+    .bind(id)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
     Ok(deer)
 }
 
+pub async fn get_taxon(context: &Context<'_>, id: Uuid) -> Result<Option<Taxon>> {
+    let taxon = query_as("SELECT * FROM Taxon WHERE id = $1")
+        .bind(id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(taxon)
+}
+
+// The families when no parent is given
+pub async fn get_taxon_children(context: &Context<'_>, parent: Option<Uuid>) -> Result<Vec<Taxon>> {
+    let taxa = query_as(
+        "SELECT * FROM Taxon WHERE parent_id IS NOT DISTINCT FROM $1 ORDER BY scientific_name",
+    )
+    .bind(parent)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(taxa)
+}
+
+pub async fn get_taxon_ancestors(context: &Context<'_>, id: Uuid) -> Result<Vec<Taxon>> {
+    let taxa = query_as(
+        r#"
+        WITH RECURSIVE ancestors AS (
+            SELECT Taxon.* FROM Taxon WHERE id = (SELECT parent_id FROM Taxon WHERE id = $1)
+            UNION ALL
+            SELECT Taxon.* FROM Taxon JOIN ancestors ON Taxon.id = ancestors.parent_id
+        )
+        SELECT * FROM ancestors ORDER BY rank"#,
+    )
+    .bind(id)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(taxa)
+}
+
+pub async fn get_genus_stats(context: &Context<'_>) -> Result<Vec<GenusStats>> {
+    let stats = query_as(
+        r#"
+        SELECT Taxon.*,
+            COUNT(Cervidae.id) AS deer_count,
+            COALESCE(SUM(Cervidae.kill_count), 0)::BIGINT AS total_kill_count,
+            AVG(Danger_Score.score) AS average_danger_score,
+            MAX(Danger_Score.score) AS max_danger_score
+        FROM Taxon
+        LEFT JOIN Cervidae ON Cervidae.taxon_id IN (SELECT taxon_subtree(Taxon.id))
+            AND Cervidae.status = 'Approved' AND Cervidae.deleted_at IS NULL
+        LEFT JOIN Danger_Score ON Danger_Score.cervidae_id = Cervidae.id
+        WHERE Taxon.rank = 'Genus'
+        GROUP BY Taxon.id
+        ORDER BY total_kill_count DESC, Taxon.scientific_name"#,
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(stats)
+}
+
+// Shadow-hidden posts are only listed for their author. The cookie alone names the
+// viewer: a revoked session can at worst still see its own posts
+pub fn viewer_id(context: &Context<'_>) -> Option<Uuid> {
+    super::decode_session_cookie(context)
+        .ok()
+        .and_then(|claims| Uuid::parse_str(&claims.sub).ok())
+}
+
+// Deleted comments are still returned so replies can point at their placeholder
 pub async fn get_comment(context: &Context<'_>, id: Uuid) -> Result<Option<Comment>> {
     let comment = query_as!(Comment, "SELECT * FROM Comment WHERE id = $1", id)
         .fetch_optional(context.data_unchecked::<PgPool>())
@@ -31,56 +564,1051 @@ pub async fn get_comment(context: &Context<'_>, id: Uuid) -> Result<Option<Comme
     Ok(comment)
 }
 
+pub async fn get_review(
+    context: &Context<'_>,
+    user_id: Uuid,
+    cervidae_id: Uuid,
+) -> Result<Option<Review>> {
+    let review = query_as!(
+        Review,
+        "SELECT * FROM Review WHERE user_id = $1 AND cervidae_id = $2 AND deleted_at IS NULL",
+        user_id,
+        cervidae_id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(review)
+}
+
+pub async fn get_review_by_id(context: &Context<'_>, id: Uuid) -> Result<Option<Review>> {
+    let review = query_as!(
+        Review,
+        r#"
+        SELECT * FROM Review WHERE id = $1 AND deleted_at IS NULL
+         AND (NOT shadow_hidden OR user_id = $2)"#,
+        id,
+        viewer_id(context)
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(review)
+}
+
 pub async fn get_reviews_by_deer(context: &Context<'_>, id: Uuid) -> Result<Vec<Review>> {
-    let reviews = query_as!(Review, "SELECT * FROM Review WHERE cervidae_id = $1", id)
+    let reviews = query_as!(
+        Review,
+        r#"
+        SELECT * FROM Review WHERE cervidae_id = $1 AND deleted_at IS NULL
+         AND (NOT shadow_hidden OR user_id = $2)"#,
+        id,
+        viewer_id(context)
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(reviews)
+}
+
+pub async fn get_reviews_by_user(context: &Context<'_>, id: Uuid) -> Result<Vec<Review>> {
+    let reviews = query_as!(
+        Review,
+        r#"
+        SELECT * FROM Review WHERE user_id = $1 AND deleted_at IS NULL
+         AND (NOT shadow_hidden OR user_id = $2)
+         AND cervidae_id IN (SELECT id FROM Cervidae WHERE deleted_at IS NULL)"#,
+        id,
+        viewer_id(context)
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(reviews)
+}
+
+pub async fn get_comments_by_deer(context: &Context<'_>, id: Uuid) -> Result<Vec<Comment>> {
+    let comments = query_as!(
+        Comment,
+        r#"
+        SELECT * FROM Comment WHERE cervidae_id = $1
+         AND (deleted_at IS NULL OR comment_has_live_descendant(id))
+         AND (NOT shadow_hidden OR user_id = $2)"#,
+        id,
+        viewer_id(context)
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(comments)
+}
+
+pub async fn get_comments_by_user(context: &Context<'_>, id: Uuid) -> Result<Vec<Comment>> {
+    let comments = query_as!(
+        Comment,
+        r#"
+        SELECT * FROM Comment WHERE user_id = $1 AND deleted_at IS NULL
+         AND (NOT shadow_hidden OR user_id = $2)
+         AND cervidae_id IN (SELECT id FROM Cervidae WHERE deleted_at IS NULL)"#,
+        id,
+        viewer_id(context)
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(comments)
+}
+
+pub async fn get_crime(context: &Context<'_>, id: Uuid) -> Result<Option<Crime>> {
+    let crime = query_as!(
+        Crime,
+        "SELECT * FROM Crime WHERE id = $1 AND deleted_at IS NULL",
+        id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(crime)
+}
+
+pub async fn get_crime_category(
+    context: &Context<'_>,
+    id: Uuid,
+) -> Result<Option<CrimeCategoryNode>> {
+    let category = query_as!(
+        CrimeCategoryNode,
+        "SELECT * FROM Crime_Category WHERE id = $1",
+        id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(category)
+}
+
+pub async fn get_crime_category_by_slug(
+    context: &Context<'_>,
+    slug: &str,
+) -> Result<Option<CrimeCategoryNode>> {
+    let category = query_as!(
+        CrimeCategoryNode,
+        "SELECT * FROM Crime_Category WHERE slug = $1",
+        slug
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(category)
+}
+
+// The top-level category a category sits under, or the category itself at the top
+pub async fn get_crime_category_root(
+    context: &Context<'_>,
+    id: Uuid,
+) -> Result<Option<CrimeCategoryNode>> {
+    let category = query_as!(
+        CrimeCategoryNode,
+        r#"
+        SELECT * FROM Crime_Category WHERE id = (
+            WITH RECURSIVE ancestors AS (
+                SELECT id, parent_id FROM Crime_Category WHERE id = $1
+                UNION ALL
+                SELECT Crime_Category.id, Crime_Category.parent_id FROM Crime_Category
+                JOIN ancestors ON Crime_Category.id = ancestors.parent_id
+            )
+            SELECT id FROM ancestors WHERE parent_id IS NULL
+        )"#,
+        id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(category)
+}
+
+// The top-level categories when no parent is given
+pub async fn get_crime_category_children(
+    context: &Context<'_>,
+    parent: Option<Uuid>,
+) -> Result<Vec<CrimeCategoryNode>> {
+    let categories = query_as!(
+        CrimeCategoryNode,
+        "SELECT * FROM Crime_Category WHERE parent_id IS NOT DISTINCT FROM $1 ORDER BY name",
+        parent
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(categories)
+}
+
+pub async fn get_crimes_by_category(
+    context: &Context<'_>,
+    id: Uuid,
+    include_subcategories: bool,
+) -> Result<Vec<Crime>> {
+    let crimes = query_as(
+        r#"
+        SELECT * FROM Crime WHERE deleted_at IS NULL
+         AND (category_id = $1 OR ($2 AND category_id IN (SELECT crime_category_subtree($1))))
+         ORDER BY name"#,
+    )
+    .bind(id)
+    .bind(include_subcategories)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(crimes)
+}
+
+// Statutes of deleted crimes are left out
+pub async fn get_statutes(
+    context: &Context<'_>,
+    crime_id: Option<Uuid>,
+    jurisdiction: Option<&str>,
+) -> Result<Vec<Statute>> {
+    let statutes = query_as!(
+        Statute,
+        r#"
+        SELECT Statute.* FROM Statute
+        JOIN Crime ON Crime.id = Statute.crime_id AND Crime.deleted_at IS NULL
+        WHERE ($1::uuid IS NULL OR Statute.crime_id = $1)
+         AND ($2::text IS NULL OR lower(Statute.jurisdiction) = lower($2))
+        ORDER BY Statute.jurisdiction, Statute.code"#,
+        crime_id,
+        jurisdiction,
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(statutes)
+}
+
+pub async fn get_statute(context: &Context<'_>, id: Uuid) -> Result<Option<Statute>> {
+    let statute = query_as!(Statute, "SELECT * FROM Statute WHERE id = $1", id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(statute)
+}
+
+pub async fn get_crimes_by_deer(context: &Context<'_>, id: Uuid) -> Result<Vec<Crime>> {
+    let crimes = query_as!(Crime, "SELECT * FROM Crime WHERE id IN (SELECT crime_id FROM Crime_Cervidae WHERE cervidae_id = $1) AND deleted_at IS NULL", id)
         .fetch_all(context.data_unchecked::<PgPool>())
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(reviews)
+    Ok(crimes)
 }
 
-pub async fn get_reviews_by_user(context: &Context<'_>, id: Uuid) -> Result<Vec<Review>> {
-    let reviews = query_as!(Review, "SELECT * FROM Review WHERE user_id = $1", id)
+// Incidents of deleted crimes are hidden along with the crime
+pub async fn get_incidents_by_deer(context: &Context<'_>, id: Uuid) -> Result<Vec<Incident>> {
+    let incidents = query_as!(
+        Incident,
+        r#"
+        SELECT Incident.* FROM Incident
+        JOIN Crime ON Crime.id = Incident.crime_id AND Crime.deleted_at IS NULL
+        WHERE Incident.cervidae_id = $1 ORDER BY occurred_at DESC, id"#,
+        id
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(incidents)
+}
+
+pub async fn get_incidents_by_crime(context: &Context<'_>, id: Uuid) -> Result<Vec<Incident>> {
+    let incidents = query_as!(
+        Incident,
+        r#"
+        SELECT Incident.* FROM Incident
+        JOIN Cervidae ON Cervidae.id = Incident.cervidae_id AND Cervidae.deleted_at IS NULL
+        WHERE Incident.crime_id = $1 ORDER BY occurred_at DESC, id"#,
+        id
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(incidents)
+}
+
+pub async fn get_incident(context: &Context<'_>, id: Uuid) -> Result<Option<Incident>> {
+    let incident = query_as!(Incident, "SELECT * FROM Incident WHERE id = $1", id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(incident)
+}
+
+pub async fn get_case(context: &Context<'_>, id: Uuid) -> Result<Option<Case>> {
+    let case = query_as!(Case, "SELECT * FROM Court_Case WHERE id = $1", id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(case)
+}
+
+pub async fn get_cases_by_deer(context: &Context<'_>, id: Uuid) -> Result<Vec<Case>> {
+    let cases = query_as!(
+        Case,
+        "SELECT * FROM Court_Case WHERE cervidae_id = $1 ORDER BY opened_at DESC, id",
+        id
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(cases)
+}
+
+pub async fn get_charge(context: &Context<'_>, id: Uuid) -> Result<Option<Charge>> {
+    let charge = query_as("SELECT * FROM Charge WHERE id = $1")
+        .bind(id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(charge)
+}
+
+pub async fn get_charges_by_case(context: &Context<'_>, id: Uuid) -> Result<Vec<Charge>> {
+    let charges = query_as("SELECT * FROM Charge WHERE case_id = $1 ORDER BY created_at, id")
+        .bind(id)
         .fetch_all(context.data_unchecked::<PgPool>())
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(reviews)
+    Ok(charges)
 }
 
-pub async fn get_comments_by_deer(context: &Context<'_>, id: Uuid) -> Result<Vec<Comment>> {
-    let comments = query_as!(Comment, "SELECT * FROM Comment WHERE cervidae_id = $1", id)
+// Charges of deleted crimes are hidden along with the crime
+pub async fn get_charges_by_deer(
+    context: &Context<'_>,
+    id: Uuid,
+    verdict: Verdict,
+) -> Result<Vec<Charge>> {
+    let charges = query_as(
+        r#"
+        SELECT Charge.* FROM Charge
+        JOIN Court_Case ON Court_Case.id = Charge.case_id
+        JOIN Crime ON Crime.id = Charge.crime_id AND Crime.deleted_at IS NULL
+        WHERE Court_Case.cervidae_id = $1 AND Charge.verdict = $2
+        ORDER BY Charge.decided_at DESC NULLS LAST, Charge.created_at DESC, Charge.id"#,
+    )
+    .bind(id)
+    .bind(verdict)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(charges)
+}
+
+pub async fn get_convicted_crimes(context: &Context<'_>, id: Uuid) -> Result<Vec<Crime>> {
+    let crimes = query_as(
+        r#"
+        SELECT * FROM Crime WHERE deleted_at IS NULL AND id IN (
+            SELECT Charge.crime_id FROM Charge
+            JOIN Court_Case ON Court_Case.id = Charge.case_id
+            WHERE Court_Case.cervidae_id = $1 AND Charge.verdict = 'Guilty'
+        ) ORDER BY name"#,
+    )
+    .bind(id)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(crimes)
+}
+
+pub async fn get_hearings_by_case(context: &Context<'_>, id: Uuid) -> Result<Vec<Hearing>> {
+    let hearings = query_as!(
+        Hearing,
+        "SELECT * FROM Hearing WHERE case_id = $1 ORDER BY scheduled_at, id",
+        id
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(hearings)
+}
+
+pub async fn get_review_stats(context: &Context<'_>, id: Uuid) -> Result<Option<ReviewStats>> {
+    let stats = query_as!(
+        ReviewStats,
+        "SELECT * FROM Review_Stats WHERE cervidae_id = $1",
+        id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(stats)
+}
+
+pub async fn get_danger_score(context: &Context<'_>, id: Uuid) -> Result<Option<DangerScore>> {
+    let score = query_as!(
+        DangerScore,
+        "SELECT * FROM Danger_Score WHERE cervidae_id = $1",
+        id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(score)
+}
+
+pub async fn get_reply_count(context: &Context<'_>, id: Uuid) -> Result<i64> {
+    let count = query_scalar!(
+        r#"
+        SELECT COUNT(*) FROM Comment WHERE parent_id = $1
+         AND (deleted_at IS NULL OR comment_has_live_descendant(id))
+         AND (NOT shadow_hidden OR user_id = $2)"#,
+        id,
+        viewer_id(context)
+    )
+    .fetch_one(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(count.unwrap_or(0))
+}
+
+pub async fn get_replies_page(
+    context: &Context<'_>,
+    id: Uuid,
+    sort: CommentSort,
+    first: Option<i64>,
+    after: Option<Uuid>,
+) -> Result<CommentConnection> {
+    let first = first.unwrap_or(DEFAULT_PAGE_SIZE);
+    if first <= 0 {
+        return Err(Error::new("Invalid arguments: first must be positive"));
+    }
+    // The cursor is the id of the last reply seen
+    let mut query_builder = QueryBuilder::new("SELECT * FROM Comment WHERE parent_id = ");
+    query_builder.push_bind(id);
+    query_builder.push(" AND (deleted_at IS NULL OR comment_has_live_descendant(id))");
+    query_builder.push(" AND (NOT shadow_hidden OR user_id = ");
+    query_builder.push_bind(viewer_id(context));
+    query_builder.push(")");
+    push_voted_page(
+        &mut query_builder,
+        "Comment",
+        comment_sort_key(sort),
+        after,
+        first + 1,
+    );
+    let mut replies: Vec<Comment> = query_builder
+        .build_query_as()
         .fetch_all(context.data_unchecked::<PgPool>())
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(comments)
+    let has_next_page = replies.len() as i64 > first;
+    replies.truncate(first as usize);
+    let total_count = get_reply_count(context, id).await?;
+    // Anything left at or ahead of the cursor is a previous page; the cursor row may be gone
+    let has_previous_page = match after {
+        Some(after) => {
+            let mut query_builder =
+                QueryBuilder::new("SELECT EXISTS (SELECT 1 FROM Comment WHERE parent_id = ");
+            query_builder.push_bind(id);
+            query_builder.push(" AND (deleted_at IS NULL OR comment_has_live_descendant(id))");
+            query_builder.push(" AND (NOT shadow_hidden OR user_id = ");
+            query_builder.push_bind(viewer_id(context));
+            query_builder.push(")");
+            push_voted_cursor(
+                &mut query_builder,
+                "Comment",
+                comment_sort_key(sort),
+                after,
+                true,
+            );
+            query_builder.push(")");
+            query_builder
+                .build_query_scalar()
+                .fetch_one(context.data_unchecked::<PgPool>())
+                .await
+                .map_err(|e| e.to_string())?
+        }
+        None => false,
+    };
+
+    Ok(CommentConnection {
+        page_info: PageInfo {
+            has_next_page: Some(has_next_page),
+            has_previous_page: Some(has_previous_page),
+            start_cursor: replies.first().map(|reply| reply.id),
+            end_cursor: replies.last().map(|reply| reply.id),
+            total_count: Some(total_count),
+        },
+        edges: replies
+            .into_iter()
+            .map(|reply| CommentEdge {
+                cursor: reply.id.to_string(),
+                node: reply,
+            })
+            .collect(),
+    })
 }
 
-pub async fn get_comments_by_user(context: &Context<'_>, id: Uuid) -> Result<Vec<Comment>> {
-    let comments = query_as!(Comment, "SELECT * FROM Comment WHERE user_id = $1", id)
+pub async fn get_reviews_page(
+    context: &Context<'_>,
+    id: Uuid,
+    sort: ReviewSort,
+    first: Option<i64>,
+    after: Option<Uuid>,
+) -> Result<ReviewConnection> {
+    let first = first.unwrap_or(DEFAULT_PAGE_SIZE);
+    if first <= 0 {
+        return Err(Error::new("Invalid arguments: first must be positive"));
+    }
+    // The cursor is the id of the last review seen
+    let mut query_builder = QueryBuilder::new("SELECT * FROM Review WHERE cervidae_id = ");
+    query_builder.push_bind(id);
+    query_builder.push(" AND deleted_at IS NULL AND (NOT shadow_hidden OR user_id = ");
+    query_builder.push_bind(viewer_id(context));
+    query_builder.push(")");
+    push_voted_page(
+        &mut query_builder,
+        "Review",
+        review_sort_key(sort),
+        after,
+        first + 1,
+    );
+    let mut reviews: Vec<Review> = query_builder
+        .build_query_as()
         .fetch_all(context.data_unchecked::<PgPool>())
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(comments)
+    let has_next_page = reviews.len() as i64 > first;
+    reviews.truncate(first as usize);
+    let total_count = query_scalar!(
+        r#"
+        SELECT COUNT(*) FROM Review WHERE cervidae_id = $1 AND deleted_at IS NULL
+         AND (NOT shadow_hidden OR user_id = $2)"#,
+        id,
+        viewer_id(context)
+    )
+    .fetch_one(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(ReviewConnection {
+        page_info: PageInfo {
+            has_next_page: Some(has_next_page),
+            has_previous_page: Some(after.is_some()),
+            start_cursor: reviews.first().map(|review| review.id),
+            end_cursor: reviews.last().map(|review| review.id),
+            total_count,
+        },
+        edges: reviews
+            .into_iter()
+            .map(|review| ReviewEdge {
+                cursor: review.id.to_string(),
+                node: review,
+            })
+            .collect(),
+    })
 }
 
-pub async fn get_crime(context: &Context<'_>, id: Uuid) -> Result<Option<Crime>> {
-    let crime = query_as!(Crime, "SELECT * FROM Crime WHERE id = $1", id)
+pub async fn get_review_vote(
+    context: &Context<'_>,
+    review_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<i16>> {
+    let value = query_scalar!(
+        "SELECT value FROM Review_Vote WHERE review_id = $1 AND user_id = $2",
+        review_id,
+        user_id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(value)
+}
+
+pub async fn get_comment_vote(
+    context: &Context<'_>,
+    comment_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<i16>> {
+    let value = query_scalar!(
+        "SELECT value FROM Comment_Vote WHERE comment_id = $1 AND user_id = $2",
+        comment_id,
+        user_id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(value)
+}
+
+pub async fn get_moderation_history(
+    context: &Context<'_>,
+    id: Uuid,
+) -> Result<Vec<DeerModerationEvent>> {
+    let events = query_as(
+        "SELECT * FROM Deer_Moderation_Event WHERE cervidae_id = $1 ORDER BY created_at DESC",
+    )
+    .bind(id)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(events)
+}
+
+pub async fn get_moderation_event(
+    context: &Context<'_>,
+    id: Uuid,
+) -> Result<Option<DeerModerationEvent>> {
+    let event = query_as("SELECT * FROM Deer_Moderation_Event WHERE id = $1")
+        .bind(id)
         .fetch_optional(context.data_unchecked::<PgPool>())
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(crime)
+    Ok(event)
 }
 
-pub async fn get_crimes_by_deer(context: &Context<'_>, id: Uuid) -> Result<Vec<Crime>> {
-    let crimes = query_as!(Crime, "SELECT * FROM Crime WHERE id IN (SELECT crime_id FROM Crime_Cervidae WHERE cervidae_id = $1)", id)
-        .fetch_all(context.data_unchecked::<PgPool>())
+pub async fn get_appeals_by_deer(context: &Context<'_>, id: Uuid) -> Result<Vec<DeerAppeal>> {
+    let appeals =
+        query_as("SELECT * FROM Deer_Appeal WHERE cervidae_id = $1 ORDER BY created_at DESC")
+            .bind(id)
+            .fetch_all(context.data_unchecked::<PgPool>())
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok(appeals)
+}
+
+// Open appeals oldest first, leaving out those the moderator helped reject
+pub async fn get_appeal_queue(
+    context: &Context<'_>,
+    moderator_id: Uuid,
+    first: Option<i64>,
+) -> Result<Vec<DeerAppeal>> {
+    let appeals = query_as(
+        r#"
+        SELECT Deer_Appeal.* FROM Deer_Appeal
+         JOIN Cervidae ON Cervidae.id = Deer_Appeal.cervidae_id AND Cervidae.deleted_at IS NULL
+         WHERE Deer_Appeal.status = 'Open' AND NOT took_part_in_rejection(Deer_Appeal.id, $1)
+         ORDER BY Deer_Appeal.created_at LIMIT $2"#,
+    )
+    .bind(moderator_id)
+    .bind(first.unwrap_or(DEFAULT_PAGE_SIZE))
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(appeals)
+}
+
+pub async fn get_latest_rejection_reason(
+    context: &Context<'_>,
+    id: Uuid,
+) -> Result<Option<String>> {
+    let reason = query_scalar!(
+        r#"
+        SELECT reason FROM Deer_Moderation_Event
+         WHERE cervidae_id = $1 AND to_status = 'Rejected'
+         ORDER BY created_at DESC LIMIT 1"#,
+        id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(reason.flatten())
+}
+
+pub async fn get_threat_config(context: &Context<'_>) -> Result<ThreatConfig> {
+    let config = query_as!(ThreatConfig, "SELECT * FROM Threat_Config")
+        .fetch_one(context.data_unchecked::<PgPool>())
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(crimes)
+    Ok(config)
+}
+
+// Deleted crimes, and incidents of them, are left out
+pub async fn get_threat_inputs(
+    context: &Context<'_>,
+    id: Uuid,
+    window_days: i32,
+) -> Result<ThreatInputs> {
+    let inputs = query_as!(
+        ThreatInputs,
+        r#"
+        SELECT
+            (SELECT MAX(Crime.severity) FROM Crime
+             JOIN Crime_Cervidae ON Crime_Cervidae.crime_id = Crime.id
+             WHERE Crime_Cervidae.cervidae_id = $1 AND Crime.deleted_at IS NULL) AS max_severity,
+            (SELECT COUNT(*) FROM Incident
+             JOIN Crime ON Crime.id = Incident.crime_id AND Crime.deleted_at IS NULL
+             WHERE Incident.cervidae_id = $1 AND Incident.verified_at IS NOT NULL
+                AND Incident.occurred_at > NOW() - make_interval(days => $2)) AS "recent_incidents!",
+            (SELECT weighted_score FROM Danger_Score WHERE cervidae_id = $1) AS danger_score"#,
+        id,
+        window_days,
+    )
+    .fetch_one(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(inputs)
+}
+
+pub async fn get_moderation_config(context: &Context<'_>) -> Result<ModerationConfig> {
+    let config = query_as!(ModerationConfig, "SELECT * FROM Moderation_Config")
+        .fetch_one(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(config)
+}
+
+pub async fn get_report(context: &Context<'_>, id: Uuid) -> Result<Option<ContentReport>> {
+    let report = query_as("SELECT * FROM Content_Report WHERE id = $1")
+        .bind(id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
+
+pub async fn get_open_report_count(
+    context: &Context<'_>,
+    target_type: ReportTarget,
+    target_id: Uuid,
+) -> Result<i64> {
+    let count = query_scalar(
+        "SELECT COUNT(*) FROM Content_Report WHERE target_type = $1 AND target_id = $2 AND status = 'Open'",
+    )
+    .bind(target_type)
+    .bind(target_id)
+    .fetch_one(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(count)
+}
+
+// Reads deleted rows too, since reported content may have been hidden
+pub async fn get_report_excerpt(
+    context: &Context<'_>,
+    target_type: ReportTarget,
+    target_id: Uuid,
+) -> Result<Option<String>> {
+    let sql = match target_type {
+        ReportTarget::Deer => {
+            "SELECT name || COALESCE(E'\n' || description, '') FROM Cervidae WHERE id = $1"
+        }
+        ReportTarget::Review => "SELECT title || E'\n' || body FROM Review WHERE id = $1",
+        ReportTarget::Comment => "SELECT content FROM Comment WHERE id = $1",
+        ReportTarget::User => "SELECT name FROM Users WHERE id = $1",
+    };
+    let excerpt = query_scalar(sql)
+        .bind(target_id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(excerpt)
+}
+
+pub async fn get_report_queue(
+    context: &Context<'_>,
+    target_type: Option<ReportTarget>,
+    first: Option<i64>,
+    after: Option<Uuid>,
+) -> Result<ContentReportConnection> {
+    let first = first.unwrap_or(DEFAULT_PAGE_SIZE);
+    if first <= 0 {
+        return Err(Error::new("Invalid arguments: first must be positive"));
+    }
+    // Open reports read oldest first; the cursor is the id of the last report seen
+    let mut reports: Vec<ContentReport> = query_as(
+        r#"
+        SELECT * FROM Content_Report WHERE status = 'Open'
+         AND ($1::Report_Target IS NULL OR target_type = $1)
+         AND ($2::uuid IS NULL OR (created_at, id) > (SELECT created_at, id FROM Content_Report WHERE id = $2))
+         ORDER BY created_at, id LIMIT $3"#,
+    )
+    .bind(target_type)
+    .bind(after)
+    .bind(first + 1)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let has_next_page = reports.len() as i64 > first;
+    reports.truncate(first as usize);
+    let total_count = query_scalar(
+        "SELECT COUNT(*) FROM Content_Report WHERE status = 'Open' AND ($1::Report_Target IS NULL OR target_type = $1)",
+    )
+    .bind(target_type)
+    .fetch_one(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(ContentReportConnection {
+        page_info: PageInfo {
+            has_next_page: Some(has_next_page),
+            has_previous_page: Some(after.is_some()),
+            start_cursor: reports.first().map(|report| report.id),
+            end_cursor: reports.last().map(|report| report.id),
+            total_count: Some(total_count),
+        },
+        edges: reports
+            .into_iter()
+            .map(|report| ContentReportEdge {
+                cursor: report.id.to_string(),
+                node: report,
+            })
+            .collect(),
+    })
+}
+
+pub async fn get_automod_rule(context: &Context<'_>, id: Uuid) -> Result<Option<AutomodRule>> {
+    let rule = query_as("SELECT * FROM Automod_Rule WHERE id = $1")
+        .bind(id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(rule)
+}
+
+pub async fn get_automod_hit(context: &Context<'_>, id: Uuid) -> Result<Option<AutomodHit>> {
+    let hit = query_as("SELECT * FROM Automod_Hit WHERE id = $1")
+        .bind(id)
+        .fetch_optional(context.data_unchecked::<PgPool>())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(hit)
+}
+
+pub async fn get_automod_hits(
+    context: &Context<'_>,
+    target_type: Option<ReportTarget>,
+    target_id: Option<Uuid>,
+    action: Option<AutomodAction>,
+    first: Option<i64>,
+) -> Result<Vec<AutomodHit>> {
+    let hits = query_as(
+        r#"
+        SELECT * FROM Automod_Hit
+         WHERE ($1::Report_Target IS NULL OR target_type = $1)
+         AND ($2::uuid IS NULL OR target_id = $2)
+         AND ($3::Automod_Action IS NULL OR action = $3)
+         ORDER BY created_at DESC LIMIT $4"#,
+    )
+    .bind(target_type)
+    .bind(target_id)
+    .bind(action)
+    .bind(first.unwrap_or(DEFAULT_PAGE_SIZE))
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(hits)
+}
+
+// Held or shadow-hidden content still waiting for a moderator, one hit per item, oldest first
+pub async fn get_automod_hold_queue(
+    context: &Context<'_>,
+    first: Option<i64>,
+) -> Result<Vec<AutomodHit>> {
+    let hits = query_as(
+        r#"
+        SELECT * FROM (
+            SELECT DISTINCT ON (target_type, target_id) * FROM Automod_Hit
+             WHERE action IN ('Hold', 'ShadowHide') AND reviewed_at IS NULL AND target_id IS NOT NULL
+             ORDER BY target_type, target_id, created_at
+        ) AS held ORDER BY created_at LIMIT $1"#,
+    )
+    .bind(first.unwrap_or(DEFAULT_PAGE_SIZE))
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(hits)
+}
+
+pub async fn get_vote_tally(context: &Context<'_>, id: Uuid) -> Result<ModerationTally> {
+    // The quorum rules ride along so the tally never has to fetch them itself
+    let mut tally: ModerationTally = query_as(
+        "SELECT tally.*, Moderation_Config.approve_quorum, Moderation_Config.voting_window_hours \
+         FROM deer_vote_tally($1) AS tally, Moderation_Config",
+    )
+    .bind(id)
+    .fetch_one(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+    tally.cervidae_id = id;
+
+    Ok(tally)
+}
+
+pub async fn get_round_votes(
+    context: &Context<'_>,
+    id: Uuid,
+    round: i32,
+) -> Result<Vec<DeerModerationVote>> {
+    let votes = query_as(
+        "SELECT * FROM Deer_Moderation_Vote WHERE cervidae_id = $1 AND round = $2 ORDER BY created_at",
+    )
+    .bind(id)
+    .bind(round)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(votes)
+}
+
+pub async fn get_revision(context: &Context<'_>, id: Uuid) -> Result<Option<DeerRevision>> {
+    let revision = query_as!(
+        DeerRevision,
+        "SELECT * FROM Cervidae_Revision WHERE id = $1",
+        id
+    )
+    .fetch_optional(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(revision)
+}
+
+pub async fn get_revisions_page(
+    context: &Context<'_>,
+    id: Uuid,
+    first: Option<i64>,
+    after: Option<Uuid>,
+) -> Result<DeerRevisionConnection> {
+    let first = first.unwrap_or(DEFAULT_PAGE_SIZE);
+    if first <= 0 {
+        return Err(Error::new("Invalid arguments: first must be positive"));
+    }
+    // Revisions read newest first; the cursor is the id of the last revision seen
+    let mut revisions = query_as!(
+        DeerRevision,
+        r#"
+        SELECT * FROM Cervidae_Revision WHERE cervidae_id = $1
+         AND ($2::uuid IS NULL OR revision < (SELECT revision FROM Cervidae_Revision WHERE id = $2))
+         ORDER BY revision DESC LIMIT $3"#,
+        id,
+        after,
+        first + 1,
+    )
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let has_next_page = revisions.len() as i64 > first;
+    revisions.truncate(first as usize);
+    let total_count = query_scalar!(
+        "SELECT COUNT(*) FROM Cervidae_Revision WHERE cervidae_id = $1",
+        id
+    )
+    .fetch_one(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(DeerRevisionConnection {
+        page_info: PageInfo {
+            has_next_page: Some(has_next_page),
+            has_previous_page: Some(after.is_some()),
+            start_cursor: revisions.first().map(|revision| revision.id),
+            end_cursor: revisions.last().map(|revision| revision.id),
+            total_count,
+        },
+        edges: revisions
+            .into_iter()
+            .map(|revision| DeerRevisionEdge {
+                cursor: revision.id.to_string(),
+                node: revision,
+            })
+            .collect(),
+    })
+}
+
+pub async fn get_comment_thread(
+    context: &Context<'_>,
+    id: Uuid,
+    max_depth: i32,
+) -> Result<Vec<CommentThreadRow>> {
+    let rows = query_as(
+        r#"
+        WITH RECURSIVE thread AS (
+            SELECT Comment.*, 0 AS depth FROM Comment
+             WHERE cervidae_id = $1 AND parent_id IS NULL
+             AND (NOT shadow_hidden OR user_id = $3)
+            UNION ALL
+            SELECT Comment.*, thread.depth + 1 FROM Comment
+             JOIN thread ON Comment.parent_id = thread.id
+             WHERE thread.depth < $2
+             AND (NOT Comment.shadow_hidden OR Comment.user_id = $3)
+        )
+        SELECT * FROM thread WHERE deleted_at IS NULL OR comment_has_live_descendant(id)"#,
+    )
+    .bind(id)
+    .bind(max_depth)
+    .bind(viewer_id(context))
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows)
+}
+
+pub async fn get_leaderboard(
+    context: &Context<'_>,
+    board: LeaderboardKind,
+    period: LeaderboardPeriod,
+    first: Option<i64>,
+) -> Result<Vec<LeaderboardEntry>> {
+    let first = first.unwrap_or(LEADERBOARD_SIZE as i64);
+    if first <= 0 {
+        return Err(Error::new("Invalid arguments: first must be positive"));
+    }
+    let entries = query_as(
+        "SELECT * FROM Leaderboard_Entry WHERE board = $1 AND period = $2 ORDER BY rank LIMIT $3",
+    )
+    .bind(board)
+    .bind(period)
+    .bind(first)
+    .fetch_all(context.data_unchecked::<PgPool>())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(entries)
 }
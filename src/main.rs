@@ -7,14 +7,18 @@ use axum::{
     Extension, Json,
 };
 use dotenvy::dotenv;
-use graphql::{MutationRoot, QueryRoot};
+use graphql::{
+    geojson::sightings_geojson, moderation::settle_expired_rounds, scoring::refresh_leaderboards,
+    MutationRoot, QueryRoot,
+};
 use sqlx::PgPool;
 use std::env;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tower_cookies::{CookieManagerLayer, Cookies};
 use tower_http::cors::CorsLayer;
 use tower_http::trace::{DefaultMakeSpan, TraceLayer};
-use tracing::{info, Level};
+use tracing::{error, info, Level};
 
 pub mod graphql;
 use http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
@@ -41,8 +45,44 @@ async fn main() {
         .await
         .expect("Failed to connect to Postgres");
 
+    // periodically rebuild the precomputed leaderboards
+    let refresh_pool = pool.clone();
+    let refresh_secs = env::var("LEADERBOARD_REFRESH_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(600);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(refresh_secs));
+        loop {
+            interval.tick().await;
+            if let Err(e) = refresh_leaderboards(&refresh_pool).await {
+                error!("Failed to refresh leaderboards: {}", e);
+            }
+        }
+    });
+
+    // settle pending deer whose voting window closed without reaching a quorum
+    let settle_pool = pool.clone();
+    let settle_secs = env::var("MODERATION_SETTLE_SECS")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(300);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(settle_secs));
+        loop {
+            interval.tick().await;
+            match settle_expired_rounds(&settle_pool).await {
+                Ok(0) => {}
+                Ok(settled) => info!("Settled {} moderation rounds", settled),
+                Err(e) => error!("Failed to settle moderation rounds: {}", e),
+            }
+        }
+    });
+
     let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
-        .data(pool)
+        .data(pool.clone())
         .data(client)
         .finish();
 
@@ -77,9 +117,11 @@ async fn main() {
 
     let app = axum::Router::new()
         .route("/", get(graphiql).post(graphql_handler)) // Now extracts cookies first
+        .route("/sightings.geojson", get(sightings_geojson))
         .layer(cors)
         .layer(CookieManagerLayer::new()) // Enables cookies
-        .layer(Extension(schema)); // Inject schema
+        .layer(Extension(schema)) // Inject schema
+        .layer(Extension(pool));
 
     println!("GraphiQL IDE: http://localhost:1234");
 